@@ -1,2 +1,20 @@
+use service::auth::api_key::AuthenticatedApiKey;
+
 #[derive(Debug, Clone)]
 pub struct AccessToken(pub String);
+
+/// Personal API key presented instead of a JWT, already checked against the database.
+#[derive(Debug, Clone)]
+pub struct ApiKeyPrincipal(pub AuthenticatedApiKey);
+
+/// Address of the client the request is attributed to, used for rate limiting.
+#[derive(Debug, Clone)]
+pub struct ClientIp(pub String);
+
+/// `User-Agent` of the request, recorded with security events.
+#[derive(Debug, Clone)]
+pub struct UserAgent(pub String);
+
+/// Client supplied `X-Device-Id`, recorded with security events.
+#[derive(Debug, Clone)]
+pub struct DeviceId(pub String);
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    Context, Error,
+};
+use entity::entities::species;
+use sea_orm::DbErr;
+use service::queries::species::SpeciesQuery;
+
+use crate::{db::Database, gql::utils::db_err_to_gql};
+
+/// Species by id, batched across every pet and breed resolved at the same time.
+pub(crate) struct SpeciesLoader {
+    db: Database,
+}
+
+impl SpeciesLoader {
+    pub(crate) fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+impl Loader<i32> for SpeciesLoader {
+    type Value = species::Model;
+    type Error = Error;
+
+    async fn load(&self, ids: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+        let species = SpeciesQuery::by_ids(self.db.get_connection(), ids)
+            .await
+            .map_err(db_err_to_gql)?;
+        Ok(species.into_iter().map(|model| (model.id, model)).collect())
+    }
+}
+
+/// The species with `id`, through the schema's `SpeciesLoader`.
+pub(crate) async fn load_species(ctx: &Context<'_>, id: i32) -> Result<species::Model, Error> {
+    ctx.data::<DataLoader<SpeciesLoader>>()?
+        .load_one(id)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("Species Not Found".to_owned()).into())
+}
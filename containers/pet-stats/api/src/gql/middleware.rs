@@ -1,13 +1,24 @@
 use std::sync::Arc;
 
 use async_graphql::{
-    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
-    Request, ServerResult,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextPrepareRequest,
+    },
+    parser::types::{ExecutableDocument, Field, OperationType, Selection, SelectionSet},
+    Name, Pos, Request, ServerResult, Value, Variables,
 };
 use async_trait::async_trait;
 use config::app_config::APP_CONFIG;
-use tracing::info;
+use service::{
+    auth::google::unverified_subject,
+    rate_limit::{RateDecision, RateLimitKey, RateLimiter},
+};
+use tracing::{error, info};
+
+use crate::{context_data::ClientIp, gql::utils::rate_limited_err};
 
+// Not registered on the schema yet.
+#[allow(dead_code)]
 pub(crate) struct AuthExtension;
 
 #[async_trait]
@@ -45,3 +56,125 @@ impl ExtensionFactory for AuthExtension {
         Arc::new(AuthExtension {}) as Arc<dyn Extension>
     }
 }
+
+/// Unauthenticated mutations that are throttled per client IP and per claimed account.
+const THROTTLED_MUTATIONS: &[&str] = &["sign", "signInLocal", "verifyTwoFactor", "rotateToken"];
+
+/// Token bucket rate limiting for the sign-in mutations.
+///
+/// Runs right after parsing, so a limited request is rejected before any resolver or
+/// database work. Every throttled root field costs one attempt, aliases included.
+pub(crate) struct RateLimitExtension;
+
+#[async_trait]
+impl Extension for RateLimitExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let Ok(limiter) = ctx.data::<RateLimiter>() else {
+            return Ok(document);
+        };
+
+        let fields = throttled_fields(&document);
+        if fields.is_empty() {
+            return Ok(document);
+        }
+
+        let mut keys: Vec<RateLimitKey> = fields
+            .iter()
+            .filter_map(|field| subject_key(field, variables))
+            .collect();
+        keys.dedup();
+        if let Ok(ClientIp(ip)) = ctx.data::<ClientIp>() {
+            keys.push(RateLimitKey::ClientIp(ip.to_owned()));
+        }
+
+        match limiter.check(&keys, fields.len() as u32).await {
+            Ok(RateDecision::Allowed) => Ok(document),
+            Ok(RateDecision::Limited { retry_after }) => {
+                Err(rate_limited_err(retry_after).into_server_error(Pos::default()))
+            }
+            Err(e) => {
+                // Fail open: a broken limiter backend must not lock everyone out.
+                error!("Rate limiter unavailable: {:?}", e);
+                Ok(document)
+            }
+        }
+    }
+}
+
+impl ExtensionFactory for RateLimitExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RateLimitExtension {}) as Arc<dyn Extension>
+    }
+}
+
+/// Throttled root fields of every mutation in the document, looking through fragments.
+fn throttled_fields(document: &ExecutableDocument) -> Vec<&Field> {
+    fn collect<'a>(
+        document: &'a ExecutableDocument,
+        selection_set: &'a SelectionSet,
+        fields: &mut Vec<&'a Field>,
+    ) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if THROTTLED_MUTATIONS.contains(&field.node.name.node.as_str()) {
+                        fields.push(&field.node);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    collect(document, &fragment.node.selection_set.node, fields)
+                }
+                Selection::FragmentSpread(spread) => {
+                    if let Some(fragment) = document.fragments.get(&spread.node.fragment_name.node)
+                    {
+                        collect(document, &fragment.node.selection_set.node, fields)
+                    }
+                }
+            }
+        }
+    }
+
+    let mut fields = Vec::new();
+    for (_, operation) in document.operations.iter() {
+        if operation.node.ty == OperationType::Mutation {
+            collect(document, &operation.node.selection_set.node, &mut fields);
+        }
+    }
+    fields
+}
+
+/// Account the field claims to sign in as, read before anything is verified.
+fn subject_key(field: &Field, variables: &Variables) -> Option<RateLimitKey> {
+    let input = field
+        .get_argument("input")?
+        .node
+        .clone()
+        .into_const_with(|name| variables.get(&name).cloned().ok_or(()))
+        .ok()?;
+    let Value::Object(input) = input else {
+        return None;
+    };
+
+    match field.name.node.as_str() {
+        "sign" => match input.get(&Name::new("idToken"))? {
+            Value::String(id_token) => unverified_subject(id_token)
+                .map(|sub| RateLimitKey::Subject(format!("oauth:{}", sub))),
+            _ => None,
+        },
+        "signInLocal" => match input.get(&Name::new("email"))? {
+            Value::String(email) => Some(RateLimitKey::Subject(format!(
+                "email:{}",
+                email.trim().to_lowercase()
+            ))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -1,7 +1,9 @@
 pub(crate) mod guards;
+mod loaders;
 mod middleware;
 pub mod mutations;
 mod objects;
 pub mod queries;
 pub(crate) mod schema;
 mod utils;
+mod validators;
@@ -0,0 +1,69 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{ApiKey, CreateApiKeyInput, CreatedApiKeyPayload, DeleteObjectPayload};
+use crate::gql::utils::{auth_err_to_gql, record_security_event, verified_claims_from_ctx};
+use async_graphql::{Context, Object, Result};
+use config::auth_config::AuthConfig;
+use entity::entities::sea_orm_active_enums::SecurityEventType;
+use service::auth::api_key::{ApiKeyService, ApiScope};
+use service::mutations::api_key::ApiKeyMutation as ServiceApiKeyMutation;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct ApiKeyMutation;
+
+#[Object]
+impl ApiKeyMutation {
+    /// Create a personal API key for integrations. Requires a signed-in session, not a key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn create_api_key(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateApiKeyInput,
+    ) -> Result<CreatedApiKeyPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+
+        let mut scopes: Vec<ApiScope> = input.scopes.into_iter().map(ApiScope::from).collect();
+        scopes.sort_by_key(|scope| scope.as_str());
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err("At least one scope is required".into());
+        }
+
+        let (key, model) = ApiKeyService::create(
+            db.get_connection(),
+            auth_config,
+            claims.sub,
+            input.name,
+            &scopes,
+        )
+        .await
+        .map_err(auth_err_to_gql)?;
+        record_security_event(ctx, Some(claims.sub), SecurityEventType::ApiKeyCreated).await;
+
+        Ok(CreatedApiKeyPayload {
+            key: key.0,
+            api_key: ApiKey::from(model),
+        })
+    }
+
+    /// Revoke one of the user's API keys. It stops working immediately.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn revoke_api_key(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteObjectPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        match ServiceApiKeyMutation::revoke_api_key(db.get_connection(), claims.sub, id).await? {
+            Some(revoked) => {
+                record_security_event(ctx, Some(claims.sub), SecurityEventType::ApiKeyRevoked)
+                    .await;
+                Ok(DeleteObjectPayload::success_response(revoked.id))
+            }
+            None => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    CareTask, CareTaskCompletion, CompleteCareTaskInput, DeleteObjectPayload, NewCareTaskInput,
+};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{care_tasks, sea_orm_active_enums::PetRole};
+use service::auth::api_key::ApiScope;
+use service::mutations::care_task::CareTaskMutation as ServiceCareTaskMutation;
+use service::queries::care_task::CareTaskQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::recurrence::Recurrence;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct CareTaskMutation;
+
+#[Object]
+impl CareTaskMutation {
+    /// Add a recurring care task, e.g. grooming or litter changes, for one of the user's pets.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_care_task(
+        &self,
+        ctx: &Context<'_>,
+        input: NewCareTaskInput,
+    ) -> Result<CareTask> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+        if let Err(e) = input.rrule.parse::<Recurrence>() {
+            return Err(gql_err("INVALID_RRULE", e.to_string()));
+        }
+
+        let care_task =
+            ServiceCareTaskMutation::add_care_task(conn, care_tasks::ActiveModel::from(input))
+                .await?;
+
+        Ok(CareTask::from(care_task))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_care_task(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match ServiceCareTaskMutation::remove_care_task(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+
+    /// Mark an occurrence of a care task done, the next open one unless `dueOn` is given.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn complete_care_task(
+        &self,
+        ctx: &Context<'_>,
+        input: CompleteCareTaskInput,
+    ) -> Result<CareTaskCompletion> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        let care_task =
+            CareTaskQuery::user_care_task(conn, user_id, PetRole::Caretaker, input.care_task_id)
+                .await
+                .map_err(db_err_to_gql)?;
+        let recurrence = care_task
+            .rrule
+            .parse::<Recurrence>()
+            .map_err(|e| gql_err("INVALID_RRULE", e.to_string()))?;
+
+        let due_on = match input.due_on {
+            Some(due_on) if recurrence.occurs_on(care_task.starts_on, due_on) => due_on,
+            Some(_) => {
+                return Err(gql_err(
+                    "CARE_TASK_NOT_SCHEDULED",
+                    "The care task is not due on that day",
+                ))
+            }
+            None => {
+                let last_completed = CareTaskQuery::last_completed_on(conn, vec![care_task.id])
+                    .await?
+                    .remove(&care_task.id);
+                recurrence
+                    .next_after(care_task.starts_on, last_completed)
+                    .ok_or_else(|| {
+                        gql_err(
+                            "CARE_TASK_FINISHED",
+                            "The care task has no occurrences left",
+                        )
+                    })?
+            }
+        };
+
+        let completion =
+            ServiceCareTaskMutation::complete_care_task(conn, care_task.id, due_on, input.note)
+                .await?;
+
+        Ok(CareTaskCompletion::from(completion))
+    }
+}
@@ -0,0 +1,61 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    DeleteObjectPayload, Device, RegisterDeviceInput, RegisteredDevicePayload,
+};
+use crate::gql::utils::{auth_err_to_gql, db_err_to_gql, verified_claims_from_ctx};
+use async_graphql::{Context, Object, Result};
+use config::auth_config::AuthConfig;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::device::DeviceService;
+use service::mutations::device::DeviceMutation as ServiceDeviceMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct DeviceMutation;
+
+#[Object]
+impl DeviceMutation {
+    /// Register a feeder or collar posting events for one of the user's pets. Requires a
+    /// signed-in session, not a key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn register_device(
+        &self,
+        ctx: &Context<'_>,
+        input: RegisterDeviceInput,
+    ) -> Result<RegisteredDevicePayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let pet = ServicePetQuery::get_user_pet(conn, claims.sub, input.pet_id, PetRole::Owner)
+            .await
+            .map_err(db_err_to_gql)?;
+        let (secret, device) =
+            DeviceService::register(conn, auth_config, claims.sub, pet.id, input.name)
+                .await
+                .map_err(auth_err_to_gql)?;
+
+        Ok(RegisteredDevicePayload {
+            secret,
+            device: Device::from(device),
+        })
+    }
+
+    /// Remove one of the user's devices. Records it sent are kept.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_device(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteObjectPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        if ServiceDeviceMutation::remove_device(db.get_connection(), claims.sub, id).await? {
+            Ok(DeleteObjectPayload::success_response(id))
+        } else {
+            Ok(DeleteObjectPayload::empty_response())
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{EliminationRecord, EliminationType, LogEliminationInput};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::mutations::elimination_record::EliminationRecordMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct EliminationMutation;
+
+#[Object]
+impl EliminationMutation {
+    /// Record a urination or stool, e.g. from a smart litter box holding a `pets:write` key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn log_elimination(
+        &self,
+        ctx: &Context<'_>,
+        input: LogEliminationInput,
+    ) -> Result<EliminationRecord> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        if input.kind == EliminationType::Urine && input.consistency.is_some() {
+            return Err(gql_err(
+                "INVALID_CONSISTENCY",
+                "Only stools have a consistency",
+            ));
+        }
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let record = EliminationRecordMutation::add_elimination_record(
+            conn,
+            pet.id,
+            input.kind.into(),
+            input.consistency,
+            input.occurred_at,
+        )
+        .await?;
+
+        Ok(EliminationRecord::from(record))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{DeleteObjectPayload, Expense, NewExpenseInput, UpdateExpenseInput};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{expenses, sea_orm_active_enums::PetRole};
+use sea_orm::prelude::Decimal;
+use service::auth::api_key::ApiScope;
+use service::mutations::expense::ExpenseMutation as ServiceExpenseMutation;
+use service::queries::expense::ExpenseQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+fn check_amount(amount: Option<Decimal>) -> Result<()> {
+    if amount.is_some_and(|amount| amount.is_sign_negative()) {
+        return Err(gql_err("INVALID_AMOUNT", "amount can't be negative"));
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct ExpenseMutation;
+
+#[Object]
+impl ExpenseMutation {
+    /// Record money spent on one of the user's pets.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_expense(&self, ctx: &Context<'_>, input: NewExpenseInput) -> Result<Expense> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+        check_amount(Some(input.amount))?;
+
+        let expense =
+            ServiceExpenseMutation::add_expense(conn, expenses::ActiveModel::from(input)).await?;
+
+        Ok(Expense::from(expense))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn update_expense(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateExpenseInput,
+    ) -> Result<Expense> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ExpenseQuery::user_expense(conn, user_id, PetRole::Caretaker, input.id)
+            .await
+            .map_err(db_err_to_gql)?;
+        check_amount(input.amount)?;
+
+        let expense =
+            ServiceExpenseMutation::update_expense(conn, expenses::ActiveModel::from(input))
+                .await?;
+
+        Ok(Expense::from(expense))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_expense(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match ServiceExpenseMutation::remove_expense(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{FeedRecord, LogFeedInput};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::mutations::feed_record::FeedRecordMutation;
+use service::nutrition::kcal;
+use service::queries::food::FoodQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct FeedMutation;
+
+#[Object]
+impl FeedMutation {
+    /// Record that a pet was fed, e.g. from a smart feeder holding a `feed:write` key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn log_feed(&self, ctx: &Context<'_>, input: LogFeedInput) -> Result<FeedRecord> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedWrite)?;
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let mut unit = input.unit.map(Into::into);
+        if let Some(food_id) = input.food_id {
+            let food = FoodQuery::user_food(conn, user_id, food_id)
+                .await
+                .map_err(db_err_to_gql)?;
+            let unit = unit.get_or_insert_with(|| food.unit.to_owned());
+            if kcal(&food, 1.0, unit).is_none() {
+                return Err(gql_err(
+                    "FOOD_UNIT_UNSUPPORTED",
+                    format!("{} has no kcal value per {:?}", food.name, unit),
+                ));
+            }
+        }
+
+        let record = FeedRecordMutation::add_feed_record(
+            conn,
+            pet.id,
+            input.amount,
+            input.food_id,
+            unit,
+            input.fed_at,
+        )
+        .await?;
+
+        Ok(FeedRecord::from(record))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    DeleteObjectPayload, Food, FoodPurchase, NewFoodInput, RecordFoodPurchaseInput,
+};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{food_purchases, foods, sea_orm_active_enums::FoodUnit};
+use sea_orm::{ActiveValue::Set, SqlErr};
+use service::auth::api_key::ApiScope;
+use service::mutations::food::FoodMutation as ServiceFoodMutation;
+use service::nutrition::convert;
+use service::queries::food::FoodQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::schedule::today_in;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct FoodMutation;
+
+#[Object]
+impl FoodMutation {
+    /// Add a food with the energy values from its label.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_food(&self, ctx: &Context<'_>, input: NewFoodInput) -> Result<Food> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedWrite)?;
+        let kcal_known = match input.unit.into() {
+            FoodUnit::Gram => input.kcal_per_100g.is_some(),
+            FoodUnit::Cup => input.kcal_per_cup.is_some(),
+        };
+        if !kcal_known {
+            return Err(gql_err(
+                "FOOD_UNIT_UNSUPPORTED",
+                "The kcal value for unit is required",
+            ));
+        }
+
+        let mut food = foods::ActiveModel::from(input);
+        food.user_id = Set(user_id);
+
+        let food = ServiceFoodMutation::add_food(conn, food)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    gql_err("FOOD_EXISTS", "You already have this food")
+                }
+                _ => db_err_to_gql(e),
+            })?;
+
+        Ok(Food::from(food))
+    }
+
+    /// Remove one of your foods. Feedings of it stay, without the food.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_food(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedWrite)?;
+
+        match ServiceFoodMutation::remove_food(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+
+    /// Record a bag of one of your foods bought, adding to its stock.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn record_food_purchase(
+        &self,
+        ctx: &Context<'_>,
+        input: RecordFoodPurchaseInput,
+    ) -> Result<FoodPurchase> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedWrite)?;
+        let food = FoodQuery::user_food(conn, user_id, input.food_id)
+            .await
+            .map_err(db_err_to_gql)?;
+        let unit = input.unit.map(Into::into).unwrap_or(food.unit.to_owned());
+        if convert(&food, 1.0, &unit, &food.unit).is_none() {
+            return Err(gql_err(
+                "FOOD_UNIT_UNSUPPORTED",
+                format!("{} can't be measured in {:?}", food.name, unit),
+            ));
+        }
+        let purchased_on = match input.purchased_on {
+            Some(date) => date,
+            None => today_in(&ServiceUserQuery::user_by_id(conn, user_id).await?.timezone),
+        };
+
+        let purchase = ServiceFoodMutation::record_purchase(
+            conn,
+            food_purchases::ActiveModel {
+                food_id: Set(food.id),
+                size: Set(input.size),
+                unit: Set(unit),
+                purchased_on: Set(purchased_on),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(FoodPurchase::from(purchase))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_food_purchase(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedWrite)?;
+
+        match ServiceFoodMutation::remove_purchase(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    DeleteObjectPayload, JournalEntry, NewJournalEntryInput, UpdateJournalEntryInput,
+};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{journal_entries, sea_orm_active_enums::PetRole};
+use service::auth::api_key::ApiScope;
+use service::mutations::journal::JournalMutation as ServiceJournalMutation;
+use service::queries::journal::JournalQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct JournalMutation;
+
+#[Object]
+impl JournalMutation {
+    /// Note how one of the user's pets is doing. Symptoms are stored lowercase.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        input: NewJournalEntryInput,
+    ) -> Result<JournalEntry> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let entry =
+            ServiceJournalMutation::add_entry(conn, journal_entries::ActiveModel::from(input))
+                .await?;
+
+        Ok(JournalEntry::from(entry))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn update_journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateJournalEntryInput,
+    ) -> Result<JournalEntry> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        JournalQuery::user_entry(conn, user_id, PetRole::Caretaker, input.id)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let entry =
+            ServiceJournalMutation::update_entry(conn, journal_entries::ActiveModel::from(input))
+                .await?;
+
+        Ok(JournalEntry::from(entry))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match ServiceJournalMutation::remove_entry(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
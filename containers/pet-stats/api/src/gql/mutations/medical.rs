@@ -0,0 +1,98 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    DeleteObjectPayload, NewVaccinationInput, NewVetVisitInput, Vaccination, VetVisit,
+};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{sea_orm_active_enums::PetRole, vaccinations, vet_visits};
+use service::auth::api_key::ApiScope;
+use service::mutations::vaccination::VaccinationMutation;
+use service::mutations::vet_visit::VetVisitMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct MedicalMutation;
+
+#[Object]
+impl MedicalMutation {
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_vaccination(
+        &self,
+        ctx: &Context<'_>,
+        input: NewVaccinationInput,
+    ) -> Result<Vaccination> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let vaccination =
+            VaccinationMutation::add_vaccination(conn, vaccinations::ActiveModel::from(input))
+                .await?;
+
+        Ok(Vaccination::from(vaccination))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_vaccination(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match VaccinationMutation::remove_vaccination(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_vet_visit(
+        &self,
+        ctx: &Context<'_>,
+        input: NewVetVisitInput,
+    ) -> Result<VetVisit> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let visit =
+            VetVisitMutation::add_vet_visit(conn, vet_visits::ActiveModel::from(input)).await?;
+
+        Ok(VetVisit::from(visit))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_vet_visit(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match VetVisitMutation::remove_vet_visit(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    DeleteObjectPayload, Medication, MedicationDose, NewMedicationInput, RecordDoseInput,
+};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::{medications, sea_orm_active_enums::PetRole};
+use service::auth::api_key::ApiScope;
+use service::mutations::medication::MedicationMutation as ServiceMedicationMutation;
+use service::queries::medication::MedicationQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::schedule::is_scheduled_dose;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct MedicationMutation;
+
+#[Object]
+impl MedicationMutation {
+    /// Start a medication schedule for one of the user's pets.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn add_medication(
+        &self,
+        ctx: &Context<'_>,
+        input: NewMedicationInput,
+    ) -> Result<Medication> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+        if input.end_on.is_some_and(|end| end < input.start_on) {
+            return Err(gql_err("INVALID_SCHEDULE", "endOn is before startOn"));
+        }
+
+        let medication =
+            ServiceMedicationMutation::add_medication(conn, medications::ActiveModel::from(input))
+                .await?;
+
+        Ok(Medication::from(medication))
+    }
+
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_medication(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        match ServiceMedicationMutation::remove_medication(conn, user_id, id).await? {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+
+    /// Mark a scheduled dose as given or skipped.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn record_dose(
+        &self,
+        ctx: &Context<'_>,
+        input: RecordDoseInput,
+    ) -> Result<MedicationDose> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        let medication = MedicationQuery::user_medication(
+            conn,
+            user_id,
+            PetRole::Caretaker,
+            input.medication_id,
+        )
+        .await
+        .map_err(db_err_to_gql)?;
+        if !is_scheduled_dose(&medication, input.scheduled_on, input.dose_number) {
+            return Err(gql_err(
+                "DOSE_NOT_SCHEDULED",
+                "No such dose is scheduled on that day",
+            ));
+        }
+
+        let dose = ServiceMedicationMutation::record_dose(
+            conn,
+            medication.id,
+            input.scheduled_on,
+            input.dose_number,
+            input.status.into(),
+        )
+        .await?;
+
+        Ok(MedicationDose::from(dose))
+    }
+}
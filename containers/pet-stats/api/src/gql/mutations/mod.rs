@@ -1,8 +1,53 @@
+use api_key::ApiKeyMutation;
 use async_graphql::MergedObject;
+use care_task::CareTaskMutation;
+use device::DeviceMutation;
+use elimination::EliminationMutation;
+use expense::ExpenseMutation;
+use feed::FeedMutation;
+use food::FoodMutation;
+use journal::JournalMutation;
+use medical::MedicalMutation;
+use medication::MedicationMutation;
+use pet_member::PetMemberMutation;
+use species::SpeciesMutation;
 use user::UserMutation;
+use walk::WalkMutation;
+use water::WaterMutation;
 
 use crate::gql::mutations::pet::PetMutation;
+mod api_key;
+mod care_task;
+mod device;
+mod elimination;
+mod expense;
+mod feed;
+mod food;
+mod journal;
+mod medical;
+mod medication;
 mod pet;
+mod pet_member;
+mod species;
 mod user;
+mod walk;
+mod water;
 #[derive(MergedObject, Default)]
-pub struct Mutation(UserMutation, PetMutation);
+pub struct Mutation(
+    UserMutation,
+    PetMutation,
+    FeedMutation,
+    ApiKeyMutation,
+    MedicalMutation,
+    MedicationMutation,
+    CareTaskMutation,
+    SpeciesMutation,
+    FoodMutation,
+    ExpenseMutation,
+    JournalMutation,
+    EliminationMutation,
+    WaterMutation,
+    WalkMutation,
+    DeviceMutation,
+    PetMemberMutation,
+);
@@ -1,13 +1,34 @@
 use crate::db::Database;
 use crate::gql::guards::AuthGuard;
-use crate::gql::objects::{DefaultPet, DeleteObjectPayload, NewPetInput, Pet, UpdatePetInput};
-use crate::gql::utils::verified_claims_from_ctx;
+use crate::gql::objects::{DeleteObjectPayload, NewPetInput, Pet, UpdatePetInput};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err, resolve_species};
 use async_graphql::Result;
 use async_graphql::{Context, Object};
-use entity::entities::pets;
-use sea_orm::ActiveValue::Set;
+use entity::entities::{pets, sea_orm_active_enums::PetRole, species};
+use sea_orm::{ActiveValue::Set, DbConn};
+use service::auth::api_key::ApiScope;
 use service::mutations::pet::PetMutationService;
-use tracing::{error, info, instrument};
+use service::queries::breed::BreedQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::species::SpeciesQuery;
+use tracing::{error, instrument};
+
+/// A pet can only be given a breed of its own species.
+async fn check_breed(conn: &DbConn, breed_id: Option<i32>, species: &species::Model) -> Result<()> {
+    let Some(breed_id) = breed_id else {
+        return Ok(());
+    };
+    let breed = BreedQuery::by_id(conn, breed_id)
+        .await
+        .map_err(db_err_to_gql)?;
+    if breed.species_id != species.id {
+        return Err(gql_err(
+            "BREED_SPECIES_MISMATCH",
+            format!("{} is not a breed of {}", breed.name, species.name),
+        ));
+    }
+    Ok(())
+}
 
 #[derive(Default)]
 pub struct PetMutation;
@@ -20,10 +41,19 @@ impl PetMutation {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        let claims = verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        let species = resolve_species(
+            conn,
+            user_id,
+            input.species.map(Into::into),
+            input.species_id,
+        )
+        .await?;
+        check_breed(conn, input.breed_id, &species).await?;
 
         let mut active_model = pets::ActiveModel::from(input);
-        active_model.user_id = Set(claims.sub);
+        active_model.user_id = Set(user_id);
+        active_model.species_id = Set(species.id);
 
         let pet = PetMutationService::add_pet(conn, active_model).await?;
 
@@ -36,12 +66,15 @@ impl PetMutation {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        let claims = verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Owner)
+            .await
+            .map_err(db_err_to_gql)?;
 
         let removed_pet = PetMutationService::remove_pet(conn, pet_id).await?;
 
         if removed_pet.rows_affected == 1 {
-            Ok(DeleteObjectPayload::success_response(claims.sub))
+            Ok(DeleteObjectPayload::success_response(user_id))
         } else {
             Ok(DeleteObjectPayload::empty_response())
         }
@@ -53,10 +86,18 @@ impl PetMutation {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        let claims = verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        let current = ServicePetQuery::get_user_pet(conn, user_id, input.id, PetRole::Owner)
+            .await
+            .map_err(db_err_to_gql)?;
+        if input.breed_id.is_some() {
+            let species = SpeciesQuery::by_id(conn, current.species_id)
+                .await
+                .map_err(db_err_to_gql)?;
+            check_breed(conn, input.breed_id, &species).await?;
+        }
 
-        let mut pet = pets::ActiveModel::from(input);
-        pet.user_id = Set(claims.sub);
+        let pet = pets::ActiveModel::from(input);
 
         let updated_pet = PetMutationService::update_pet(conn, pet).await?;
 
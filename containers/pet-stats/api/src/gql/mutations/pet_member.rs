@@ -0,0 +1,126 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{
+    CreatePetInvitationInput, CreatedPetInvitationPayload, DeleteObjectPayload, Pet, PetInvitation,
+};
+use crate::gql::utils::{
+    auth_err_to_gql, authorized_user_id, db_err_to_gql, gql_err, verified_claims_from_ctx,
+};
+use async_graphql::{Context, Object, Result};
+use config::auth_config::AuthConfig;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::membership::{InvitationCode, MembershipError};
+use service::mutations::pet_member::PetMemberMutation as ServicePetMemberMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+fn membership_err(e: MembershipError) -> async_graphql::Error {
+    match e {
+        MembershipError::InvalidInvitation => gql_err("INVALID_INVITATION", e.to_string()),
+        MembershipError::LastOwner => gql_err("LAST_OWNER", e.to_string()),
+        MembershipError::Database(e) => db_err_to_gql(e),
+    }
+}
+
+#[derive(Default)]
+pub struct PetMemberMutation;
+
+#[Object]
+impl PetMemberMutation {
+    /// Invite someone to one of the user's pets with a role. Only owners can invite, and only
+    /// from a signed-in session, not a key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn create_pet_invitation(
+        &self,
+        ctx: &Context<'_>,
+        input: CreatePetInvitationInput,
+    ) -> Result<CreatedPetInvitationPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let pet = ServicePetQuery::get_user_pet(conn, claims.sub, input.pet_id, PetRole::Owner)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let code = InvitationCode::generate().map_err(auth_err_to_gql)?;
+        let invitation = ServicePetMemberMutation::create_invitation(
+            conn,
+            pet.id,
+            claims.sub,
+            input.role.into(),
+            &code.hash(auth_config.refresh_key_hashing_secret.as_bytes()),
+        )
+        .await?;
+
+        Ok(CreatedPetInvitationPayload {
+            code: code.0,
+            invitation: PetInvitation::from(invitation),
+        })
+    }
+
+    /// Join the pet an invitation code is for. Codes can be used once.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, code))]
+    pub async fn accept_pet_invitation(&self, ctx: &Context<'_>, code: String) -> Result<Pet> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let hash = InvitationCode(code).hash(auth_config.refresh_key_hashing_secret.as_bytes());
+        let member = ServicePetMemberMutation::accept_invitation(conn, claims.sub, &hash)
+            .await
+            .map_err(membership_err)?;
+
+        let pet = ServicePetQuery::get_pet_by_id(conn, member.pet_id).await?;
+        Ok(Pet::from(pet))
+    }
+
+    /// Stop sharing one of the user's pets. The last owner can't leave.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn leave_pet(&self, ctx: &Context<'_>, pet_id: i32) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        let removed = ServicePetMemberMutation::remove_member(db.get_connection(), pet_id, user_id)
+            .await
+            .map_err(membership_err)?;
+        if removed {
+            Ok(DeleteObjectPayload::success_response(pet_id))
+        } else {
+            Ok(DeleteObjectPayload::empty_response())
+        }
+    }
+
+    /// Remove someone from one of the user's pets. Only owners can remove members.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_pet_member(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        user_id: i32,
+    ) -> Result<DeleteObjectPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        ServicePetQuery::get_user_pet(conn, claims.sub, pet_id, PetRole::Owner)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let removed = ServicePetMemberMutation::remove_member(conn, pet_id, user_id)
+            .await
+            .map_err(membership_err)?;
+        if removed {
+            Ok(DeleteObjectPayload::success_response(user_id))
+        } else {
+            Ok(DeleteObjectPayload::empty_response())
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{DeleteObjectPayload, Species};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use sea_orm::SqlErr;
+use service::auth::api_key::ApiScope;
+use service::mutations::species::SpeciesMutation as ServiceSpeciesMutation;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct SpeciesMutation;
+
+#[Object]
+impl SpeciesMutation {
+    /// Define a species of your own, for pets none of the shared species fit.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn add_species(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(min_length = 1, max_length = 100))] name: String,
+    ) -> Result<Species> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        let species = ServiceSpeciesMutation::add_species(conn, user_id, name)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    gql_err("SPECIES_EXISTS", "You already have a species by that name")
+                }
+                _ => db_err_to_gql(e),
+            })?;
+
+        Ok(Species::from(species))
+    }
+
+    /// Remove one of your own species. Not possible while any pet has it.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn remove_species(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteObjectPayload> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        let removed = ServiceSpeciesMutation::remove_species(conn, user_id, id)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+                    gql_err("SPECIES_IN_USE", "Species is still used by pets")
+                }
+                _ => db_err_to_gql(e),
+            })?;
+
+        match removed {
+            1 => Ok(DeleteObjectPayload::success_response(id)),
+            _ => Ok(DeleteObjectPayload::empty_response()),
+        }
+    }
+}
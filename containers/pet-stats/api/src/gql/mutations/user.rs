@@ -1,15 +1,30 @@
 use crate::context_data::AccessToken;
 use crate::db::Database;
 use crate::gql::guards::AuthGuard;
-use crate::gql::objects::{OauthPayload, OauthSignInInput, SignOutPayload, TokenRotationPayload};
-use crate::gql::utils::db_err_to_gql;
-use async_graphql::{Context, Error, Object, Result};
+use crate::gql::objects::{
+    AccessTokenRevocationPayload, AccountDeletionPayload, LocalSignInInput, LocalSignInPayload,
+    OauthPayload, OauthSignInInput, ReauthenticationInput, SignOutPayload, TokenRotationPayload,
+    TotpConfirmationPayload, TotpDisablePayload, TotpEnrollmentPayload, TwoFactorChallengePayload,
+    TwoFactorVerificationInput, User,
+};
+use crate::gql::utils::{
+    admin_claims_from_ctx, apply_access_token_cutoff, auth_err_to_gql, db_err_to_gql, gql_err,
+    record_on_failure, record_on_failure_if, record_security_event, revoke_access_token,
+    verified_claims_from_ctx,
+};
+use async_graphql::{Context, Object, Result};
+use chrono::Duration;
+use config::account_config::AccountConfig;
 use config::auth_config::AuthConfig;
-use entity::entities::sea_orm_active_enums::ProviderType;
+use entity::entities::sea_orm_active_enums::SecurityEventType;
 use jwt::{create_jwt, verify_jwt, JwtAuthError, DEFAULT_EXP};
 use sea_orm::DbErr;
-use service::auth::oauth_provider::OAuthProvider;
+use service::auth::error::AuthError;
+use service::auth::google::GoogleOAuth;
 use service::auth::refresh_token::RefreshToken;
+use service::auth::sign_in::{LocalSignIn, SignInService};
+use service::auth::two_factor::TwoFactorService;
+use service::schedule::parse_timezone;
 use service::{
     mutations::user::UserMutation as ServiceUserMutation,
     queries::user::UserQuery as ServiceUserQuery,
@@ -33,62 +48,168 @@ impl UserMutation {
         let conn = db.get_connection();
 
         // OPTIMIZE: Matching another provider oauth traits after subscript apple developer.
-        let google_oauth =
-            service::auth::google::GoogleOAuth::new(auth_config.google_oauth_client_id.to_owned())?;
+        let google_oauth = ctx.data::<GoogleOAuth>()?;
 
-        info!("Verifying OAuth token");
-        let claim = google_oauth.verify_token(&input.id_token).await?;
-        info!(
-            "OAuth token verified successfully for user_id: {:?}",
-            claim.sub
-        );
+        let signed_in =
+            SignInService::sign_in_with_google(conn, google_oauth, auth_config, &input.id_token)
+                .await;
+        let tokens = record_on_failure(ctx, signed_in, None, SecurityEventType::SignInFailed)
+            .await
+            .map_err(auth_err_to_gql)?;
+        record_security_event(ctx, Some(tokens.user.id), SecurityEventType::SignIn).await;
 
-        let user = match ServiceUserQuery::user_by_provider_user_id(conn, claim.sub.clone()).await {
-            Ok(user) => user,
-            Err(DbErr::RecordNotFound(e)) => {
-                info!("Record Not Found {:?}", e);
-                ServiceUserMutation::create_oauth_user(
-                    conn,
-                    claim.email,
-                    ProviderType::Google,
-                    claim.sub,
-                )
-                .await?
+        Ok(OauthPayload {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token.0,
+        })
+    }
+
+    /// Sign in with email and password.
+    ///
+    /// Accounts with two-factor authentication get a short-lived challenge token instead of
+    /// tokens, to be completed with `verifyTwoFactor`.
+    #[instrument(skip(self, ctx, input))]
+    pub async fn sign_in_local(
+        &self,
+        ctx: &Context<'_>,
+        input: LocalSignInInput,
+    ) -> Result<LocalSignInPayload> {
+        info!("Starting local sign-in process.");
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let signed_in =
+            SignInService::sign_in_local(conn, auth_config, &input.email, &input.password).await;
+        let result = record_on_failure(ctx, signed_in, None, SecurityEventType::SignInFailed)
+            .await
+            .map_err(auth_err_to_gql)?;
+
+        Ok(match result {
+            LocalSignIn::Completed(tokens) => {
+                record_security_event(ctx, Some(tokens.user.id), SecurityEventType::SignIn).await;
+                LocalSignInPayload::Signed(OauthPayload {
+                    access_token: tokens.access_token,
+                    refresh_token: tokens.refresh_token.0,
+                })
             }
-            Err(err) => {
-                error!("{:?}", err.to_string());
-                return Err(Error::new(err.to_string()));
+            LocalSignIn::TwoFactorRequired(challenge) => {
+                LocalSignInPayload::TwoFactorRequired(TwoFactorChallengePayload {
+                    challenge_token: challenge.token.0,
+                    expires_at: challenge.expires_at,
+                })
             }
-        };
-
-        info!("Generating JWT for user_id: {:?}", user.id);
-        let jwt_token = create_jwt(
-            user.id,
-            user.email.to_owned(),
-            auth_config.jwt_sign_secret.to_owned(),
-            DEFAULT_EXP,
-        )?;
-        info!("JWT generated successfully for user_id: {:?}", user.id);
+        })
+    }
 
-        info!("Generating refresh token for user_id: {}", user.id);
-        let token = RefreshToken::generate()?;
-        info!(
-            "Refresh token generated and stored for user_id: {}",
-            user.id
-        );
-        let token_hash = token.hash(auth_config.refresh_key_hashing_secret.as_bytes());
+    /// Finish a local sign-in with a TOTP code or a recovery code.
+    #[instrument(skip(self, ctx, input))]
+    pub async fn verify_two_factor(
+        &self,
+        ctx: &Context<'_>,
+        input: TwoFactorVerificationInput,
+    ) -> Result<OauthPayload> {
+        info!("Verifying second sign-in factor.");
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
 
-        info!("Storing refresh token for user_id: {}", user.id);
-        ServiceUserMutation::store_refresh_token(conn, user.id, &token_hash).await?;
-        info!("Refresh token stored successfully for user_id: {}", user.id);
+        let completed = SignInService::complete_two_factor(
+            conn,
+            auth_config,
+            &input.challenge_token,
+            &input.code,
+        )
+        .await;
+        let tokens = record_on_failure(ctx, completed, None, SecurityEventType::TwoFactorFailed)
+            .await
+            .map_err(auth_err_to_gql)?;
+        record_security_event(ctx, Some(tokens.user.id), SecurityEventType::SignIn).await;
 
-        info!(
-            "OAuth sign-in completed successfully for user_id: {}",
-            user.id
-        );
         Ok(OauthPayload {
-            access_token: jwt_token,
-            refresh_token: token.0,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token.0,
+        })
+    }
+
+    /// Start TOTP enrollment. Nothing changes for sign-in until `confirmTotp` succeeds.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn enroll_totp(&self, ctx: &Context<'_>) -> Result<TotpEnrollmentPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        let enrollment = TwoFactorService::enroll_totp(db.get_connection(), claims.sub)
+            .await
+            .map_err(auth_err_to_gql)?;
+
+        Ok(TotpEnrollmentPayload {
+            otpauth_uri: enrollment.otpauth_uri,
+            secret: enrollment.secret.to_base32(),
+        })
+    }
+
+    /// Confirm TOTP enrollment with a code from the authenticator app.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, code))]
+    pub async fn confirm_totp(
+        &self,
+        ctx: &Context<'_>,
+        code: String,
+    ) -> Result<TotpConfirmationPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+
+        let confirmed =
+            TwoFactorService::confirm_totp(db.get_connection(), auth_config, claims.sub, &code)
+                .await;
+        let codes = record_on_failure_if(
+            ctx,
+            confirmed,
+            Some(claims.sub),
+            SecurityEventType::TwoFactorFailed,
+            is_wrong_code,
+        )
+        .await
+        .map_err(auth_err_to_gql)?;
+        record_security_event(ctx, Some(claims.sub), SecurityEventType::TwoFactorEnabled).await;
+
+        Ok(TotpConfirmationPayload {
+            success: true,
+            recovery_codes: codes.into_iter().map(|code| code.0).collect(),
+        })
+    }
+
+    /// Turn two-factor authentication off with a current TOTP code or a recovery code.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, code))]
+    pub async fn disable_totp(
+        &self,
+        ctx: &Context<'_>,
+        code: String,
+    ) -> Result<TotpDisablePayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let db = ctx.data::<Database>()?;
+
+        let disabled =
+            TwoFactorService::disable_totp(db.get_connection(), auth_config, claims.sub, &code)
+                .await;
+        record_on_failure_if(
+            ctx,
+            disabled,
+            Some(claims.sub),
+            SecurityEventType::TwoFactorFailed,
+            is_wrong_code,
+        )
+        .await
+        .map_err(auth_err_to_gql)?;
+        record_security_event(ctx, Some(claims.sub), SecurityEventType::TwoFactorDisabled).await;
+
+        Ok(TotpDisablePayload {
+            success: true,
+            message: "two-factor authentication disabled.".to_string(),
         })
     }
 
@@ -105,12 +226,18 @@ impl UserMutation {
         info!("Getting user claims from data.");
         let auth_config = ctx.data::<AuthConfig>()?;
         let token = ctx.data::<AccessToken>()?;
-        match verify_jwt(token.0.as_str(), auth_config.jwt_sign_secret.clone()) {
-            Ok(_claims) => {
+        let mut user_id = match verify_jwt(token.0.as_str(), auth_config.jwt_sign_secret.clone()) {
+            Ok(claims) => {
                 info!("Access token verified on Sign Out workflow.");
+                // The refresh token alone would leave this access token usable until `exp`.
+                if let Err(e) = revoke_access_token(ctx, &claims).await {
+                    error!("Failed to revoke access token on sign out: {:?}", e);
+                }
+                Some(claims.sub)
             }
             Err(JwtAuthError::Expired) => {
                 warn!("Token expired: {:?}; treating as success", token.0);
+                None
             }
             Err(e) => {
                 return Err(e.into());
@@ -128,8 +255,9 @@ impl UserMutation {
         // When sign out is not important token expired, and record search because revoked token
         // record wouldn't using anymore.
         match ServiceUserMutation::revoke_refresh_token(conn, &refresh_token_hash).await {
-            Ok(_) => {
+            Ok(user_token) => {
                 info!("Refresh token revoked successfully.");
+                user_id = Some(user_token.user_id);
             }
 
             Err(DbErr::RecordNotFound(_)) => {
@@ -147,6 +275,7 @@ impl UserMutation {
         }
 
         info!("Disable user refresh token has successfully ended.");
+        record_security_event(ctx, user_id, SecurityEventType::SignOut).await;
 
         info!("Sign-Out process ended successfully.");
 
@@ -156,6 +285,22 @@ impl UserMutation {
         })
     }
 
+    /// Revoke the access token of this request right away instead of waiting for it to expire.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn revoke_access_token(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<AccessTokenRevocationPayload> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        revoke_access_token(ctx, &claims).await?;
+
+        Ok(AccessTokenRevocationPayload {
+            success: true,
+            message: "access token revoked.".to_string(),
+        })
+    }
+
     /// Generate new Access token and Refresh token.
     /// This mutations called when access token expired.
     ///
@@ -184,16 +329,23 @@ impl UserMutation {
         let new_refresh_token_hash =
             new_refresh_token.hash(auth_config.refresh_key_hashing_secret.as_bytes());
 
-        let user_token = ServiceUserMutation::rotate_refresh_token(
+        let rotated = ServiceUserMutation::rotate_refresh_token(
             conn,
             &old_refresh_token_hash,
             &new_refresh_token_hash,
         )
         .await
-        .map_err(|e| {
-            error!("DB Error: {:?}", e);
-            db_err_to_gql(e)
-        })?;
+        .inspect_err(|e| error!("DB Error: {:?}", e));
+        let user_token =
+            record_on_failure(ctx, rotated, None, SecurityEventType::TokenRotationFailed)
+                .await
+                .map_err(db_err_to_gql)?;
+        record_security_event(
+            ctx,
+            Some(user_token.user_id),
+            SecurityEventType::TokenRotated,
+        )
+        .await;
 
         let user = ServiceUserQuery::user_by_id(conn, user_token.user_id).await?;
 
@@ -208,4 +360,145 @@ impl UserMutation {
             refresh_token: new_refresh_token.0,
         })
     }
+
+    /// Set the signed-in user's IANA timezone, e.g. `Asia/Seoul`.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn update_my_timezone(&self, ctx: &Context<'_>, timezone: String) -> Result<User> {
+        let claims = verified_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        let Some(tz) = parse_timezone(&timezone) else {
+            return Err(gql_err("INVALID_TIMEZONE", "Unknown IANA timezone"));
+        };
+        let user = ServiceUserMutation::update_timezone(
+            db.get_connection(),
+            claims.sub,
+            tz.name().to_owned(),
+        )
+        .await
+        .map_err(db_err_to_gql)?;
+
+        Ok(User::from(user))
+    }
+
+    /// Schedule the signed-in account for deletion.
+    ///
+    /// Requires a freshly issued ID token of the same provider account, or the password and
+    /// second factor of an email and password account. All sessions are signed out
+    /// immediately, and the account with its pets and records is removed once the grace period
+    /// ends. Signing in again before that keeps the account.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn delete_my_account(
+        &self,
+        ctx: &Context<'_>,
+        input: ReauthenticationInput,
+    ) -> Result<AccountDeletionPayload> {
+        info!("Starting account deletion request.");
+        let claims = verified_claims_from_ctx(ctx)?;
+
+        let account_config = ctx.data::<AccountConfig>()?;
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let reauthenticated = match input {
+            ReauthenticationInput::Oauth(input) => {
+                let google_oauth = ctx.data::<GoogleOAuth>()?;
+                SignInService::reauthenticate_with_google(
+                    conn,
+                    google_oauth,
+                    claims.sub,
+                    &input.id_token,
+                )
+                .await
+            }
+            ReauthenticationInput::Password(input) => {
+                let auth_config = ctx.data::<AuthConfig>()?;
+                SignInService::reauthenticate_with_password(
+                    conn,
+                    auth_config,
+                    claims.sub,
+                    &input.password,
+                    input.code.as_deref(),
+                )
+                .await
+            }
+        };
+        record_on_failure(
+            ctx,
+            reauthenticated,
+            Some(claims.sub),
+            SecurityEventType::ReauthenticationFailed,
+        )
+        .await
+        .map_err(auth_err_to_gql)?;
+
+        let user = ServiceUserMutation::schedule_account_deletion(
+            conn,
+            claims.sub,
+            Duration::days(account_config.account_deletion_grace_days),
+        )
+        .await
+        .map_err(db_err_to_gql)?;
+        apply_access_token_cutoff(ctx, &user)?;
+        record_security_event(
+            ctx,
+            Some(claims.sub),
+            SecurityEventType::AccountDeletionScheduled,
+        )
+        .await;
+
+        Ok(AccountDeletionPayload {
+            success: true,
+            deletion_scheduled_at: user.deletion_scheduled_at,
+            message: format!(
+                "Account will be deleted after {} days. Sign in again to cancel.",
+                account_config.account_deletion_grace_days
+            ),
+        })
+    }
+
+    /// Disable an account, admins only. Every session of the account ends right away and it
+    /// can't sign in or use its API keys until it is enabled again.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn disable_account(&self, ctx: &Context<'_>, user_id: i32) -> Result<User> {
+        let claims = admin_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        let user = ServiceUserMutation::disable_account(db.get_connection(), user_id)
+            .await
+            .map_err(db_err_to_gql)?;
+        apply_access_token_cutoff(ctx, &user)?;
+        info!(
+            "user_id: {} disabled by admin user_id: {}",
+            user_id, claims.sub
+        );
+
+        Ok(User::from(user))
+    }
+
+    /// Let a disabled account sign in again, admins only.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    pub async fn enable_account(&self, ctx: &Context<'_>, user_id: i32) -> Result<User> {
+        let claims = admin_claims_from_ctx(ctx)?;
+        let db = ctx.data::<Database>()?;
+
+        let user = ServiceUserMutation::enable_account(db.get_connection(), user_id)
+            .await
+            .map_err(db_err_to_gql)?;
+        info!(
+            "user_id: {} enabled by admin user_id: {}",
+            user_id, claims.sub
+        );
+
+        Ok(User::from(user))
+    }
+}
+
+/// A wrong second factor is worth auditing, a missing enrollment or a database error is not.
+fn is_wrong_code(e: &AuthError) -> bool {
+    matches!(e, AuthError::InvalidTwoFactorCode)
 }
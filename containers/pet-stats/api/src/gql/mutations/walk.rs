@@ -0,0 +1,94 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{AttachWalkTrackInput, LogWalkInput, Walk};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::mutations::walk::WalkMutation as ServiceWalkMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::walk::WalkQuery;
+use service::walk_track::{parse_gpx, Track, TrackError, TrackPoint};
+use tracing::instrument;
+
+fn track_err(e: TrackError) -> async_graphql::Error {
+    let code = match e {
+        TrackError::InvalidGpx(_) => "INVALID_GPX",
+        TrackError::InvalidCoordinate(_) => "INVALID_COORDINATE",
+        TrackError::TooShort | TrackError::TooLong | TrackError::TooFar => "INVALID_TRACK_LENGTH",
+    };
+    gql_err(code, e.to_string())
+}
+
+#[derive(Default)]
+pub struct WalkMutation;
+
+#[Object]
+impl WalkMutation {
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn log_walk(&self, ctx: &Context<'_>, input: LogWalkInput) -> Result<Walk> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let walk = ServiceWalkMutation::add_walk(
+            conn,
+            user_id,
+            pet.id,
+            input.distance_m,
+            input.duration_minutes,
+            input.walked_at,
+        )
+        .await?;
+
+        Ok(Walk::new(walk, None))
+    }
+
+    /// Attach a route from a GPX file or a list of coordinates to one of the user's walks.
+    /// The walk's distance, and its duration when the points are timed, are computed from
+    /// the route and replace what was entered.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn attach_walk_track(
+        &self,
+        ctx: &Context<'_>,
+        input: AttachWalkTrackInput,
+    ) -> Result<Walk> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+        WalkQuery::user_walk(conn, user_id, PetRole::Caretaker, input.walk_id)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let points = match (input.gpx, input.points) {
+            (Some(gpx), None) => parse_gpx(&gpx).map_err(track_err)?,
+            (None, Some(points)) => points
+                .into_iter()
+                .map(|p| TrackPoint {
+                    lat: p.lat,
+                    lon: p.lon,
+                    at: p.at,
+                })
+                .collect(),
+            _ => {
+                return Err(gql_err(
+                    "TRACK_SOURCE_REQUIRED",
+                    "Provide exactly one of gpx and points",
+                ))
+            }
+        };
+        let track = Track::from_points(&points).map_err(track_err)?;
+
+        let track = ServiceWalkMutation::attach_track(conn, input.walk_id, track).await?;
+        let walk = WalkQuery::user_walk(conn, user_id, PetRole::Caretaker, input.walk_id).await?;
+
+        Ok(Walk::new(walk, Some(track)))
+    }
+}
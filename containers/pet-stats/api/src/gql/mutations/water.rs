@@ -0,0 +1,36 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{LogWaterInput, WaterRecord};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::mutations::water_record::WaterRecordMutation;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct WaterMutation;
+
+#[Object]
+impl WaterMutation {
+    /// Record water a pet drank, e.g. from a smart fountain holding a `pets:write` key.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, input))]
+    pub async fn log_water(&self, ctx: &Context<'_>, input: LogWaterInput) -> Result<WaterRecord> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsWrite)?;
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, input.pet_id, PetRole::Caretaker)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let record =
+            WaterRecordMutation::add_water_record(conn, pet.id, input.amount_ml, input.drank_at)
+                .await?;
+
+        Ok(WaterRecord::from(record))
+    }
+}
@@ -1,10 +1,28 @@
-use async_graphql::{Enum, InputObject, SimpleObject};
-use chrono::NaiveDate;
-use entity::entities::{pets, users};
+use async_graphql::{
+    ComplexObject, Context, Enum, InputObject, OneofObject, Result, SimpleObject, Union,
+};
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    api_keys, breeds, care_task_completions, care_tasks, devices, elimination_records, expenses,
+    feed_records, food_purchases, foods, journal_entries, medication_doses, medications,
+    pet_invitations, pet_members, pets, security_events, species, users, vaccinations, vet_visits,
+    walk_tracks, water_records,
+};
 use sea_orm::{
-    prelude::DateTimeWithTimeZone,
+    prelude::{DateTimeWithTimeZone, Decimal},
     ActiveValue::{NotSet, Set},
 };
+use service::auth::api_key::ApiScope;
+use service::journal::normalize_symptoms;
+use service::queries::{
+    breed::BreedQuery, feed_record::FeedRecordQuery, food::FoodQuery, user::UserQuery,
+};
+use service::schedule::today_in;
+
+use crate::db::Database;
+use crate::gql::loaders::load_species;
+use crate::gql::utils::{authorized_user_id, day_bounds};
+use crate::gql::validators::PlausibleDate;
 
 #[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
 #[graphql(remote = "entity::entities::sea_orm_active_enums::LoginType")]
@@ -18,6 +36,8 @@ pub struct User {
     pub id: i32,
     pub email: Option<String>,
     pub login_type: LoginType,
+    /// IANA timezone name, decides what "today" is for the user.
+    pub timezone: String,
 }
 
 impl From<users::Model> for User {
@@ -26,6 +46,7 @@ impl From<users::Model> for User {
             id: entity.id,
             email: entity.email,
             login_type: LoginType::from(entity.login_type),
+            timezone: entity.timezone,
         }
     }
 }
@@ -37,30 +58,196 @@ pub(crate) struct DefaultPet {
 }
 
 #[derive(Debug, SimpleObject)]
+#[graphql(complex)]
 pub struct Pet {
     #[graphql(flatten)]
     default: DefaultPet,
     pub feed_count: Option<i32>,
     pub sex: PetSexType,
-    pub species: PetSpeciesType,
+    pub species_id: i32,
     pub feed_count_per: Option<FeedDurationType>,
     pub birthday: NaiveDate,
     pub birthday_precision: DateDurationType,
+    /// In kg.
     pub weight: Option<f32>,
+    pub breed_id: Option<i32>,
     pub created_at: DateTimeWithTimeZone,
 }
 
+impl Pet {
+    async fn load_species(&self, ctx: &Context<'_>) -> Result<species::Model> {
+        load_species(ctx, self.species_id).await
+    }
+
+    async fn load_breed(&self, ctx: &Context<'_>) -> Result<Option<breeds::Model>> {
+        let Some(breed_id) = self.breed_id else {
+            return Ok(None);
+        };
+        let db = ctx.data::<Database>()?;
+        Ok(Some(
+            BreedQuery::by_id(db.get_connection(), breed_id).await?,
+        ))
+    }
+
+    fn computed_age(&self) -> service::pet_age::PetAge {
+        service::pet_age::PetAge::on(
+            self.birthday,
+            &self.birthday_precision.into(),
+            Local::now().date_naive(),
+        )
+    }
+}
+
+#[ComplexObject]
+impl Pet {
+    /// `Other` for species outside the built-in ones, see `speciesDetails`.
+    async fn species(&self, ctx: &Context<'_>) -> Result<PetSpeciesType> {
+        Ok(service::species::PetSpeciesType::of(&self.load_species(ctx).await?).into())
+    }
+
+    async fn species_details(&self, ctx: &Context<'_>) -> Result<Species> {
+        Ok(Species::from(self.load_species(ctx).await?))
+    }
+
+    async fn breed(&self, ctx: &Context<'_>) -> Result<Option<Breed>> {
+        Ok(self.load_breed(ctx).await?.map(Breed::from))
+    }
+
+    /// How `weight` compares to the typical weight of the breed.
+    async fn weight_status(&self, ctx: &Context<'_>) -> Result<Option<WeightStatus>> {
+        Ok(self
+            .load_breed(ctx)
+            .await?
+            .and_then(|breed| service::queries::breed::weight_status(self.weight, &breed))
+            .map(WeightStatus::from))
+    }
+
+    /// Age as of today, as precise as `birthdayPrecision` allows.
+    async fn age(&self) -> PetAge {
+        PetAge::from(self.computed_age())
+    }
+
+    /// Not set for species without known life stage ages.
+    async fn life_stage(&self, ctx: &Context<'_>) -> Result<Option<LifeStage>> {
+        let species = self.load_species(ctx).await?;
+        Ok(service::pet_age::life_stage(&species, &self.computed_age()).map(LifeStage::from))
+    }
+
+    /// kcal per day a dog or cat of its weight needs at rest.
+    async fn resting_energy_requirement(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let Some(weight) = self.weight else {
+            return Ok(None);
+        };
+        let species = self.load_species(ctx).await?;
+        Ok(service::nutrition::resting_energy_requirement(
+            service::species::PetSpeciesType::of(&species),
+            weight,
+        ))
+    }
+
+    /// Calories eaten on `date` in the user's timezone, today by default.
+    async fn calorie_intake(
+        &self,
+        ctx: &Context<'_>,
+        date: Option<NaiveDate>,
+    ) -> Result<CalorieIntake> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+        let user = UserQuery::user_by_id(conn, user_id).await?;
+        let date = date.unwrap_or_else(|| today_in(&user.timezone));
+
+        let (from, until) = day_bounds(&user.timezone, date)?;
+        let feedings =
+            FeedRecordQuery::with_food_between(conn, self.default.id, from, until).await?;
+        let intake = service::nutrition::CalorieIntake::of(&feedings);
+
+        Ok(CalorieIntake {
+            date,
+            kcal: intake.kcal,
+            target_kcal: self.resting_energy_requirement(ctx).await?,
+            unknown_feedings: intake.unknown_feedings,
+        })
+    }
+
+    /// Age in human years, only for dogs and cats.
+    async fn human_equivalent_age(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let species = self.load_species(ctx).await?;
+        Ok(service::pet_age::human_equivalent_years(
+            service::species::PetSpeciesType::of(&species),
+            &self.computed_age(),
+        ))
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct PetAge {
+    pub years: i32,
+    /// Not set when only the birth year is known.
+    pub months: Option<i32>,
+    /// Only set for full date birthdays.
+    pub days: Option<i64>,
+    pub approximate: bool,
+    /// e.g. `3 years 2 months` or `about 3 years`.
+    pub text: String,
+}
+
+impl From<service::pet_age::PetAge> for PetAge {
+    fn from(age: service::pet_age::PetAge) -> Self {
+        Self {
+            years: age.years,
+            months: age.months,
+            days: age.days,
+            approximate: age.is_approximate(),
+            text: age.describe(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "service::pet_age::LifeStage")]
+pub enum LifeStage {
+    /// Puppy, kitten or juvenile.
+    Young,
+    Adult,
+    Senior,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct UpcomingBirthday {
+    pub pet: Pet,
+    /// The birthday, or the first of the month when only the birth month is known.
+    pub on: NaiveDate,
+    pub turning: i32,
+}
+
+impl From<service::queries::pet::UpcomingBirthday> for UpcomingBirthday {
+    fn from(birthday: service::queries::pet::UpcomingBirthday) -> Self {
+        Self {
+            pet: Pet::from(birthday.pet),
+            on: birthday.on,
+            turning: birthday.turning,
+        }
+    }
+}
+
 #[derive(Debug, InputObject)]
 pub(crate) struct NewPetInput {
     pub name: String,
     pub sex: PetSexType,
-    pub species: PetSpeciesType,
+    /// One of the built-in species, use `speciesId` for any other.
+    pub species: Option<PetSpeciesType>,
+    /// Takes precedence over `species`, see the `species` query.
+    pub species_id: Option<i32>,
     #[graphql(default = 1)]
     pub feed_count: i32,
     pub feed_count_per: Option<FeedDurationType>,
     pub birthday: NaiveDate,
     pub birthday_precision: DateDurationType,
     pub weight: Option<f32>,
+    /// Must be a breed of `species`.
+    pub breed_id: Option<i32>,
 }
 
 #[derive(Debug, InputObject)]
@@ -71,6 +258,7 @@ pub(crate) struct UpdatePetInput {
     pub birthday: Option<NaiveDate>,
     pub birthday_precision: Option<DateDurationType>,
     pub weight: Option<f32>,
+    pub breed_id: Option<i32>,
 }
 
 #[derive(Debug, SimpleObject)]
@@ -103,11 +291,12 @@ impl From<NewPetInput> for pets::ActiveModel {
         pets::ActiveModel {
             name: Set(value.name),
             sex: Set(value.sex.into()),
-            species: Set(value.species.into()),
             birthday: Set(value.birthday),
             birthday_precision: Set(value.birthday_precision.into()),
             feed_count: Set(Some(value.feed_count)),
             feed_count_per: Set(value.feed_count_per.map(|v| v.into())),
+            weight: Set(value.weight),
+            breed_id: Set(value.breed_id),
             ..Default::default()
         }
     }
@@ -125,6 +314,7 @@ impl From<UpdatePetInput> for pets::ActiveModel {
                 .map(|p| Set(p.into()))
                 .unwrap_or(NotSet),
             weight: value.weight.map(|w| Set(Some(w))).unwrap_or(NotSet),
+            breed_id: value.breed_id.map(|b| Set(Some(b))).unwrap_or(NotSet),
             ..Default::default()
         }
     }
@@ -138,19 +328,20 @@ impl From<pets::Model> for Pet {
                 name: value.name,
             },
             sex: PetSexType::from(value.sex),
-            species: PetSpeciesType::from(value.species),
+            species_id: value.species_id,
             feed_count: value.feed_count,
             feed_count_per: value.feed_count_per.map(FeedDurationType::from),
             birthday: value.birthday,
             birthday_precision: DateDurationType::from(value.birthday_precision),
             weight: value.weight,
+            breed_id: value.breed_id,
             created_at: value.created_at,
         }
     }
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
-#[graphql(remote = "entity::entities::sea_orm_active_enums::PetSpeciesType")]
+#[graphql(remote = "service::species::PetSpeciesType")]
 pub enum PetSpeciesType {
     Dog,
     Cat,
@@ -158,6 +349,20 @@ pub enum PetSpeciesType {
     Lizard,
     Turtle,
     Snake,
+    /// Any species not listed above.
+    Other,
+}
+
+/// What a member may do with a pet. Each role can do everything the ones below it can.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::PetRole")]
+pub enum PetRole {
+    /// Edits and removes the pet, invites and removes members.
+    Owner,
+    /// Logs and edits the pet's records.
+    Caretaker,
+    /// Sees the pet and its records.
+    Viewer,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
@@ -204,14 +409,1356 @@ pub struct OauthSignInInput {
     pub provider_type: ProviderType,
 }
 
+/// Proof that the signed-in user is present, asked for before sensitive operations.
+#[derive(OneofObject, Debug)]
+pub enum ReauthenticationInput {
+    /// A freshly issued ID token of the same provider account.
+    Oauth(OauthSignInInput),
+    /// The password of an email and password account.
+    Password(PasswordReauthenticationInput),
+}
+
+#[derive(InputObject, Debug)]
+pub struct PasswordReauthenticationInput {
+    pub password: String,
+    /// Code from the authenticator app or an unused recovery code, when two-factor
+    /// authentication is enabled.
+    pub code: Option<String>,
+}
+
 #[derive(SimpleObject, Debug)]
 pub struct SignOutPayload {
     pub success: bool,
     pub message: String,
 }
 
+#[derive(SimpleObject, Debug)]
+pub struct AccountDeletionPayload {
+    pub success: bool,
+    pub deletion_scheduled_at: Option<DateTimeWithTimeZone>,
+    pub message: String,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct AccessTokenRevocationPayload {
+    pub success: bool,
+    pub message: String,
+}
+
+/// What the server knows about an access token. Inactive tokens carry no details.
+#[derive(SimpleObject, Debug)]
+pub struct AccessTokenIntrospection {
+    pub active: bool,
+    pub user_id: Option<i32>,
+    pub issued_at: Option<DateTimeWithTimeZone>,
+    pub expires_at: Option<DateTimeWithTimeZone>,
+}
+
+impl AccessTokenIntrospection {
+    pub(crate) fn inactive() -> Self {
+        Self {
+            active: false,
+            user_id: None,
+            issued_at: None,
+            expires_at: None,
+        }
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct TokenRotationPayload {
     pub access_token: String,
     pub refresh_token: String,
 }
+
+#[derive(InputObject, Debug)]
+pub struct LocalSignInInput {
+    pub email: String,
+    pub password: String,
+}
+
+/// Returned by the password step when the account has two-factor authentication enabled.
+#[derive(SimpleObject, Debug)]
+pub struct TwoFactorChallengePayload {
+    pub challenge_token: String,
+    pub expires_at: DateTimeWithTimeZone,
+}
+
+#[derive(Union)]
+pub enum LocalSignInPayload {
+    Signed(OauthPayload),
+    TwoFactorRequired(TwoFactorChallengePayload),
+}
+
+#[derive(InputObject, Debug)]
+pub struct TwoFactorVerificationInput {
+    pub challenge_token: String,
+    /// Code from the authenticator app or an unused recovery code.
+    pub code: String,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct TotpEnrollmentPayload {
+    pub otpauth_uri: String,
+    /// Base32 secret for authenticator apps that can't scan the URI.
+    pub secret: String,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct TotpConfirmationPayload {
+    pub success: bool,
+    /// Shown only once, each code signs in a single time.
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct TotpDisablePayload {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::SecurityEventType")]
+pub enum SecurityEventType {
+    SignIn,
+    SignInFailed,
+    SignOut,
+    TokenRotated,
+    TokenRotationFailed,
+    TwoFactorEnabled,
+    TwoFactorDisabled,
+    TwoFactorFailed,
+    ReauthenticationFailed,
+    AccountDeletionScheduled,
+    AccessTokenRejected,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct SecurityEvent {
+    pub id: i64,
+    pub event_type: SecurityEventType,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device_id: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<security_events::Model> for SecurityEvent {
+    fn from(entity: security_events::Model) -> Self {
+        Self {
+            id: entity.id,
+            event_type: SecurityEventType::from(entity.event_type),
+            ip_address: entity.ip_address,
+            user_agent: entity.user_agent,
+            device_id: entity.device_id,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "service::auth::api_key::ApiScope")]
+pub enum ApiKeyScope {
+    PetsRead,
+    PetsWrite,
+    FeedRead,
+    FeedWrite,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    /// First characters of the key, to tell keys apart.
+    pub key_prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<api_keys::Model> for ApiKey {
+    fn from(entity: api_keys::Model) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            key_prefix: entity.key_prefix,
+            scopes: entity
+                .scopes
+                .iter()
+                .filter_map(|s| s.parse::<service::auth::api_key::ApiScope>().ok())
+                .map(ApiKeyScope::from)
+                .collect(),
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct CreateApiKeyInput {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct CreatedApiKeyPayload {
+    /// The key itself. It is not stored and can't be shown again.
+    pub key: String,
+    pub api_key: ApiKey,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Device {
+    pub id: i32,
+    pub pet_id: i32,
+    /// Identifies the device in ingest URLs.
+    pub uid: String,
+    pub name: String,
+    pub last_seen_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<devices::Model> for Device {
+    fn from(entity: devices::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            uid: entity.uid,
+            name: entity.name,
+            last_seen_at: entity.last_seen_at,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct RegisterDeviceInput {
+    pub pet_id: i32,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub name: String,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct RegisteredDevicePayload {
+    /// Key the device signs its requests with. It can't be shown again.
+    pub secret: String,
+    pub device: Device,
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct FeedRecord {
+    pub id: i32,
+    pub pet_id: i32,
+    /// In `unit`.
+    pub amount: Option<f32>,
+    pub food_id: Option<i32>,
+    pub unit: Option<FoodUnit>,
+    pub fed_at: DateTimeWithTimeZone,
+}
+
+impl From<feed_records::Model> for FeedRecord {
+    fn from(entity: feed_records::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            amount: entity.amount,
+            food_id: entity.food_id,
+            unit: entity.unit.map(FoodUnit::from),
+            fed_at: entity.created_at,
+        }
+    }
+}
+
+impl FeedRecord {
+    async fn load_food(&self, ctx: &Context<'_>) -> Result<Option<foods::Model>> {
+        let Some(food_id) = self.food_id else {
+            return Ok(None);
+        };
+        let db = ctx.data::<Database>()?;
+        Ok(Some(FoodQuery::by_id(db.get_connection(), food_id).await?))
+    }
+}
+
+#[ComplexObject]
+impl FeedRecord {
+    async fn food(&self, ctx: &Context<'_>) -> Result<Option<Food>> {
+        Ok(self.load_food(ctx).await?.map(Food::from))
+    }
+
+    /// Energy of the feeding, when the food has a kcal value for `unit`.
+    async fn kcal(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let (Some(amount), Some(unit)) = (self.amount, self.unit) else {
+            return Ok(None);
+        };
+        Ok(self
+            .load_food(ctx)
+            .await?
+            .and_then(|food| service::nutrition::kcal(&food, amount, &unit.into())))
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct LogFeedInput {
+    pub pet_id: i32,
+    #[graphql(validator(minimum = 0))]
+    pub amount: Option<f32>,
+    /// One of the user's foods.
+    pub food_id: Option<i32>,
+    /// Unit of `amount`, defaults to the unit of the food.
+    pub unit: Option<FoodUnit>,
+    /// Defaults to now.
+    pub fed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::FoodUnit")]
+pub enum FoodUnit {
+    Gram,
+    Cup,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Food {
+    pub id: i32,
+    pub brand: String,
+    pub name: String,
+    pub kcal_per_100g: Option<f32>,
+    pub kcal_per_cup: Option<f32>,
+    /// How the food is usually measured out.
+    pub unit: FoodUnit,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<foods::Model> for Food {
+    fn from(entity: foods::Model) -> Self {
+        Self {
+            id: entity.id,
+            brand: entity.brand,
+            name: entity.name,
+            kcal_per_100g: entity.kcal_per_100g,
+            kcal_per_cup: entity.kcal_per_cup,
+            unit: FoodUnit::from(entity.unit),
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewFoodInput {
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub brand: String,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub name: String,
+    #[graphql(validator(minimum = 0))]
+    pub kcal_per_100g: Option<f32>,
+    #[graphql(validator(minimum = 0))]
+    pub kcal_per_cup: Option<f32>,
+    /// Needs the matching kcal value.
+    pub unit: FoodUnit,
+}
+
+impl From<NewFoodInput> for foods::ActiveModel {
+    fn from(value: NewFoodInput) -> Self {
+        foods::ActiveModel {
+            brand: Set(value.brand),
+            name: Set(value.name),
+            kcal_per_100g: Set(value.kcal_per_100g),
+            kcal_per_cup: Set(value.kcal_per_cup),
+            unit: Set(value.unit.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct FoodPurchase {
+    pub id: i32,
+    pub food_id: i32,
+    pub size: f32,
+    pub unit: FoodUnit,
+    pub purchased_on: NaiveDate,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<food_purchases::Model> for FoodPurchase {
+    fn from(entity: food_purchases::Model) -> Self {
+        Self {
+            id: entity.id,
+            food_id: entity.food_id,
+            size: entity.size,
+            unit: FoodUnit::from(entity.unit),
+            purchased_on: entity.purchased_on,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct RecordFoodPurchaseInput {
+    pub food_id: i32,
+    /// Size of the bag in `unit`, up to a tonne's worth.
+    #[graphql(validator(minimum = 0, maximum = 1000000))]
+    pub size: f32,
+    /// Defaults to the unit of the food.
+    pub unit: Option<FoodUnit>,
+    /// Defaults to today.
+    #[graphql(validator(custom = "PlausibleDate"))]
+    pub purchased_on: Option<NaiveDate>,
+}
+
+/// Estimated stock of a food, amounts in the unit of the food.
+#[derive(SimpleObject, Debug)]
+pub struct FoodStock {
+    pub food: Food,
+    pub bought: f64,
+    /// What was bought less what was fed since the first purchase.
+    pub remaining: f64,
+    /// Average over the last two weeks.
+    pub daily_use: f64,
+    /// Not set while none of the food is being used.
+    pub runs_out_on: Option<NaiveDate>,
+    /// Feedings without an amount, or in a unit that can't be converted.
+    pub untracked_feedings: u32,
+}
+
+impl From<service::pantry::FoodStock> for FoodStock {
+    fn from(stock: service::pantry::FoodStock) -> Self {
+        Self {
+            food: Food::from(stock.food),
+            bought: stock.bought,
+            remaining: stock.remaining,
+            daily_use: stock.daily_use,
+            runs_out_on: stock.runs_out_on,
+            untracked_feedings: stock.untracked_feedings,
+        }
+    }
+}
+
+/// Calories a pet ate on a day against what it needs at rest.
+#[derive(SimpleObject, Debug)]
+pub struct CalorieIntake {
+    pub date: NaiveDate,
+    pub kcal: f64,
+    /// Resting energy requirement, see `Pet.restingEnergyRequirement`.
+    pub target_kcal: Option<f64>,
+    /// Feedings that could not be counted, without a food, amount or known kcal value.
+    pub unknown_feedings: u32,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Vaccination {
+    pub id: i32,
+    pub pet_id: i32,
+    pub vaccine: String,
+    pub given_on: NaiveDate,
+    pub next_due_on: Option<NaiveDate>,
+    pub clinic: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<vaccinations::Model> for Vaccination {
+    fn from(entity: vaccinations::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            vaccine: entity.vaccine,
+            given_on: entity.given_on,
+            next_due_on: entity.next_due_on,
+            clinic: entity.clinic,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewVaccinationInput {
+    pub pet_id: i32,
+    pub vaccine: String,
+    pub given_on: NaiveDate,
+    pub next_due_on: Option<NaiveDate>,
+    pub clinic: Option<String>,
+}
+
+impl From<NewVaccinationInput> for vaccinations::ActiveModel {
+    fn from(value: NewVaccinationInput) -> Self {
+        vaccinations::ActiveModel {
+            pet_id: Set(value.pet_id),
+            vaccine: Set(value.vaccine),
+            given_on: Set(value.given_on),
+            next_due_on: Set(value.next_due_on),
+            clinic: Set(value.clinic),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct VetVisit {
+    pub id: i32,
+    pub pet_id: i32,
+    pub visited_on: NaiveDate,
+    pub reason: String,
+    pub diagnosis: Option<String>,
+    pub notes: Option<String>,
+    /// In minor currency units, e.g. cents.
+    pub cost_cents: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<vet_visits::Model> for VetVisit {
+    fn from(entity: vet_visits::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            visited_on: entity.visited_on,
+            reason: entity.reason,
+            diagnosis: entity.diagnosis,
+            notes: entity.notes,
+            cost_cents: entity.cost_cents,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewVetVisitInput {
+    pub pet_id: i32,
+    pub visited_on: NaiveDate,
+    pub reason: String,
+    pub diagnosis: Option<String>,
+    pub notes: Option<String>,
+    #[graphql(validator(minimum = 0))]
+    pub cost_cents: Option<i32>,
+}
+
+impl From<NewVetVisitInput> for vet_visits::ActiveModel {
+    fn from(value: NewVetVisitInput) -> Self {
+        vet_visits::ActiveModel {
+            pet_id: Set(value.pet_id),
+            visited_on: Set(value.visited_on),
+            reason: Set(value.reason),
+            diagnosis: Set(value.diagnosis),
+            notes: Set(value.notes),
+            cost_cents: Set(value.cost_cents),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::MedicationFrequency")]
+pub enum MedicationFrequency {
+    Daily,
+    EveryOtherDay,
+    Weekly,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::DoseStatus")]
+pub enum DoseStatus {
+    Given,
+    Skipped,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Medication {
+    pub id: i32,
+    pub pet_id: i32,
+    pub name: String,
+    pub dose: f32,
+    pub unit: String,
+    pub frequency: MedicationFrequency,
+    pub doses_per_day: i32,
+    pub start_on: NaiveDate,
+    pub end_on: Option<NaiveDate>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<medications::Model> for Medication {
+    fn from(entity: medications::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            name: entity.name,
+            dose: entity.dose,
+            unit: entity.unit,
+            frequency: MedicationFrequency::from(entity.frequency),
+            doses_per_day: entity.doses_per_day,
+            start_on: entity.start_on,
+            end_on: entity.end_on,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewMedicationInput {
+    pub pet_id: i32,
+    pub name: String,
+    pub dose: f32,
+    /// e.g. `mg`, `ml` or `tablet`.
+    pub unit: String,
+    pub frequency: MedicationFrequency,
+    #[graphql(default = 1, validator(minimum = 1, maximum = 12))]
+    pub doses_per_day: i32,
+    pub start_on: NaiveDate,
+    pub end_on: Option<NaiveDate>,
+}
+
+impl From<NewMedicationInput> for medications::ActiveModel {
+    fn from(value: NewMedicationInput) -> Self {
+        medications::ActiveModel {
+            pet_id: Set(value.pet_id),
+            name: Set(value.name),
+            dose: Set(value.dose),
+            unit: Set(value.unit),
+            frequency: Set(value.frequency.into()),
+            doses_per_day: Set(value.doses_per_day),
+            start_on: Set(value.start_on),
+            end_on: Set(value.end_on),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct RecordDoseInput {
+    pub medication_id: i32,
+    pub scheduled_on: NaiveDate,
+    /// 1 based position of the dose within the day.
+    #[graphql(default = 1)]
+    pub dose_number: i32,
+    pub status: DoseStatus,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct MedicationDose {
+    pub id: i32,
+    pub medication_id: i32,
+    pub scheduled_on: NaiveDate,
+    pub dose_number: i32,
+    pub status: DoseStatus,
+    pub recorded_at: DateTimeWithTimeZone,
+}
+
+impl From<medication_doses::Model> for MedicationDose {
+    fn from(entity: medication_doses::Model) -> Self {
+        Self {
+            id: entity.id,
+            medication_id: entity.medication_id,
+            scheduled_on: entity.scheduled_on,
+            dose_number: entity.dose_number,
+            status: DoseStatus::from(entity.status),
+            recorded_at: entity.recorded_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct DueDose {
+    pub medication: Medication,
+    pub dose_number: i32,
+    /// Not set while the dose is still pending.
+    pub status: Option<DoseStatus>,
+    pub recorded_at: Option<DateTimeWithTimeZone>,
+}
+
+impl From<service::queries::medication::DueDose> for DueDose {
+    fn from(due: service::queries::medication::DueDose) -> Self {
+        Self {
+            medication: Medication::from(due.medication),
+            dose_number: due.dose_number,
+            status: due
+                .logged
+                .as_ref()
+                .map(|l| DoseStatus::from(l.status.clone())),
+            recorded_at: due.logged.map(|l| l.recorded_at),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct PetDueDoses {
+    pub pet_id: i32,
+    /// The user's current date the doses are due on.
+    pub date: NaiveDate,
+    pub doses: Vec<DueDose>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct CareTask {
+    pub id: i32,
+    pub pet_id: i32,
+    pub title: String,
+    pub notes: Option<String>,
+    /// iCalendar RRULE, e.g. `FREQ=WEEKLY;BYDAY=SA`.
+    pub rrule: String,
+    pub starts_on: NaiveDate,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<care_tasks::Model> for CareTask {
+    fn from(entity: care_tasks::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            title: entity.title,
+            notes: entity.notes,
+            rrule: entity.rrule,
+            starts_on: entity.starts_on,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewCareTaskInput {
+    pub pet_id: i32,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub title: String,
+    pub notes: Option<String>,
+    /// iCalendar RRULE with FREQ of DAILY, WEEKLY, MONTHLY or YEARLY. INTERVAL, COUNT, UNTIL,
+    /// BYDAY (weekly) and BYMONTHDAY (monthly) are supported.
+    #[graphql(validator(max_length = 255))]
+    pub rrule: String,
+    #[graphql(validator(custom = "PlausibleDate"))]
+    pub starts_on: NaiveDate,
+}
+
+impl From<NewCareTaskInput> for care_tasks::ActiveModel {
+    fn from(value: NewCareTaskInput) -> Self {
+        care_tasks::ActiveModel {
+            pet_id: Set(value.pet_id),
+            title: Set(value.title),
+            notes: Set(value.notes),
+            rrule: Set(value.rrule),
+            starts_on: Set(value.starts_on),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct CompleteCareTaskInput {
+    pub care_task_id: i32,
+    /// Occurrence being completed, the next open one when omitted.
+    #[graphql(validator(custom = "PlausibleDate"))]
+    pub due_on: Option<NaiveDate>,
+    pub note: Option<String>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct CareTaskCompletion {
+    pub id: i32,
+    pub care_task_id: i32,
+    pub due_on: NaiveDate,
+    pub note: Option<String>,
+    pub completed_at: DateTimeWithTimeZone,
+}
+
+impl From<care_task_completions::Model> for CareTaskCompletion {
+    fn from(entity: care_task_completions::Model) -> Self {
+        Self {
+            id: entity.id,
+            care_task_id: entity.care_task_id,
+            due_on: entity.due_on,
+            note: entity.note,
+            completed_at: entity.completed_at,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "service::queries::care_task::CareTaskStatus")]
+pub enum CareTaskStatus {
+    Overdue,
+    DueToday,
+    Upcoming,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct CareAgendaItem {
+    pub care_task: CareTask,
+    pub due_on: NaiveDate,
+    pub status: CareTaskStatus,
+}
+
+impl From<service::queries::care_task::CareAgendaItem> for CareAgendaItem {
+    fn from(item: service::queries::care_task::CareAgendaItem) -> Self {
+        Self {
+            care_task: CareTask::from(item.care_task),
+            due_on: item.due_on,
+            status: CareTaskStatus::from(item.status),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+#[graphql(complex)]
+pub struct Breed {
+    pub id: i32,
+    pub species_id: i32,
+    pub name: String,
+    pub min_weight_kg: Option<f32>,
+    pub max_weight_kg: Option<f32>,
+    pub min_life_expectancy_years: i32,
+    pub max_life_expectancy_years: i32,
+}
+
+impl From<breeds::Model> for Breed {
+    fn from(entity: breeds::Model) -> Self {
+        Self {
+            id: entity.id,
+            species_id: entity.species_id,
+            name: entity.name,
+            min_weight_kg: entity.min_weight_kg,
+            max_weight_kg: entity.max_weight_kg,
+            min_life_expectancy_years: entity.min_life_expectancy_years,
+            max_life_expectancy_years: entity.max_life_expectancy_years,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Breed {
+    async fn species(&self, ctx: &Context<'_>) -> Result<PetSpeciesType> {
+        let species = load_species(ctx, self.species_id).await?;
+        Ok(service::species::PetSpeciesType::of(&species).into())
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "service::queries::breed::WeightStatus")]
+pub enum WeightStatus {
+    Under,
+    Within,
+    Over,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Species {
+    pub id: i32,
+    pub name: String,
+    /// `Other` for everything but the built-in species.
+    pub kind: PetSpeciesType,
+    /// Defined by the user rather than shared.
+    pub custom: bool,
+    /// Age in months a pet of the species counts as adult.
+    pub adult_from_months: Option<i32>,
+    /// Age in months a pet of the species counts as senior.
+    pub senior_from_months: Option<i32>,
+}
+
+impl From<species::Model> for Species {
+    fn from(entity: species::Model) -> Self {
+        Self {
+            id: entity.id,
+            kind: service::species::PetSpeciesType::of(&entity).into(),
+            name: entity.name,
+            custom: entity.user_id.is_some(),
+            adult_from_months: entity.adult_from_months,
+            senior_from_months: entity.senior_from_months,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::ExpenseCategory")]
+pub enum ExpenseCategory {
+    Food,
+    Vet,
+    Grooming,
+    Toys,
+    Insurance,
+    Other,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Expense {
+    pub id: i32,
+    pub pet_id: i32,
+    pub category: ExpenseCategory,
+    pub amount: Decimal,
+    /// ISO 4217 code, e.g. `EUR`.
+    pub currency: String,
+    pub spent_on: NaiveDate,
+    pub note: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<expenses::Model> for Expense {
+    fn from(entity: expenses::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            category: ExpenseCategory::from(entity.category),
+            amount: entity.amount,
+            currency: entity.currency,
+            spent_on: entity.spent_on,
+            note: entity.note,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewExpenseInput {
+    pub pet_id: i32,
+    pub category: ExpenseCategory,
+    /// Up to two decimal places.
+    pub amount: Decimal,
+    #[graphql(validator(regex = "^[A-Z]{3}$"))]
+    pub currency: String,
+    pub spent_on: NaiveDate,
+    #[graphql(validator(max_length = 255))]
+    pub note: Option<String>,
+}
+
+impl From<NewExpenseInput> for expenses::ActiveModel {
+    fn from(value: NewExpenseInput) -> Self {
+        expenses::ActiveModel {
+            pet_id: Set(value.pet_id),
+            category: Set(value.category.into()),
+            amount: Set(value.amount),
+            currency: Set(value.currency),
+            spent_on: Set(value.spent_on),
+            note: Set(value.note),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct UpdateExpenseInput {
+    pub id: i32,
+    pub category: Option<ExpenseCategory>,
+    pub amount: Option<Decimal>,
+    #[graphql(validator(regex = "^[A-Z]{3}$"))]
+    pub currency: Option<String>,
+    pub spent_on: Option<NaiveDate>,
+    #[graphql(validator(max_length = 255))]
+    pub note: Option<String>,
+}
+
+impl From<UpdateExpenseInput> for expenses::ActiveModel {
+    fn from(value: UpdateExpenseInput) -> Self {
+        expenses::ActiveModel {
+            id: Set(value.id),
+            category: value.category.map(|c| Set(c.into())).unwrap_or(NotSet),
+            amount: value.amount.map(Set).unwrap_or(NotSet),
+            currency: value.currency.map(Set).unwrap_or(NotSet),
+            spent_on: value.spent_on.map(Set).unwrap_or(NotSet),
+            note: value.note.map(|n| Set(Some(n))).unwrap_or(NotSet),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "service::queries::expense::ExpenseGrouping")]
+pub enum ExpenseGrouping {
+    Month,
+    Category,
+    Pet,
+}
+
+/// Spending in one currency. Only the dimensions the report is grouped by are set.
+#[derive(SimpleObject, Debug)]
+pub struct ExpenseTotal {
+    /// First day of the month.
+    pub month: Option<NaiveDate>,
+    pub category: Option<ExpenseCategory>,
+    pub pet_id: Option<i32>,
+    pub currency: String,
+    pub total: Decimal,
+    pub count: i64,
+}
+
+impl From<service::queries::expense::ExpenseTotal> for ExpenseTotal {
+    fn from(total: service::queries::expense::ExpenseTotal) -> Self {
+        Self {
+            month: total.month,
+            category: total.category.map(ExpenseCategory::from),
+            pet_id: total.pet_id,
+            currency: total.currency,
+            total: total.total,
+            count: total.count,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct JournalEntry {
+    pub id: i32,
+    pub pet_id: i32,
+    pub body: String,
+    /// 1 (low) to 5 (great).
+    pub mood: Option<i32>,
+    /// 1 (lethargic) to 5 (very active).
+    pub energy: Option<i32>,
+    /// Lowercase tags, e.g. `vomiting` or `limping`.
+    pub symptoms: Vec<String>,
+    pub noted_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<journal_entries::Model> for JournalEntry {
+    fn from(entity: journal_entries::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            body: entity.body,
+            mood: entity.mood,
+            energy: entity.energy,
+            symptoms: entity.symptoms,
+            noted_at: entity.noted_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct NewJournalEntryInput {
+    pub pet_id: i32,
+    #[graphql(validator(max_length = 10000))]
+    pub body: String,
+    #[graphql(validator(minimum = 1, maximum = 5))]
+    pub mood: Option<i32>,
+    #[graphql(validator(minimum = 1, maximum = 5))]
+    pub energy: Option<i32>,
+    #[graphql(default, validator(max_items = 20, list, max_length = 50))]
+    pub symptoms: Vec<String>,
+    /// Defaults to now.
+    pub noted_at: Option<DateTimeWithTimeZone>,
+}
+
+impl From<NewJournalEntryInput> for journal_entries::ActiveModel {
+    fn from(value: NewJournalEntryInput) -> Self {
+        journal_entries::ActiveModel {
+            pet_id: Set(value.pet_id),
+            body: Set(value.body),
+            mood: Set(value.mood),
+            energy: Set(value.energy),
+            symptoms: Set(normalize_symptoms(value.symptoms)),
+            noted_at: value.noted_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct UpdateJournalEntryInput {
+    pub id: i32,
+    #[graphql(validator(max_length = 10000))]
+    pub body: Option<String>,
+    #[graphql(validator(minimum = 1, maximum = 5))]
+    pub mood: Option<i32>,
+    #[graphql(validator(minimum = 1, maximum = 5))]
+    pub energy: Option<i32>,
+    /// Replaces the entry's symptoms.
+    #[graphql(validator(max_items = 20, list, max_length = 50))]
+    pub symptoms: Option<Vec<String>>,
+    pub noted_at: Option<DateTimeWithTimeZone>,
+}
+
+impl From<UpdateJournalEntryInput> for journal_entries::ActiveModel {
+    fn from(value: UpdateJournalEntryInput) -> Self {
+        journal_entries::ActiveModel {
+            id: Set(value.id),
+            body: value.body.map(Set).unwrap_or(NotSet),
+            mood: value.mood.map(|m| Set(Some(m))).unwrap_or(NotSet),
+            energy: value.energy.map(|e| Set(Some(e))).unwrap_or(NotSet),
+            symptoms: value
+                .symptoms
+                .map(|s| Set(normalize_symptoms(s)))
+                .unwrap_or(NotSet),
+            noted_at: value.noted_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct SymptomCount {
+    pub symptom: String,
+    /// Journal entries noting the symptom.
+    pub entries: i64,
+    pub last_noted_at: DateTimeWithTimeZone,
+}
+
+impl From<service::queries::journal::SymptomCount> for SymptomCount {
+    fn from(count: service::queries::journal::SymptomCount) -> Self {
+        Self {
+            symptom: count.symptom,
+            entries: count.entries,
+            last_noted_at: count.last_noted_at,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum SearchItem {
+    Pet(Pet),
+    JournalEntry(JournalEntry),
+    VetVisit(VetVisit),
+}
+
+#[derive(SimpleObject)]
+pub struct SearchResult {
+    /// Higher is a better match. Only comparable within one search.
+    pub rank: f32,
+    pub item: SearchItem,
+}
+
+impl From<service::queries::search::SearchResult> for SearchResult {
+    fn from(result: service::queries::search::SearchResult) -> Self {
+        use service::queries::search::SearchItem as Item;
+
+        Self {
+            rank: result.rank,
+            item: match result.item {
+                Item::Pet(pet) => SearchItem::Pet(Pet::from(pet)),
+                Item::JournalEntry(entry) => SearchItem::JournalEntry(JournalEntry::from(entry)),
+                Item::VetVisit(visit) => SearchItem::VetVisit(VetVisit::from(visit)),
+            },
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+#[graphql(remote = "entity::entities::sea_orm_active_enums::EliminationType")]
+pub enum EliminationType {
+    Urine,
+    Stool,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct EliminationRecord {
+    pub id: i32,
+    pub pet_id: i32,
+    pub kind: EliminationType,
+    /// Stool score from 1 (hard) to 7 (liquid).
+    pub consistency: Option<i32>,
+    pub occurred_at: DateTimeWithTimeZone,
+}
+
+impl From<elimination_records::Model> for EliminationRecord {
+    fn from(entity: elimination_records::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            kind: EliminationType::from(entity.kind),
+            consistency: entity.consistency,
+            occurred_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct LogEliminationInput {
+    pub pet_id: i32,
+    pub kind: EliminationType,
+    /// Stool score from 1 (hard) to 7 (liquid). Not accepted for urine.
+    #[graphql(validator(minimum = 1, maximum = 7))]
+    pub consistency: Option<i32>,
+    /// Defaults to now.
+    pub occurred_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct DailyElimination {
+    pub date: NaiveDate,
+    pub urinations: i64,
+    pub stools: i64,
+    /// Of the stools that were scored.
+    pub average_consistency: Option<f64>,
+}
+
+impl From<service::queries::elimination_record::DailyElimination> for DailyElimination {
+    fn from(day: service::queries::elimination_record::DailyElimination) -> Self {
+        Self {
+            date: day.day,
+            urinations: day.urinations,
+            stools: day.stools,
+            average_consistency: day.average_consistency,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct WaterRecord {
+    pub id: i32,
+    pub pet_id: i32,
+    pub amount_ml: i32,
+    pub drank_at: DateTimeWithTimeZone,
+}
+
+impl From<water_records::Model> for WaterRecord {
+    fn from(entity: water_records::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            amount_ml: entity.amount_ml,
+            drank_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct LogWaterInput {
+    pub pet_id: i32,
+    #[graphql(validator(minimum = 1, maximum = 10000))]
+    pub amount_ml: i32,
+    /// Defaults to now.
+    pub drank_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct DailyWaterIntake {
+    pub date: NaiveDate,
+    pub total_ml: i64,
+    pub drinks: i64,
+}
+
+impl From<service::queries::water_record::DailyWaterIntake> for DailyWaterIntake {
+    fn from(day: service::queries::water_record::DailyWaterIntake) -> Self {
+        Self {
+            date: day.day,
+            total_ml: day.total_ml,
+            drinks: day.drinks,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct Walk {
+    pub id: i32,
+    pub pet_id: i32,
+    pub distance_m: Option<i32>,
+    pub duration_minutes: Option<i32>,
+    pub walked_at: DateTimeWithTimeZone,
+    pub track: Option<WalkTrack>,
+}
+
+impl Walk {
+    pub fn new(walk: service::queries::walk::Walk, track: Option<walk_tracks::Model>) -> Self {
+        Self {
+            id: walk.id,
+            pet_id: walk.pet_id,
+            distance_m: walk.distance_m,
+            duration_minutes: walk.duration_minutes,
+            walked_at: walk.created_at,
+            track: track.map(WalkTrack::from),
+        }
+    }
+}
+
+/// Route of a walk.
+#[derive(SimpleObject, Debug)]
+pub struct WalkTrack {
+    /// Google encoded polyline with five decimal places.
+    pub polyline: String,
+    pub point_count: i32,
+    /// Along the route, by the haversine formula.
+    pub distance_m: i32,
+    pub started_at: Option<DateTimeWithTimeZone>,
+    pub ended_at: Option<DateTimeWithTimeZone>,
+}
+
+impl From<walk_tracks::Model> for WalkTrack {
+    fn from(entity: walk_tracks::Model) -> Self {
+        Self {
+            polyline: entity.polyline,
+            point_count: entity.point_count,
+            distance_m: entity.distance_m,
+            started_at: entity.started_at,
+            ended_at: entity.ended_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct LogWalkInput {
+    pub pet_id: i32,
+    #[graphql(validator(minimum = 0))]
+    pub distance_m: Option<i32>,
+    #[graphql(validator(minimum = 0, maximum = 1440))]
+    pub duration_minutes: Option<i32>,
+    /// Defaults to now.
+    pub walked_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(InputObject, Debug)]
+pub struct CoordinateInput {
+    pub lat: f64,
+    pub lon: f64,
+    pub at: Option<DateTimeWithTimeZone>,
+}
+
+/// Exactly one of `gpx` and `points`.
+#[derive(InputObject, Debug)]
+pub struct AttachWalkTrackInput {
+    pub walk_id: i32,
+    /// Contents of a GPX file. Its tracks are used, or its routes without tracks.
+    #[graphql(validator(max_length = 5000000))]
+    pub gpx: Option<String>,
+    pub points: Option<Vec<CoordinateInput>>,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct PetMember {
+    pub user_id: i32,
+    pub email: Option<String>,
+    pub role: PetRole,
+    pub joined_at: DateTimeWithTimeZone,
+}
+
+impl From<(pet_members::Model, Option<users::Model>)> for PetMember {
+    fn from((member, user): (pet_members::Model, Option<users::Model>)) -> Self {
+        Self {
+            user_id: member.user_id,
+            email: user.and_then(|user| user.email),
+            role: member.role.into(),
+            joined_at: member.created_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct PetInvitation {
+    pub id: i32,
+    pub pet_id: i32,
+    pub role: PetRole,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl From<pet_invitations::Model> for PetInvitation {
+    fn from(entity: pet_invitations::Model) -> Self {
+        Self {
+            id: entity.id,
+            pet_id: entity.pet_id,
+            role: entity.role.into(),
+            expires_at: entity.expires_at,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub struct CreatePetInvitationInput {
+    pub pet_id: i32,
+    pub role: PetRole,
+}
+
+#[derive(SimpleObject, Debug)]
+pub struct CreatedPetInvitationPayload {
+    /// Single use code to share with the invitee. It is not stored and can't be shown again.
+    pub code: String,
+    pub invitation: PetInvitation,
+}
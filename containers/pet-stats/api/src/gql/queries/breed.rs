@@ -0,0 +1,41 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{Breed, PetSpeciesType};
+use crate::gql::utils::{authorized_user_id, resolve_species};
+use async_graphql::{Context, Object, Result};
+use service::auth::api_key::ApiScope;
+use service::queries::breed::BreedQuery as ServiceBreedQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct BreedQuery;
+
+#[Object]
+impl BreedQuery {
+    /// Breed catalog with typical weights and life expectancy, of one species when either
+    /// `species` or `speciesId` is given.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn breeds(
+        &self,
+        ctx: &Context<'_>,
+        species: Option<PetSpeciesType>,
+        species_id: Option<i32>,
+    ) -> Result<Vec<Breed>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+
+        let species_id = match (species, species_id) {
+            (None, None) => None,
+            (kind, id) => Some(
+                resolve_species(conn, user_id, kind.map(Into::into), id)
+                    .await?
+                    .id,
+            ),
+        };
+        let breeds = ServiceBreedQuery::list(conn, species_id).await?;
+        Ok(breeds.into_iter().map(Breed::from).collect())
+    }
+}
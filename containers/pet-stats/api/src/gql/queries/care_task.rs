@@ -0,0 +1,84 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{CareAgendaItem, CareTask, CareTaskCompletion};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use chrono::Duration;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::care_task::CareTaskQuery as ServiceCareTaskQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::schedule::today_in;
+use tracing::instrument;
+
+/// Upper bound for `careAgenda(withinDays)`.
+const MAX_AGENDA_DAYS: i32 = 366;
+
+#[derive(Default)]
+pub struct CareTaskQuery;
+
+#[Object]
+impl CareTaskQuery {
+    /// Care tasks of one of the user's pets.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn care_tasks(&self, ctx: &Context<'_>, pet_id: i32) -> Result<Vec<CareTask>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let care_tasks = ServiceCareTaskQuery::by_pet(conn, pet.id).await?;
+        Ok(care_tasks.into_iter().map(CareTask::from).collect())
+    }
+
+    /// Latest completions of a care task, most recent occurrence first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn care_task_completions(
+        &self,
+        ctx: &Context<'_>,
+        care_task_id: i32,
+    ) -> Result<Vec<CareTaskCompletion>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let care_task =
+            ServiceCareTaskQuery::user_care_task(conn, user_id, PetRole::Viewer, care_task_id)
+                .await
+                .map_err(db_err_to_gql)?;
+
+        let completions = ServiceCareTaskQuery::completions(conn, care_task.id).await?;
+        Ok(completions
+            .into_iter()
+            .map(CareTaskCompletion::from)
+            .collect())
+    }
+
+    /// Overdue care tasks, the ones due today and those coming up within `withinDays`,
+    /// across all of the user's pets in the user's timezone, soonest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn care_agenda(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 7)] within_days: i32,
+    ) -> Result<Vec<CareAgendaItem>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+        let today = today_in(&user.timezone);
+
+        let within_days = within_days.clamp(0, MAX_AGENDA_DAYS);
+        let until = today + Duration::days(within_days as i64);
+        let agenda = ServiceCareTaskQuery::agenda(conn, user_id, today, until).await?;
+        Ok(agenda.into_iter().map(CareAgendaItem::from).collect())
+    }
+}
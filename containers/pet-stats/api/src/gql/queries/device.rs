@@ -0,0 +1,24 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::Device;
+use crate::gql::utils::verified_claims_from_ctx;
+use async_graphql::{Context, Object, Result};
+use service::queries::device::DeviceQuery as ServiceDeviceQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct DeviceQuery;
+
+#[Object]
+impl DeviceQuery {
+    /// Registered devices, newest first. Their secrets are never shown again.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<Device>> {
+        let db = ctx.data::<Database>()?;
+        let claims = verified_claims_from_ctx(ctx)?;
+
+        let devices = ServiceDeviceQuery::by_user(db.get_connection(), claims.sub).await?;
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+}
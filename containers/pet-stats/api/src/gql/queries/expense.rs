@@ -0,0 +1,65 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{Expense, ExpenseGrouping, ExpenseTotal};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use chrono::NaiveDate;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::expense::ExpenseQuery as ServiceExpenseQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct ExpenseQuery;
+
+#[Object]
+impl ExpenseQuery {
+    /// Expenses of one of the user's pets, latest first. `from` and `until` are inclusive.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn expenses(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        from: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Result<Vec<Expense>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let expenses = ServiceExpenseQuery::by_pet(conn, pet.id, from, until).await?;
+        Ok(expenses.into_iter().map(Expense::from).collect())
+    }
+
+    /// Spending between `from` and `until`, both inclusive, totalled per currency and each
+    /// of `groupBy`. Limited to one pet when `petId` is given.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn expense_report(
+        &self,
+        ctx: &Context<'_>,
+        from: NaiveDate,
+        until: NaiveDate,
+        pet_id: Option<i32>,
+        #[graphql(default_with = "vec![ExpenseGrouping::Month]")] group_by: Vec<ExpenseGrouping>,
+    ) -> Result<Vec<ExpenseTotal>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        if until < from {
+            return Err(gql_err("INVALID_PERIOD", "until is before from"));
+        }
+
+        let group_by: Vec<_> = group_by.into_iter().map(Into::into).collect();
+        let report =
+            ServiceExpenseQuery::report(conn, user_id, from, until, pet_id, &group_by).await?;
+        Ok(report.into_iter().map(ExpenseTotal::from).collect())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::FeedRecord;
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::feed_record::FeedRecordQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use tracing::instrument;
+
+/// Upper bound for `feedRecords(limit)`.
+const MAX_FEED_RECORDS: i32 = 200;
+
+#[derive(Default)]
+pub struct FeedQuery;
+
+#[Object]
+impl FeedQuery {
+    /// Latest feedings of one of the user's pets, newest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn feed_records(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<FeedRecord>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let limit = limit.clamp(1, MAX_FEED_RECORDS) as u64;
+        let records = FeedRecordQuery::recent_by_pet(conn, pet.id, limit).await?;
+        Ok(records.into_iter().map(FeedRecord::from).collect())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{Food, FoodPurchase, FoodStock};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use chrono::Duration;
+use service::auth::api_key::ApiScope;
+use service::queries::food::FoodQuery as ServiceFoodQuery;
+use service::queries::pantry::PantryQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::schedule::today_in;
+use tracing::instrument;
+
+/// Upper bound for `lowStockReminders(withinDays)`.
+const MAX_REMINDER_DAYS: i32 = 366;
+
+#[derive(Default)]
+pub struct FoodQuery;
+
+#[Object]
+impl FoodQuery {
+    /// Your foods by brand and name.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn foods(&self, ctx: &Context<'_>) -> Result<Vec<Food>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+
+        let foods = ServiceFoodQuery::by_user(conn, user_id).await?;
+        Ok(foods.into_iter().map(Food::from).collect())
+    }
+
+    /// Purchases of one of your foods, latest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn food_purchases(&self, ctx: &Context<'_>, food_id: i32) -> Result<Vec<FoodPurchase>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+        let food = ServiceFoodQuery::user_food(conn, user_id, food_id)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let purchases = ServiceFoodQuery::purchases(conn, food.id).await?;
+        Ok(purchases.into_iter().map(FoodPurchase::from).collect())
+    }
+
+    /// Estimated stock of every food you bought, with the date it runs out going by the
+    /// last two weeks of feedings.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn pantry(&self, ctx: &Context<'_>) -> Result<Vec<FoodStock>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+        let today = today_in(&user.timezone);
+
+        let stock = PantryQuery::stock(conn, user_id, &user.timezone, today).await?;
+        Ok(stock.into_iter().map(FoodStock::from).collect())
+    }
+
+    /// Foods running out within `withinDays` that need to be bought again, soonest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn low_stock_reminders(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 7)] within_days: i32,
+    ) -> Result<Vec<FoodStock>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::FeedRead)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+        let today = today_in(&user.timezone);
+
+        let within_days = within_days.clamp(0, MAX_REMINDER_DAYS);
+        let until = today + Duration::days(within_days as i64);
+        let mut low: Vec<_> = PantryQuery::stock(conn, user_id, &user.timezone, today)
+            .await?
+            .into_iter()
+            .filter(|stock| stock.runs_low_by(until))
+            .collect();
+        low.sort_by_key(|stock| stock.runs_out_on);
+        Ok(low.into_iter().map(FoodStock::from).collect())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{JournalEntry, SymptomCount};
+use crate::gql::utils::{authorized_user_id, day_bounds, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use chrono::NaiveDate;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::journal::normalize_symptoms;
+use service::queries::journal::JournalQuery as ServiceJournalQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct JournalQuery;
+
+#[Object]
+impl JournalQuery {
+    /// Journal of one of the user's pets, latest first. With `symptoms`, only entries noting
+    /// all of them. `from` and `until` are inclusive days in the user's timezone.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn journal_entries(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        #[graphql(default)] symptoms: Vec<String>,
+        from: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Result<Vec<JournalEntry>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+
+        let from = from
+            .map(|date| day_bounds(&user.timezone, date).map(|(start, _)| start))
+            .transpose()?;
+        let until = until
+            .map(|date| day_bounds(&user.timezone, date).map(|(_, end)| end))
+            .transpose()?;
+        let entries =
+            ServiceJournalQuery::by_pet(conn, pet.id, normalize_symptoms(symptoms), from, until)
+                .await?;
+        Ok(entries.into_iter().map(JournalEntry::from).collect())
+    }
+
+    /// How often each symptom was noted for the pet between `from` and `until`, both
+    /// inclusive days in the user's timezone. Most frequent first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn symptom_summary(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<SymptomCount>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        if until < from {
+            return Err(gql_err("INVALID_PERIOD", "until is before from"));
+        }
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+
+        let (from, _) = day_bounds(&user.timezone, from)?;
+        let (_, until) = day_bounds(&user.timezone, until)?;
+        let summary = ServiceJournalQuery::symptom_frequency(conn, pet.id, from, until).await?;
+        Ok(summary.into_iter().map(SymptomCount::from).collect())
+    }
+}
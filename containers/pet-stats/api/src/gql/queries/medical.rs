@@ -0,0 +1,73 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{Vaccination, VetVisit};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use chrono::{Duration, Local};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::vaccination::VaccinationQuery;
+use service::queries::vet_visit::VetVisitQuery;
+use tracing::instrument;
+
+/// Upper bound for `upcomingVaccinations(withinDays)`.
+const MAX_UPCOMING_DAYS: i32 = 366;
+
+#[derive(Default)]
+pub struct MedicalQuery;
+
+#[Object]
+impl MedicalQuery {
+    /// Vaccination history of one of the user's pets, latest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn vaccinations(&self, ctx: &Context<'_>, pet_id: i32) -> Result<Vec<Vaccination>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let vaccinations = VaccinationQuery::by_pet(conn, pet.id).await?;
+        Ok(vaccinations.into_iter().map(Vaccination::from).collect())
+    }
+
+    /// Vet visits of one of the user's pets, latest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn vet_visits(&self, ctx: &Context<'_>, pet_id: i32) -> Result<Vec<VetVisit>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let visits = VetVisitQuery::by_pet(conn, pet.id).await?;
+        Ok(visits.into_iter().map(VetVisit::from).collect())
+    }
+
+    /// Vaccinations falling due within `withinDays` across all of the user's pets, overdue
+    /// ones included, soonest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn upcoming_vaccinations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 30)] within_days: i32,
+    ) -> Result<Vec<Vaccination>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+
+        let within_days = within_days.clamp(0, MAX_UPCOMING_DAYS);
+        let until = Local::now().date_naive() + Duration::days(within_days as i64);
+        let vaccinations = VaccinationQuery::upcoming_for_user(conn, user_id, until).await?;
+        Ok(vaccinations.into_iter().map(Vaccination::from).collect())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{DueDose, Medication, PetDueDoses};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::medication::MedicationQuery as ServiceMedicationQuery;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::schedule::today_in;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct MedicationQuery;
+
+#[Object]
+impl MedicationQuery {
+    /// Medication schedules of one of the user's pets, latest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn medications(&self, ctx: &Context<'_>, pet_id: i32) -> Result<Vec<Medication>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let medications = ServiceMedicationQuery::by_pet(conn, pet.id).await?;
+        Ok(medications.into_iter().map(Medication::from).collect())
+    }
+
+    /// Doses due today, in the user's timezone, grouped by pet.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn due_doses_today(&self, ctx: &Context<'_>) -> Result<Vec<PetDueDoses>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+        let today = today_in(&user.timezone);
+
+        let doses = ServiceMedicationQuery::due_doses(conn, user_id, today).await?;
+
+        let mut per_pet: Vec<PetDueDoses> = Vec::new();
+        for dose in doses {
+            let pet_id = dose.medication.pet_id;
+            match per_pet.last_mut() {
+                Some(group) if group.pet_id == pet_id => group.doses.push(DueDose::from(dose)),
+                _ => per_pet.push(PetDueDoses {
+                    pet_id,
+                    date: today,
+                    doses: vec![DueDose::from(dose)],
+                }),
+            }
+        }
+        Ok(per_pet)
+    }
+}
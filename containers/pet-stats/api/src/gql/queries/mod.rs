@@ -1,9 +1,57 @@
 use async_graphql::MergedObject;
+use breed::BreedQuery;
+use care_task::CareTaskQuery;
+use device::DeviceQuery;
+use elimination::EliminationQuery;
+use expense::ExpenseQuery;
+use feed::FeedQuery;
+use food::FoodQuery;
+use journal::JournalQuery;
+use medical::MedicalQuery;
+use medication::MedicationQuery;
+use pet_member::PetMemberQuery;
+use search::SearchQuery;
+use species::SpeciesQuery;
 use user::UserQuery;
+use walk::WalkQuery;
+use water::WaterQuery;
 
 use pet::PetQuery;
 
+mod breed;
+mod care_task;
+mod device;
+mod elimination;
+mod expense;
+mod feed;
+mod food;
+mod journal;
+mod medical;
+mod medication;
 mod pet;
+mod pet_member;
+mod search;
+mod species;
 mod user;
+mod walk;
+mod water;
 #[derive(MergedObject, Default)]
-pub struct Query(UserQuery, PetQuery);
+pub struct Query(
+    UserQuery,
+    PetQuery,
+    FeedQuery,
+    MedicalQuery,
+    MedicationQuery,
+    CareTaskQuery,
+    BreedQuery,
+    SpeciesQuery,
+    FoodQuery,
+    ExpenseQuery,
+    JournalQuery,
+    EliminationQuery,
+    WaterQuery,
+    WalkQuery,
+    SearchQuery,
+    DeviceQuery,
+    PetMemberQuery,
+);
@@ -1,10 +1,18 @@
-use crate::gql::objects::Pet;
-use crate::gql::utils::verified_claims_from_ctx;
+use crate::gql::objects::{Pet, UpcomingBirthday};
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
 use crate::{db::Database, gql::guards::AuthGuard};
 use async_graphql::{Context, Object, Result};
+use chrono::Duration;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
 use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::schedule::today_in;
 use tracing::instrument;
 
+/// Upper bound for `upcomingBirthdays(withinDays)`.
+const MAX_UPCOMING_DAYS: i32 = 366;
+
 #[derive(Default)]
 pub struct PetQuery;
 
@@ -16,9 +24,9 @@ impl PetQuery {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        let claims = verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
 
-        let pets = ServicePetQuery::get_pets_by_user_id(conn, claims.sub).await?;
+        let pets = ServicePetQuery::get_pets_by_user_id(conn, user_id).await?;
 
         Ok(pets.into_iter().map(Pet::from).collect())
     }
@@ -29,9 +37,11 @@ impl PetQuery {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
 
-        let pet = ServicePetQuery::get_pet_by_id(conn, pet_id).await?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
 
         Ok(Pet::from(pet))
     }
@@ -42,10 +52,32 @@ impl PetQuery {
         let db = ctx.data::<Database>()?;
         let conn = db.get_connection();
 
-        let claims = verified_claims_from_ctx(ctx)?;
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
 
-        let pet_count = ServicePetQuery::count_pets_by_user_id(conn, claims.sub).await?;
+        let pet_count = ServicePetQuery::count_pets_by_user_id(conn, user_id).await?;
 
         Ok(pet_count)
     }
+
+    /// Birthdays of the user's pets within `withinDays`, in the user's timezone, soonest
+    /// first. Pets with only a known birth year are left out.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn upcoming_birthdays(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 30)] within_days: i32,
+    ) -> Result<Vec<UpcomingBirthday>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+        let today = today_in(&user.timezone);
+
+        let within_days = within_days.clamp(0, MAX_UPCOMING_DAYS);
+        let until = today + Duration::days(within_days as i64);
+        let birthdays = ServicePetQuery::upcoming_birthdays(conn, user_id, today, until).await?;
+        Ok(birthdays.into_iter().map(UpcomingBirthday::from).collect())
+    }
 }
@@ -0,0 +1,32 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::PetMember;
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::pet_member::PetMemberQuery as ServicePetMemberQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct PetMemberQuery;
+
+#[Object]
+impl PetMemberQuery {
+    /// Everyone sharing the pet, in the order they joined.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn pet_members(&self, ctx: &Context<'_>, pet_id: i32) -> Result<Vec<PetMember>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let members = ServicePetMemberQuery::by_pet(conn, pet.id).await?;
+        Ok(members.into_iter().map(PetMember::from).collect())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::SearchResult;
+use crate::gql::utils::authorized_user_id;
+use async_graphql::{Context, Object, Result};
+use service::auth::api_key::ApiScope;
+use service::queries::search::SearchQuery as ServiceSearchQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct SearchQuery;
+
+#[Object]
+impl SearchQuery {
+    /// Search the user's pets, journal entries and vet visits, best match first. Supports
+    /// `"quoted phrases"`, `or` and `-excluded` words.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(max_length = 200))] query: String,
+        #[graphql(default = 20, validator(minimum = 1, maximum = 100))] limit: u64,
+    ) -> Result<Vec<SearchResult>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        if query.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let results = ServiceSearchQuery::search(conn, user_id, &query, limit).await?;
+        Ok(results.into_iter().map(SearchResult::from).collect())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::Species;
+use crate::gql::utils::authorized_user_id;
+use async_graphql::{Context, Object, Result};
+use service::auth::api_key::ApiScope;
+use service::queries::species::SpeciesQuery as ServiceSpeciesQuery;
+use tracing::instrument;
+
+#[derive(Default)]
+pub struct SpeciesQuery;
+
+#[Object]
+impl SpeciesQuery {
+    /// Species a pet can have, the shared ones first and then your own.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn species(&self, ctx: &Context<'_>) -> Result<Vec<Species>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+
+        let species = ServiceSpeciesQuery::available(conn, user_id).await?;
+        Ok(species.into_iter().map(Species::from).collect())
+    }
+}
@@ -1,12 +1,23 @@
 use crate::gql::guards::AuthGuard;
-use crate::gql::objects::User;
-use crate::gql::utils::verified_claims_from_ctx;
+use crate::gql::objects::{AccessTokenIntrospection, ApiKey, SecurityEvent, User};
+use crate::gql::utils::{is_admin, verified_claims_from_ctx};
 use crate::db::Database;
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, Json, Object, Result};
+use chrono::DateTime;
+use config::auth_config::AuthConfig;
+use jwt::verify_jwt;
+use sea_orm::JsonValue;
 
+use service::auth::token_revocation::TokenRevocationList;
+use service::queries::api_key::ApiKeyQuery;
+use service::queries::security_event::SecurityEventQuery;
 use service::queries::user::UserQuery as ServiceUserQuery;
+use std::sync::Arc;
 use tracing::instrument;
 
+/// Upper bound for `mySecurityEvents(limit)`.
+const MAX_SECURITY_EVENTS: i32 = 100;
+
 #[derive(Default)]
 pub struct UserQuery;
 
@@ -22,7 +33,7 @@ impl UserQuery {
     }
 
     #[graphql(guard = "AuthGuard")]
-    async fn get(&self, ctx: &Context<'_>, d: String) -> String {
+    async fn get(&self, _ctx: &Context<'_>, d: String) -> String {
         d.to_string()
     }
 
@@ -38,4 +49,79 @@ impl UserQuery {
         let user = ServiceUserQuery::user_by_id(conn, id).await?;
         Ok(User::from(user))
     }
+
+    /// Everything stored about the signed-in user, as a JSON archive.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn export_my_data(&self, ctx: &Context<'_>) -> Result<Json<JsonValue>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let claims = verified_claims_from_ctx(ctx)?;
+
+        let archive = ServiceUserQuery::export_user_data(conn, claims.sub).await?;
+        Ok(Json(archive))
+    }
+
+    /// Recent sign-ins, sign-outs and other security relevant activity, newest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn my_security_events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<SecurityEvent>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let claims = verified_claims_from_ctx(ctx)?;
+
+        let limit = limit.clamp(1, MAX_SECURITY_EVENTS) as u64;
+        let events = SecurityEventQuery::recent_by_user(conn, claims.sub, limit).await?;
+        Ok(events.into_iter().map(SecurityEvent::from).collect())
+    }
+
+    /// Active personal API keys. The keys themselves are never shown again after creation.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn my_api_keys(&self, ctx: &Context<'_>) -> Result<Vec<ApiKey>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let claims = verified_claims_from_ctx(ctx)?;
+
+        let keys = ApiKeyQuery::active_by_user(conn, claims.sub).await?;
+        Ok(keys.into_iter().map(ApiKey::from).collect())
+    }
+
+    /// Whether an access token is still accepted: signed by us, not expired and not revoked.
+    /// Only the signed-in user's own tokens are reported, admins can introspect any token.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx, token))]
+    async fn introspect_access_token(
+        &self,
+        ctx: &Context<'_>,
+        token: String,
+    ) -> Result<AccessTokenIntrospection> {
+        let caller = verified_claims_from_ctx(ctx)?;
+        let auth_config = ctx.data::<AuthConfig>()?;
+        let revocations = ctx.data::<Arc<TokenRevocationList>>()?;
+
+        let Ok(claims) = verify_jwt(&token, auth_config.jwt_sign_secret.to_owned()) else {
+            return Ok(AccessTokenIntrospection::inactive());
+        };
+        if claims.sub != caller.sub && !is_admin(ctx, caller.sub) {
+            return Ok(AccessTokenIntrospection::inactive());
+        }
+        if revocations.is_revoked(&claims) {
+            return Ok(AccessTokenIntrospection::inactive());
+        }
+
+        Ok(AccessTokenIntrospection {
+            active: true,
+            user_id: Some(claims.sub),
+            issued_at: DateTime::from_timestamp(claims.iat.0, 0).map(|t| t.fixed_offset()),
+            expires_at: DateTime::from_timestamp(claims.exp.0, 0).map(|t| t.fixed_offset()),
+        })
+    }
 }
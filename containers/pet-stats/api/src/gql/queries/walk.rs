@@ -0,0 +1,45 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::Walk;
+use crate::gql::utils::{authorized_user_id, db_err_to_gql};
+use async_graphql::{Context, Object, Result};
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::walk::WalkQuery as ServiceWalkQuery;
+use tracing::instrument;
+
+/// Upper bound for `walks(limit)`.
+const MAX_WALKS: i32 = 200;
+
+#[derive(Default)]
+pub struct WalkQuery;
+
+#[Object]
+impl WalkQuery {
+    /// Latest walks of one of the user's pets, newest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn walks(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<Walk>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let limit = limit.clamp(1, MAX_WALKS) as u64;
+        let walks = ServiceWalkQuery::recent_by_pet(conn, pet.id, limit).await?;
+        Ok(walks
+            .into_iter()
+            .map(|(walk, track)| Walk::new(walk, track))
+            .collect())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::db::Database;
+use crate::gql::guards::AuthGuard;
+use crate::gql::objects::{DailyWaterIntake, WaterRecord};
+use crate::gql::utils::{authorized_user_id, day_bounds, db_err_to_gql, gql_err};
+use async_graphql::{Context, Object, Result};
+use chrono::NaiveDate;
+use entity::entities::sea_orm_active_enums::PetRole;
+use service::auth::api_key::ApiScope;
+use service::queries::pet::PetQuery as ServicePetQuery;
+use service::queries::user::UserQuery as ServiceUserQuery;
+use service::queries::water_record::WaterRecordQuery;
+use tracing::instrument;
+
+/// Upper bound for `waterRecords(limit)`.
+const MAX_WATER_RECORDS: i32 = 200;
+
+#[derive(Default)]
+pub struct WaterQuery;
+
+#[Object]
+impl WaterQuery {
+    /// Latest drinks of one of the user's pets, newest first.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn water_records(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<WaterRecord>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+
+        let limit = limit.clamp(1, MAX_WATER_RECORDS) as u64;
+        let records = WaterRecordQuery::recent_by_pet(conn, pet.id, limit).await?;
+        Ok(records.into_iter().map(WaterRecord::from).collect())
+    }
+
+    /// Water drunk per day between `from` and `until`, both inclusive days in the user's
+    /// timezone. Days without records are left out.
+    #[graphql(guard = "AuthGuard")]
+    #[instrument(skip(self, ctx))]
+    async fn water_intake_summary(
+        &self,
+        ctx: &Context<'_>,
+        pet_id: i32,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<DailyWaterIntake>> {
+        let db = ctx.data::<Database>()?;
+        let conn = db.get_connection();
+
+        let user_id = authorized_user_id(ctx, ApiScope::PetsRead)?;
+        if until < from {
+            return Err(gql_err("INVALID_PERIOD", "until is before from"));
+        }
+        let pet = ServicePetQuery::get_user_pet(conn, user_id, pet_id, PetRole::Viewer)
+            .await
+            .map_err(db_err_to_gql)?;
+        let user = ServiceUserQuery::user_by_id(conn, user_id).await?;
+
+        let (from, _) = day_bounds(&user.timezone, from)?;
+        let (_, until) = day_bounds(&user.timezone, until)?;
+        let days = WaterRecordQuery::daily(conn, pet.id, &user.timezone, from, until).await?;
+        Ok(days.into_iter().map(DailyWaterIntake::from).collect())
+    }
+}
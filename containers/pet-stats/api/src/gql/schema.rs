@@ -1,19 +1,40 @@
-use async_graphql::{EmptySubscription, Schema};
+use std::sync::Arc;
+
+use async_graphql::{dataloader::DataLoader, EmptySubscription, Schema};
 use config::base_config::Config;
 use sea_orm::DbErr;
+use service::auth::google::GoogleOAuth;
+use service::auth::rejected_tokens::RejectedAccessTokens;
+use service::auth::token_revocation::TokenRevocationList;
+use service::rate_limit::RateLimiter;
 use tracing::{error, info, instrument};
 
-use crate::{db::Database, error::ApiError, gql::middleware::AuthExtension};
+use crate::{
+    db::Database,
+    error::ApiError,
+    gql::{loaders::SpeciesLoader, middleware::RateLimitExtension},
+};
 
 use super::{mutations::Mutation, queries::Query};
 
 pub type AppSchema = Schema<Query, Mutation, EmptySubscription>;
 
-#[instrument]
-pub async fn create_schema() -> Result<AppSchema, ApiError> {
+#[instrument(skip(revocations, rejections, rate_limiter))]
+pub async fn create_schema(
+    revocations: Arc<TokenRevocationList>,
+    rejections: Arc<RejectedAccessTokens>,
+    rate_limiter: RateLimiter,
+) -> Result<AppSchema, ApiError> {
     info!("Starting schema creation process");
 
     let oauth_config = config::auth_config::AuthConfig::new()?;
+    let account_config = config::account_config::AccountConfig::new()?;
+
+    // Shared across requests so the provider key set is cached between sign-ins.
+    let google_oauth = GoogleOAuth::new(
+        oauth_config.google_oauth_client_id.to_owned(),
+        oauth_config.google_oauth_public_key_url.to_owned(),
+    )?;
 
     info!("Initializing database connection");
     let db = Database::new().await.map_err(|error| {
@@ -24,12 +45,22 @@ pub async fn create_schema() -> Result<AppSchema, ApiError> {
         DbErr::Custom(error.to_string())
     })?;
 
+    // Not cached, a custom species renamed by one request shows up in the next one.
+    let species_loader = DataLoader::new(SpeciesLoader::new(Database::new().await?), tokio::spawn);
+
     info!("Starting Database migration");
 
     info!("Successfully completed database migraEmptyMutationtion");
     let schema = Schema::build(Query::default(), Mutation::default(), EmptySubscription)
         .data(db)
         .data(oauth_config)
+        .data(account_config)
+        .data(google_oauth)
+        .data(rate_limiter)
+        .data(species_loader)
+        .data(revocations)
+        .data(rejections)
+        .extension(RateLimitExtension)
         .finish();
 
     info!("Schema creation completed successfully");
@@ -1,13 +1,27 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_graphql::{Context, Error, ErrorExtensions};
-use config::auth_config::AuthConfig;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use config::{account_config::AccountConfig, auth_config::AuthConfig};
+use entity::entities::{sea_orm_active_enums::SecurityEventType, species, users};
 use jwt::{verify_jwt, Claims, JwtAuthError};
-use sea_orm::DbErr;
+use sea_orm::{DbConn, DbErr};
+use service::auth::{
+    api_key::ApiScope, error::AuthError, rejected_tokens::RejectedAccessTokens,
+    token_revocation::TokenRevocationList,
+};
+use service::mutations::security_event::{EventOrigin, SecurityEventMutation};
+use service::queries::species::SpeciesQuery;
+use service::schedule::day_bounds_in;
+use service::species::PetSpeciesType;
 use tracing::{error, info};
 
-use crate::context_data::AccessToken;
+use crate::context_data::{AccessToken, ApiKeyPrincipal, ClientIp, DeviceId, UserAgent};
+use crate::db::Database;
 
 #[inline]
-fn gql_err(code: &'static str, msg: impl Into<String>) -> Error {
+pub(crate) fn gql_err(code: &'static str, msg: impl Into<String>) -> Error {
     Error::new(msg).extend_with(|_, e| e.set("code", code))
 }
 
@@ -20,21 +34,227 @@ pub fn db_err_to_gql(err: DbErr) -> Error {
     }
 }
 
+pub fn auth_err_to_gql(err: AuthError) -> Error {
+    match err {
+        AuthError::StaleAuthentication => {
+            gql_err("REAUTHENTICATION_REQUIRED", "Sign in again to continue")
+        }
+        AuthError::InvalidToken => gql_err("INVALID_TOKEN", "Invalid token"),
+        AuthError::TokenExpired => gql_err("TOKEN_EXPIRED", "Expired token"),
+        AuthError::InvalidCredentials => {
+            gql_err("INVALID_CREDENTIALS", "Invalid email or password")
+        }
+        AuthError::InvalidTwoFactorCode => {
+            gql_err("INVALID_TWO_FACTOR_CODE", "Invalid two-factor code")
+        }
+        AuthError::TwoFactorNotEnabled => gql_err(
+            "TWO_FACTOR_NOT_ENABLED",
+            "Two-factor authentication is not enabled",
+        ),
+        AuthError::TwoFactorAlreadyEnabled => gql_err(
+            "TWO_FACTOR_ALREADY_ENABLED",
+            "Two-factor authentication is already enabled",
+        ),
+        AuthError::NotLocalAccount => gql_err(
+            "NOT_LOCAL_ACCOUNT",
+            "Only available for email and password accounts",
+        ),
+        AuthError::AccountDisabled => gql_err("ACCOUNT_DISABLED", "Account is disabled"),
+        other => gql_err("OTHER_ERROR", other.to_string()),
+    }
+}
+
+/// `day_bounds_in` for a date the client picked, which may be at the end of the calendar.
+pub fn day_bounds(
+    timezone: &str,
+    date: NaiveDate,
+) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), Error> {
+    day_bounds_in(timezone, date)
+        .ok_or_else(|| gql_err("INVALID_DATE", format!("{} is out of range", date)))
+}
+
+/// Too many attempts. `retryAfter` tells the client how many seconds to wait.
+pub fn rate_limited_err(retry_after: Duration) -> Error {
+    let secs = retry_after.as_secs();
+    Error::new(format!("Too many attempts, retry after {} seconds", secs)).extend_with(|_, e| {
+        e.set("code", "RATE_LIMITED");
+        e.set("retryAfter", secs);
+    })
+}
+
 pub fn verified_claims_from_ctx(ctx: &Context<'_>) -> Result<Claims, Error> {
+    if ctx.data_opt::<ApiKeyPrincipal>().is_some() {
+        return Err(gql_err(
+            "API_KEY_NOT_ALLOWED",
+            "Sign in to use this operation, API keys are not accepted",
+        ));
+    }
     let auth_config = ctx.data::<AuthConfig>()?;
     let token = ctx.data::<AccessToken>()?;
 
+    // Expiry is the normal end of a session, only forged and revoked tokens are audited.
     let claims = verify_jwt(token.0.as_str(), auth_config.jwt_sign_secret.to_owned()).map_err(
         |e| match e {
             JwtAuthError::Expired => {
                 error!("Access Token Expired");
                 gql_err("ACCESS_TOKEN_EXPIRED", "Access Token Expired")
             }
-            other => other.into(),
+            other => {
+                report_rejected_access_token(ctx, None);
+                other.into()
+            }
         },
     )?;
 
+    if ctx
+        .data_opt::<Arc<TokenRevocationList>>()
+        .is_some_and(|revocations| revocations.is_revoked(&claims))
+    {
+        report_rejected_access_token(ctx, Some(claims.sub));
+        return Err(gql_err("ACCESS_TOKEN_REVOKED", "Access Token Revoked"));
+    }
+
     info!("Succeed validation.");
 
     Ok(claims)
 }
+
+/// Revoke the access token behind `claims` before its `exp`.
+pub async fn revoke_access_token(ctx: &Context<'_>, claims: &Claims) -> Result<(), Error> {
+    let revocations = ctx.data::<Arc<TokenRevocationList>>()?;
+    let db = ctx.data::<Database>()?;
+    revocations
+        .revoke(db.get_connection(), claims)
+        .await
+        .map_err(db_err_to_gql)
+}
+
+/// Reject the user's access tokens issued before the cutoff stored with `user`, on this
+/// instance right away, the others pick it up on their next sync.
+pub fn apply_access_token_cutoff(ctx: &Context<'_>, user: &users::Model) -> Result<(), Error> {
+    let revocations = ctx.data::<Arc<TokenRevocationList>>()?;
+    if let Some(at) = user.tokens_revoked_before {
+        revocations.revoke_issued_before(user.id, at);
+    }
+    Ok(())
+}
+
+/// Whether the user is listed in `ADMIN_USER_IDS`.
+pub fn is_admin(ctx: &Context<'_>, user_id: i32) -> bool {
+    ctx.data_opt::<AccountConfig>()
+        .is_some_and(|config| config.admin_user_ids.contains(&user_id))
+}
+
+/// Claims of the signed-in user, who has to be an admin.
+pub fn admin_claims_from_ctx(ctx: &Context<'_>) -> Result<Claims, Error> {
+    let claims = verified_claims_from_ctx(ctx)?;
+    if !is_admin(ctx, claims.sub) {
+        return Err(gql_err("FORBIDDEN", "Only admins can use this operation"));
+    }
+    Ok(claims)
+}
+
+/// User the request acts for, from either a JWT or a personal API key.
+///
+/// API keys must have been granted `scope`. JWTs carry the full account and need no scope.
+pub fn authorized_user_id(ctx: &Context<'_>, scope: ApiScope) -> Result<i32, Error> {
+    match ctx.data_opt::<ApiKeyPrincipal>() {
+        Some(ApiKeyPrincipal(api_key)) if api_key.allows(scope) => Ok(api_key.user_id),
+        Some(_) => Err(gql_err(
+            "INSUFFICIENT_SCOPE",
+            format!("API key is missing the {} scope", scope),
+        )),
+        None => Ok(verified_claims_from_ctx(ctx)?.sub),
+    }
+}
+
+fn event_origin(ctx: &Context<'_>) -> EventOrigin {
+    EventOrigin {
+        ip_address: ctx.data_opt::<ClientIp>().map(|ip| ip.0.to_owned()),
+        user_agent: ctx.data_opt::<UserAgent>().map(|ua| ua.0.to_owned()),
+        device_id: ctx.data_opt::<DeviceId>().map(|device| device.0.to_owned()),
+    }
+}
+
+/// Queue an `AccessTokenRejected` event, written by the rejected token flush task.
+fn report_rejected_access_token(ctx: &Context<'_>, user_id: Option<i32>) {
+    if let Some(rejections) = ctx.data_opt::<Arc<RejectedAccessTokens>>() {
+        rejections.report(user_id, event_origin(ctx));
+    }
+}
+
+/// Write a security event for the current request to the audit log.
+///
+/// Failing to record is logged and otherwise ignored, it must not fail the operation itself.
+pub async fn record_security_event(
+    ctx: &Context<'_>,
+    user_id: Option<i32>,
+    event_type: SecurityEventType,
+) {
+    let origin = event_origin(ctx);
+
+    let Ok(db) = ctx.data::<Database>() else {
+        error!("No database to record {:?}", event_type);
+        return;
+    };
+    if let Err(e) =
+        SecurityEventMutation::record(db.get_connection(), user_id, event_type, &origin).await
+    {
+        error!("Failed to record security event: {:?}", e);
+    }
+}
+
+/// Record `event_type` when `result` is an error, then hand `result` back.
+pub async fn record_on_failure<T, E>(
+    ctx: &Context<'_>,
+    result: Result<T, E>,
+    user_id: Option<i32>,
+    event_type: SecurityEventType,
+) -> Result<T, E> {
+    record_on_failure_if(ctx, result, user_id, event_type, |_| true).await
+}
+
+/// Like `record_on_failure`, for operations where only some errors are security relevant.
+pub async fn record_on_failure_if<T, E>(
+    ctx: &Context<'_>,
+    result: Result<T, E>,
+    user_id: Option<i32>,
+    event_type: SecurityEventType,
+    relevant: impl FnOnce(&E) -> bool,
+) -> Result<T, E> {
+    if result.as_ref().is_err_and(relevant) {
+        record_security_event(ctx, user_id, event_type).await;
+    }
+    result
+}
+
+/// Species picked by id, which must be shared or the user's own, or else by built-in kind.
+/// A `kind` given along with the id has to match it.
+pub async fn resolve_species(
+    conn: &DbConn,
+    user_id: i32,
+    kind: Option<PetSpeciesType>,
+    species_id: Option<i32>,
+) -> Result<species::Model, Error> {
+    if let Some(species_id) = species_id {
+        let species = SpeciesQuery::usable_by(conn, user_id, species_id)
+            .await
+            .map_err(db_err_to_gql)?;
+        if let Some(kind) = kind.filter(|kind| *kind != PetSpeciesType::of(&species)) {
+            return Err(gql_err(
+                "SPECIES_MISMATCH",
+                format!("{} is not a {:?}", species.name, kind),
+            ));
+        }
+        return Ok(species);
+    }
+    match kind.as_ref().and_then(PetSpeciesType::code) {
+        Some(code) => SpeciesQuery::by_code(conn, code)
+            .await
+            .map_err(db_err_to_gql),
+        None => Err(gql_err(
+            "SPECIES_REQUIRED",
+            "speciesId is required unless species is one of the built-in species",
+        )),
+    }
+}
@@ -0,0 +1,25 @@
+use async_graphql::{CustomValidator, InputValueError};
+use chrono::NaiveDate;
+
+/// Dates a pet's life can touch. Anything outside is a typo or a probe, and dates near the
+/// ends of `NaiveDate` overflow the day and schedule arithmetic.
+pub(crate) struct PlausibleDate;
+
+impl PlausibleDate {
+    pub(crate) const MIN: NaiveDate = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+    pub(crate) const MAX: NaiveDate = NaiveDate::from_ymd_opt(2199, 12, 31).unwrap();
+}
+
+impl CustomValidator<NaiveDate> for PlausibleDate {
+    fn check(&self, value: &NaiveDate) -> Result<(), InputValueError<NaiveDate>> {
+        if (Self::MIN..=Self::MAX).contains(value) {
+            Ok(())
+        } else {
+            Err(InputValueError::custom(format!(
+                "date must be between {} and {}",
+                Self::MIN,
+                Self::MAX
+            )))
+        }
+    }
+}
@@ -1,11 +1,24 @@
+#![recursion_limit = "256"]
+
+use std::sync::Arc;
+use std::time::Duration;
+
 use actix_web::middleware::from_fn;
 use actix_web::{web, App, HttpServer};
+use config::account_config::AccountConfig;
 use config::auth_config::AuthConfig;
 use config::base_config::Config;
+use config::rate_limit_config::RateLimitConfig;
 use config::secret_config::SecretConfig;
+use config::token_cleanup_config::TokenCleanupConfig;
+use db::Database;
 use error::ApiError;
 use gql::schema::{create_schema, AppSchema};
-use middleware::{access_token_validator, logging_transaction};
+use middleware::{access_token_validator, client_ip_resolver, logging_transaction};
+use service::auth::rejected_tokens::RejectedAccessTokens;
+use service::auth::token_revocation::TokenRevocationList;
+use service::rate_limit::{BucketPolicy, RateLimiter};
+use tasks::token_cleanup::TokenCleanupMetrics;
 use tokio::select;
 use tokio::signal;
 use tokio::signal::unix::{signal, SignalKind};
@@ -18,12 +31,13 @@ mod error;
 mod gql;
 mod middleware;
 mod routes;
+mod tasks;
 /// Actix Web web server main function.
 /// Launch the GraphQL server.
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```ignore
 /// fn main() -> Result<()> {
 ///
 /// match api::main() {
@@ -40,16 +54,62 @@ mod routes;
 #[actix_web::main]
 pub async fn main() -> Result<(), ApiError> {
     env_logger::init();
-    let schema: AppSchema = create_schema().await?;
+    let revocations = Arc::new(TokenRevocationList::new());
+    let rejections = Arc::new(RejectedAccessTokens::new());
+    let rate_limit_config = RateLimitConfig::new()?;
+    let rate_limiter = RateLimiter::in_memory(
+        BucketPolicy {
+            burst: rate_limit_config.rate_limit_ip_burst,
+            per_minute: rate_limit_config.rate_limit_ip_per_minute,
+        },
+        BucketPolicy {
+            burst: rate_limit_config.rate_limit_subject_burst,
+            per_minute: rate_limit_config.rate_limit_subject_per_minute,
+        },
+    );
+    let schema: AppSchema = create_schema(
+        revocations.clone(),
+        rejections.clone(),
+        rate_limiter.clone(),
+    )
+    .await?;
     let secret_config = SecretConfig::new()?;
     let auth_config = AuthConfig::new()?;
+    let account_config = AccountConfig::new()?;
+    let token_cleanup_config = TokenCleanupConfig::new()?;
+    let database = web::Data::new(Database::new().await?);
+    let token_cleanup_metrics = web::Data::new(TokenCleanupMetrics::default());
+
+    tokio::spawn(tasks::account_purge::run(
+        Database::new().await?,
+        Duration::from_secs(account_config.account_purge_interval_secs),
+    ));
+    tokio::spawn(tasks::revocation_sync::run(
+        Database::new().await?,
+        revocations,
+    ));
+    tokio::spawn(tasks::token_cleanup::run(
+        Database::new().await?,
+        token_cleanup_config,
+        token_cleanup_metrics.clone().into_inner(),
+    ));
+    tokio::spawn(tasks::rate_limit_sweep::run(rate_limiter));
+    tokio::spawn(tasks::rejected_token_flush::run(
+        Database::new().await?,
+        rejections,
+    ));
+
     let server = HttpServer::new(move || {
         App::new()
             .configure(routes::configure_routes)
             .app_data(web::Data::new(auth_config.clone()))
             .app_data(web::Data::new(secret_config.clone()))
+            .app_data(web::Data::new(rate_limit_config.clone()))
             .app_data(web::Data::new(schema.clone()))
+            .app_data(database.clone())
+            .app_data(token_cleanup_metrics.clone())
             .wrap(from_fn(access_token_validator))
+            .wrap(from_fn(client_ip_resolver))
             .wrap(from_fn(logging_transaction))
     })
     .bind("0.0.0.0:8080")
@@ -1,12 +1,23 @@
+use std::net::SocketAddr;
+
 use actix_web::{
     body::MessageBody,
     dev::{ServiceRequest, ServiceResponse},
+    error::{ErrorInternalServerError, ErrorUnauthorized},
     middleware::Next,
-    Error, HttpMessage,
+    web, Error, HttpMessage,
+};
+use config::{auth_config::AuthConfig, rate_limit_config::RateLimitConfig};
+use service::auth::{
+    api_key::{ApiKey, ApiKeyService},
+    error::AuthError,
 };
-use tracing::{debug, instrument};
+use tracing::{debug, error, instrument, warn};
 
-use crate::context_data::AccessToken;
+use crate::{
+    context_data::{AccessToken, ApiKeyPrincipal, ClientIp},
+    db::Database,
+};
 
 fn extract_bearer_token(req: &ServiceRequest) -> Option<String> {
     req.headers()
@@ -18,19 +29,80 @@ fn extract_bearer_token(req: &ServiceRequest) -> Option<String> {
 
 /// Validate access token from request If exist Authorization header.
 /// If not exist Authorization header is mean not guarded request (eg. Sign In)
+///
+/// Personal API keys (`psk_…`) are accepted in place of a JWT. They are looked up here, so
+/// unknown or revoked keys are rejected before reaching GraphQL.
 #[instrument(skip(next), fields())]
 pub(crate) async fn access_token_validator(
     req: ServiceRequest,
     next: Next<impl MessageBody>,
 ) -> Result<ServiceResponse<impl MessageBody>, Error> {
     if let Some(header) = extract_bearer_token(&req) {
-        req.extensions_mut().insert(AccessToken(header.to_string()));
+        if ApiKey::is_api_key(&header) {
+            let principal = authenticate_api_key(&req, ApiKey(header)).await?;
+            req.extensions_mut().insert(principal);
+        } else {
+            req.extensions_mut().insert(AccessToken(header.to_string()));
+        }
     }
 
     let res = next.call(req).await?;
     Ok(res)
 }
 
+async fn authenticate_api_key(req: &ServiceRequest, key: ApiKey) -> Result<ApiKeyPrincipal, Error> {
+    let (Some(db), Some(auth_config)) = (
+        req.app_data::<web::Data<Database>>(),
+        req.app_data::<web::Data<AuthConfig>>(),
+    ) else {
+        error!("API key presented but database or auth config is not registered");
+        return Err(ErrorInternalServerError("API keys are not available"));
+    };
+
+    match ApiKeyService::authenticate(db.get_connection(), auth_config, &key).await {
+        Ok(api_key) => Ok(ApiKeyPrincipal(api_key)),
+        Err(AuthError::InvalidToken) => {
+            warn!("Rejected unknown or revoked API key");
+            Err(ErrorUnauthorized("Invalid API key"))
+        }
+        Err(e) => {
+            error!("API key lookup failed: {:?}", e);
+            Err(ErrorInternalServerError("API key lookup failed"))
+        }
+    }
+}
+
+/// Attach the client IP to the request.
+///
+/// Proxy headers are only honoured when `rate_limit_trust_proxy_headers` is set, otherwise
+/// any client could pick its own rate limit bucket.
+#[instrument(skip(next, req), fields())]
+pub(crate) async fn client_ip_resolver(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let trust_proxy_headers = req
+        .app_data::<web::Data<RateLimitConfig>>()
+        .is_some_and(|config| config.rate_limit_trust_proxy_headers);
+
+    let client_ip = if trust_proxy_headers {
+        req.connection_info()
+            .realip_remote_addr()
+            .map(|addr| match addr.parse::<SocketAddr>() {
+                Ok(socket) => socket.ip().to_string(),
+                Err(_) => addr.to_owned(),
+            })
+    } else {
+        req.peer_addr().map(|addr| addr.ip().to_string())
+    };
+
+    if let Some(ip) = client_ip {
+        req.extensions_mut().insert(ClientIp(ip));
+    }
+
+    next.call(req).await
+}
+
 /// Logging request and response.
 ///
 /// # Errors
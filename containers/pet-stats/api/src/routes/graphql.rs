@@ -1,6 +1,6 @@
 use actix_web::{
     get,
-    http::header::ContentType,
+    http::header::{self, ContentType},
     post,
     web::{self},
     HttpMessage, HttpRequest, HttpResponse, Result,
@@ -14,7 +14,7 @@ use config::app_config::{Flavor, APP_CONFIG};
 use tracing::instrument;
 
 use crate::{
-    context_data::AccessToken,
+    context_data::{AccessToken, ApiKeyPrincipal, ClientIp, DeviceId, UserAgent},
     gql::{mutations::Mutation, queries::Query},
 };
 
@@ -40,10 +40,29 @@ async fn graphql_handler(
     if let Some(tok) = req.extensions().get::<AccessToken>().cloned() {
         request = request.data(tok)
     };
+    if let Some(principal) = req.extensions().get::<ApiKeyPrincipal>().cloned() {
+        request = request.data(principal)
+    };
+    if let Some(ip) = req.extensions().get::<ClientIp>().cloned() {
+        request = request.data(ip)
+    };
+    if let Some(user_agent) = header_value(&req, header::USER_AGENT.as_str()) {
+        request = request.data(UserAgent(user_agent))
+    };
+    if let Some(device_id) = header_value(&req, "X-Device-Id") {
+        request = request.data(DeviceId(device_id))
+    };
 
     schema.execute(request).await.into()
 }
 
+fn header_value(req: &HttpRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+}
+
 #[instrument(skip(cfg))]
 pub(crate) fn graphql_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(graphql_handler);
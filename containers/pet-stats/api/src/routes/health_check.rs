@@ -1,10 +1,20 @@
 use actix_web::{get, web, HttpResponse};
 
+use crate::tasks::token_cleanup::TokenCleanupMetrics;
+
 #[get("/healthz")]
 async fn healthz() -> HttpResponse {
     HttpResponse::Ok().body("Ok")
 }
 
+/// Background task counters in the Prometheus text format.
+#[get("/metrics")]
+async fn metrics(token_cleanup: web::Data<TokenCleanupMetrics>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(token_cleanup.render())
+}
+
 pub(crate) fn health_check_routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(healthz);
+    cfg.service(healthz).service(metrics);
 }
@@ -0,0 +1,76 @@
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use config::auth_config::AuthConfig;
+use serde_json::json;
+use service::{
+    auth::device::{signing_secret, verify_signature},
+    ingest::EventBatch,
+    mutations::device::DeviceMutation,
+    queries::device::DeviceQuery,
+};
+use tracing::{error, instrument, warn};
+
+use crate::db::Database;
+
+/// Header carrying `sha256=<hex HMAC of the body>`.
+const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Feed and activity events from a registered device.
+///
+/// The body is signed with the secret handed out on registration. Events are idempotent on
+/// their id, so devices can resend a batch until they get a 200.
+#[instrument(skip(req, body, auth_config, database))]
+#[post("/ingest/devices/{uid}/events")]
+async fn ingest_device_events(
+    req: HttpRequest,
+    uid: web::Path<String>,
+    body: web::Bytes,
+    auth_config: web::Data<AuthConfig>,
+    database: web::Data<Database>,
+) -> HttpResponse {
+    let signature = req
+        .headers()
+        .get(SIGNATURE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+    let secret = signing_secret(auth_config.refresh_key_hashing_secret.as_bytes(), &uid);
+    if !verify_signature(&secret, &body, signature) {
+        warn!("Rejected ingest request with an invalid signature");
+        return HttpResponse::Unauthorized().body("Invalid signature");
+    }
+
+    let db = database.get_connection();
+    let device = match DeviceQuery::by_uid(db, &uid).await {
+        Ok(Some(device)) => device,
+        Ok(None) => {
+            warn!("Rejected ingest request for an unknown device");
+            return HttpResponse::Unauthorized().body("Unknown device");
+        }
+        Err(e) => {
+            error!("Device lookup failed: {:?}", e);
+            return HttpResponse::InternalServerError().body("Device lookup failed");
+        }
+    };
+
+    let batch = match serde_json::from_slice::<EventBatch>(&body) {
+        Ok(batch) => batch,
+        Err(e) => return HttpResponse::BadRequest().body(e.to_string()),
+    };
+    if let Err(e) = batch.validate() {
+        return HttpResponse::BadRequest().body(e.to_string());
+    }
+
+    match DeviceMutation::ingest(db, &device, &batch.events).await {
+        Ok(summary) => HttpResponse::Ok().json(json!({
+            "accepted": summary.accepted,
+            "duplicates": summary.duplicates,
+        })),
+        Err(e) => {
+            error!("Storing device events failed: {:?}", e);
+            HttpResponse::InternalServerError().body("Storing events failed")
+        }
+    }
+}
+
+pub(crate) fn ingest_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(ingest_device_events);
+}
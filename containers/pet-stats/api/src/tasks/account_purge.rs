@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use service::mutations::user::UserMutation;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, error, info, instrument};
+
+use crate::db::Database;
+
+/// Hard delete accounts whose deletion grace period has ended, every `every`.
+#[instrument(skip(db))]
+pub(crate) async fn run(db: Database, every: Duration) {
+    info!("Account purge task started.");
+    let mut ticker = interval(every);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        match UserMutation::purge_scheduled_accounts(db.get_connection()).await {
+            Ok(0) => debug!("No accounts due for deletion."),
+            Ok(n) => info!("Purged {} accounts past their deletion grace period.", n),
+            Err(e) => error!("Account purge failed: {:?}", e),
+        }
+    }
+}
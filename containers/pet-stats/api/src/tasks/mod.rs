@@ -0,0 +1,5 @@
+pub(crate) mod account_purge;
+pub(crate) mod rate_limit_sweep;
+pub(crate) mod rejected_token_flush;
+pub(crate) mod revocation_sync;
+pub(crate) mod token_cleanup;
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use service::rate_limit::RateLimiter;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, error, info, instrument};
+
+/// How often idle rate limit buckets are dropped. A bucket left alone this long by a
+/// client that stopped retrying has usually refilled.
+const SWEEP_EVERY: Duration = Duration::from_secs(60);
+
+/// Keep the in-memory rate limit buckets bounded by dropping the ones that have refilled.
+#[instrument(skip(limiter))]
+pub(crate) async fn run(limiter: RateLimiter) {
+    info!("Rate limit bucket sweep started.");
+    let mut ticker = interval(SWEEP_EVERY);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        match limiter.sweep().await {
+            Ok(dropped) => debug!("Dropped {} idle rate limit buckets", dropped),
+            Err(e) => error!("Rate limit bucket sweep failed: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::{sync::Arc, time::Duration};
+
+use service::auth::rejected_tokens::RejectedAccessTokens;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, error, info, instrument};
+
+use crate::db::Database;
+
+/// How long a rejected access token may wait before it shows up in the audit log.
+const FLUSH_EVERY: Duration = Duration::from_secs(10);
+
+/// Write access tokens rejected by the resolvers to the audit log.
+#[instrument(skip(db, rejections))]
+pub(crate) async fn run(db: Database, rejections: Arc<RejectedAccessTokens>) {
+    info!("Rejected access token flush started.");
+    let mut ticker = interval(FLUSH_EVERY);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        match rejections.flush(db.get_connection()).await {
+            Ok(0) => {}
+            Ok(written) => debug!("Recorded {} rejected access tokens", written),
+            Err(e) => error!("Failed to record rejected access tokens: {:?}", e),
+        }
+    }
+}
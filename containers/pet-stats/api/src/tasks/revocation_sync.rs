@@ -0,0 +1,26 @@
+use std::{sync::Arc, time::Duration};
+
+use service::auth::token_revocation::TokenRevocationList;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{error, info, instrument};
+
+use crate::db::Database;
+
+/// How long an access token revoked on another instance may still be accepted here.
+const SYNC_EVERY: Duration = Duration::from_secs(15);
+
+/// Keep the in-memory access token revocation list in step with the database.
+/// The first run, right at startup, loads every revocation that is still relevant.
+#[instrument(skip(db, revocations))]
+pub(crate) async fn run(db: Database, revocations: Arc<TokenRevocationList>) {
+    info!("Access token revocation sync started.");
+    let mut ticker = interval(SYNC_EVERY);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        if let Err(e) = revocations.sync(db.get_connection()).await {
+            error!("Access token revocation sync failed: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt::Write;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use chrono::Local;
+use config::token_cleanup_config::TokenCleanupConfig;
+use service::auth::sign_in::CHALLENGE_MAX_ATTEMPTS;
+use service::mutations::{
+    token_revocation::TokenRevocationMutation, two_factor::TwoFactorMutation, user::UserMutation,
+};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, error, info, instrument};
+
+use crate::db::Database;
+
+/// Totals since startup, served on `/metrics` in the Prometheus text format.
+#[derive(Debug, Default)]
+pub(crate) struct TokenCleanupMetrics {
+    runs: AtomicU64,
+    failures: AtomicU64,
+    refresh_tokens_removed: AtomicU64,
+    revocations_removed: AtomicU64,
+    challenges_removed: AtomicU64,
+}
+
+impl TokenCleanupMetrics {
+    pub(crate) fn render(&self) -> String {
+        let counters = [
+            (
+                "token_cleanup_runs_total",
+                "Token cleanup runs.",
+                &self.runs,
+            ),
+            (
+                "token_cleanup_failures_total",
+                "Token cleanup deletes that failed.",
+                &self.failures,
+            ),
+            (
+                "token_cleanup_refresh_tokens_removed_total",
+                "Expired or revoked refresh tokens deleted.",
+                &self.refresh_tokens_removed,
+            ),
+            (
+                "token_cleanup_revocations_removed_total",
+                "Access token revocations deleted after the token expired.",
+                &self.revocations_removed,
+            ),
+            (
+                "token_cleanup_challenges_removed_total",
+                "Expired or exhausted two-factor challenges deleted.",
+                &self.challenges_removed,
+            ),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        }
+        out
+    }
+}
+
+/// Delete refresh tokens that expired or were revoked more than the retention window ago,
+/// access token revocations that outlived the token, and two-factor challenges that can't be
+/// completed anymore.
+///
+/// Each run deletes in batches until a batch comes back short, so a backlog is worked off
+/// without holding one long delete.
+#[instrument(skip(db, metrics))]
+pub(crate) async fn run(
+    db: Database,
+    config: TokenCleanupConfig,
+    metrics: Arc<TokenCleanupMetrics>,
+) {
+    info!("Token cleanup task started.");
+    let mut ticker = interval(Duration::from_secs(config.token_cleanup_interval_secs));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        metrics.runs.fetch_add(1, Ordering::Relaxed);
+        let cutoff =
+            (Local::now() - chrono::Duration::days(config.token_retention_days)).fixed_offset();
+        let mut removed = 0;
+
+        loop {
+            match UserMutation::purge_stale_tokens(
+                db.get_connection(),
+                cutoff,
+                config.token_cleanup_batch_size,
+            )
+            .await
+            {
+                Ok(n) => {
+                    removed += n;
+                    if n < config.token_cleanup_batch_size {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error!(removed, "Token cleanup failed: {:?}", e);
+                    metrics.failures.fetch_add(1, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+
+        metrics
+            .refresh_tokens_removed
+            .fetch_add(removed, Ordering::Relaxed);
+        match removed {
+            0 => debug!("No stale refresh tokens."),
+            n => info!(removed = n, "Purged {} stale refresh tokens.", n),
+        }
+
+        match TokenRevocationMutation::purge_expired(db.get_connection()).await {
+            Ok(0) => debug!("No expired access token revocations."),
+            Ok(n) => {
+                metrics.revocations_removed.fetch_add(n, Ordering::Relaxed);
+                info!(
+                    removed = n,
+                    "Purged {} expired access token revocations.", n
+                )
+            }
+            Err(e) => {
+                metrics.failures.fetch_add(1, Ordering::Relaxed);
+                error!("Access token revocation cleanup failed: {:?}", e)
+            }
+        }
+
+        match TwoFactorMutation::purge_stale_challenges(db.get_connection(), CHALLENGE_MAX_ATTEMPTS)
+            .await
+        {
+            Ok(0) => debug!("No stale two-factor challenges."),
+            Ok(n) => {
+                metrics.challenges_removed.fetch_add(n, Ordering::Relaxed);
+                info!(removed = n, "Purged {} stale two-factor challenges.", n)
+            }
+            Err(e) => {
+                metrics.failures.fetch_add(1, Ordering::Relaxed);
+                error!("Two-factor challenge cleanup failed: {:?}", e)
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+use crate::{
+    base_config::Config,
+    error::ConfigError,
+    utils::{load_config, non_zero},
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AccountConfig {
+    /// Days between a deletion request and the hard delete.
+    #[serde(default = "default_account_deletion_grace_days")]
+    pub account_deletion_grace_days: i64,
+    /// Seconds between runs of the scheduled account purge.
+    #[serde(default = "default_account_purge_interval_secs")]
+    pub account_purge_interval_secs: u64,
+    /// Users allowed to disable and enable other accounts, comma separated ids.
+    #[serde(default)]
+    pub admin_user_ids: Vec<i32>,
+}
+
+fn default_account_deletion_grace_days() -> i64 {
+    30
+}
+
+fn default_account_purge_interval_secs() -> u64 {
+    60 * 60
+}
+
+impl AccountConfig {
+    /// The purge runs on a `tokio::time::interval`, which panics on a zero period.
+    fn validate(self) -> Result<Self, ConfigError> {
+        non_zero(
+            "ACCOUNT_PURGE_INTERVAL_SECS",
+            self.account_purge_interval_secs,
+        )?;
+        Ok(self)
+    }
+}
+
+impl Config for AccountConfig {
+    fn new() -> Result<Self, ConfigError> {
+        load_config::<AccountConfig>()?.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_purge_interval_is_rejected() {
+        let config = AccountConfig {
+            account_deletion_grace_days: default_account_deletion_grace_days(),
+            account_purge_interval_secs: 0,
+            admin_user_ids: vec![],
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Zero("ACCOUNT_PURGE_INTERVAL_SECS"))
+        ));
+
+        let config = AccountConfig {
+            account_deletion_grace_days: default_account_deletion_grace_days(),
+            account_purge_interval_secs: default_account_purge_interval_secs(),
+            admin_user_ids: vec![],
+        };
+        assert!(config.validate().is_ok());
+    }
+}
@@ -4,4 +4,8 @@ use thiserror::Error;
 pub enum ConfigError {
     #[error("Missing or invalid environment variables: {0}")]
     Envy(#[from] envy::Error),
+    #[error("{0} must be greater than zero")]
+    Zero(&'static str),
+    #[error("{0} must not be negative")]
+    Negative(&'static str),
 }
@@ -1,8 +1,11 @@
+pub mod account_config;
 pub mod app_config;
 pub mod auth_config;
 pub mod base_config;
 pub mod db_config;
 pub mod error;
 pub mod logging_config;
+pub mod rate_limit_config;
 pub mod secret_config;
+pub mod token_cleanup_config;
 pub(crate) mod utils;
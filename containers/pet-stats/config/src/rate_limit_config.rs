@@ -0,0 +1,108 @@
+use serde::Deserialize;
+
+use crate::{
+    base_config::Config,
+    error::ConfigError,
+    utils::{load_config, non_zero},
+};
+
+/// Token buckets for the unauthenticated sign-in mutations.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RateLimitConfig {
+    /// Sign-in attempts a single client IP may burst.
+    #[serde(default = "default_rate_limit_ip_burst")]
+    pub rate_limit_ip_burst: u32,
+    /// Attempts per minute given back to a client IP.
+    #[serde(default = "default_rate_limit_ip_per_minute")]
+    pub rate_limit_ip_per_minute: u32,
+    /// Sign-in attempts a single account may burst, from any IP.
+    #[serde(default = "default_rate_limit_subject_burst")]
+    pub rate_limit_subject_burst: u32,
+    /// Attempts per minute given back to an account.
+    #[serde(default = "default_rate_limit_subject_per_minute")]
+    pub rate_limit_subject_per_minute: u32,
+    /// Take the client IP from `Forwarded` / `X-Forwarded-For`.
+    /// Only enable behind a proxy that overwrites these headers.
+    #[serde(default)]
+    pub rate_limit_trust_proxy_headers: bool,
+}
+
+fn default_rate_limit_ip_burst() -> u32 {
+    20
+}
+
+fn default_rate_limit_ip_per_minute() -> u32 {
+    10
+}
+
+fn default_rate_limit_subject_burst() -> u32 {
+    5
+}
+
+fn default_rate_limit_subject_per_minute() -> u32 {
+    2
+}
+
+impl RateLimitConfig {
+    /// A zero burst limits every request forever, a zero refill never gives attempts back.
+    fn validate(self) -> Result<Self, ConfigError> {
+        non_zero("RATE_LIMIT_IP_BURST", self.rate_limit_ip_burst.into())?;
+        non_zero(
+            "RATE_LIMIT_IP_PER_MINUTE",
+            self.rate_limit_ip_per_minute.into(),
+        )?;
+        non_zero(
+            "RATE_LIMIT_SUBJECT_BURST",
+            self.rate_limit_subject_burst.into(),
+        )?;
+        non_zero(
+            "RATE_LIMIT_SUBJECT_PER_MINUTE",
+            self.rate_limit_subject_per_minute.into(),
+        )?;
+        Ok(self)
+    }
+}
+
+impl Config for RateLimitConfig {
+    fn new() -> Result<Self, ConfigError> {
+        load_config::<RateLimitConfig>()?.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> RateLimitConfig {
+        RateLimitConfig {
+            rate_limit_ip_burst: default_rate_limit_ip_burst(),
+            rate_limit_ip_per_minute: default_rate_limit_ip_per_minute(),
+            rate_limit_subject_burst: default_rate_limit_subject_burst(),
+            rate_limit_subject_per_minute: default_rate_limit_subject_per_minute(),
+            rate_limit_trust_proxy_headers: false,
+        }
+    }
+
+    #[test]
+    fn test_zero_burst_or_refill_is_rejected() {
+        assert!(defaults().validate().is_ok());
+
+        let config = RateLimitConfig {
+            rate_limit_ip_burst: 0,
+            ..defaults()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Zero("RATE_LIMIT_IP_BURST"))
+        ));
+
+        let config = RateLimitConfig {
+            rate_limit_subject_per_minute: 0,
+            ..defaults()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Zero("RATE_LIMIT_SUBJECT_PER_MINUTE"))
+        ));
+    }
+}
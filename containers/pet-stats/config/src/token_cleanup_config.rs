@@ -0,0 +1,96 @@
+use serde::Deserialize;
+
+use crate::{
+    base_config::Config,
+    error::ConfigError,
+    utils::{load_config, non_negative, non_zero},
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TokenCleanupConfig {
+    /// Days an expired or revoked refresh token is kept before it is deleted.
+    #[serde(default = "default_token_retention_days")]
+    pub token_retention_days: i64,
+    /// Rows deleted per statement, keeps each delete short.
+    #[serde(default = "default_token_cleanup_batch_size")]
+    pub token_cleanup_batch_size: u64,
+    /// Seconds between runs of the token cleanup.
+    #[serde(default = "default_token_cleanup_interval_secs")]
+    pub token_cleanup_interval_secs: u64,
+}
+
+fn default_token_retention_days() -> i64 {
+    7
+}
+
+fn default_token_cleanup_batch_size() -> u64 {
+    1000
+}
+
+fn default_token_cleanup_interval_secs() -> u64 {
+    60 * 60
+}
+
+impl TokenCleanupConfig {
+    /// A zero batch never comes back short, and a zero period panics in `tokio::time::interval`.
+    /// A negative retention would put the cutoff in the future and delete live tokens.
+    fn validate(self) -> Result<Self, ConfigError> {
+        non_negative("TOKEN_RETENTION_DAYS", self.token_retention_days)?;
+        non_zero("TOKEN_CLEANUP_BATCH_SIZE", self.token_cleanup_batch_size)?;
+        non_zero(
+            "TOKEN_CLEANUP_INTERVAL_SECS",
+            self.token_cleanup_interval_secs,
+        )?;
+        Ok(self)
+    }
+}
+
+impl Config for TokenCleanupConfig {
+    fn new() -> Result<Self, ConfigError> {
+        load_config::<TokenCleanupConfig>()?.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(batch_size: u64, interval_secs: u64) -> TokenCleanupConfig {
+        TokenCleanupConfig {
+            token_retention_days: default_token_retention_days(),
+            token_cleanup_batch_size: batch_size,
+            token_cleanup_interval_secs: interval_secs,
+        }
+    }
+
+    #[test]
+    fn test_zero_values_are_rejected() {
+        assert!(matches!(
+            config(0, 60).validate(),
+            Err(ConfigError::Zero("TOKEN_CLEANUP_BATCH_SIZE"))
+        ));
+        assert!(matches!(
+            config(1000, 0).validate(),
+            Err(ConfigError::Zero("TOKEN_CLEANUP_INTERVAL_SECS"))
+        ));
+        assert!(config(1000, 60).validate().is_ok());
+    }
+
+    #[test]
+    fn test_negative_retention_is_rejected() {
+        let negative = TokenCleanupConfig {
+            token_retention_days: -1,
+            ..config(1000, 60)
+        };
+        assert!(matches!(
+            negative.validate(),
+            Err(ConfigError::Negative("TOKEN_RETENTION_DAYS"))
+        ));
+
+        let immediate = TokenCleanupConfig {
+            token_retention_days: 0,
+            ..config(1000, 60)
+        };
+        assert!(immediate.validate().is_ok());
+    }
+}
@@ -11,7 +11,7 @@ use tracing::{error, info, instrument};
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// load_config::<AuthConfig>().unwrap();
 /// ```
 #[instrument]
@@ -30,6 +30,32 @@ where
         .map_err(ConfigError::Envy)
 }
 
+/// Reject a zero for a setting that is used as a period or a divisor.
+///
+/// # Errors
+///
+/// `ConfigError::Zero` naming the environment variable.
+pub(crate) fn non_zero(name: &'static str, value: u64) -> Result<(), ConfigError> {
+    if value == 0 {
+        error!("Invalid application configuration: {} is zero", name);
+        return Err(ConfigError::Zero(name));
+    }
+    Ok(())
+}
+
+/// Reject a negative count of days or items.
+///
+/// # Errors
+///
+/// `ConfigError::Negative` naming the environment variable.
+pub(crate) fn non_negative(name: &'static str, value: i64) -> Result<(), ConfigError> {
+    if value < 0 {
+        error!("Invalid application configuration: {} is negative", name);
+        return Err(ConfigError::Negative(name));
+    }
+    Ok(())
+}
+
 /// Load the configurations generic from T, Having prefix option.
 ///
 /// # Arguments
@@ -43,7 +69,7 @@ where
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// load_config_with_prefix::<SomeConfig>("env_".to_owned());
 /// ```
 #[instrument]
@@ -1,20 +1,34 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+use super::sea_orm_active_enums::FoodUnit;
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "feed_records")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub pet_id: i32,
-    pub amount: Option<i32>,
+    #[sea_orm(column_type = "Float", nullable)]
+    pub amount: Option<f32>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub food_id: Option<i32>,
+    pub unit: Option<FoodUnit>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::device_events::Entity")]
+    DeviceEvents,
+    #[sea_orm(
+        belongs_to = "super::foods::Entity",
+        from = "Column::FoodId",
+        to = "super::foods::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Foods,
     #[sea_orm(
         belongs_to = "super::pets::Entity",
         from = "Column::PetId",
@@ -25,6 +39,18 @@ pub enum Relation {
     Pets,
 }
 
+impl Related<super::device_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DeviceEvents.def()
+    }
+}
+
+impl Related<super::foods::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Foods.def()
+    }
+}
+
 impl Related<super::pets::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Pets.def()
@@ -2,11 +2,36 @@
 
 pub mod prelude;
 
+pub mod api_keys;
+pub mod breeds;
+pub mod care_task_completions;
+pub mod care_tasks;
+pub mod device_events;
+pub mod devices;
+pub mod elimination_records;
+pub mod expenses;
 pub mod feed_records;
+pub mod food_purchases;
+pub mod foods;
+pub mod journal_entries;
+pub mod medication_doses;
+pub mod medications;
 pub mod oauth_accounts;
+pub mod pet_invitations;
+pub mod pet_members;
 pub mod pets;
+pub mod recovery_codes;
+pub mod revoked_access_tokens;
 pub mod sea_orm_active_enums;
+pub mod security_events;
+pub mod species;
+pub mod two_factor_challenges;
 pub mod user_tokens;
+pub mod user_totp;
 pub mod users;
+pub mod vaccinations;
+pub mod vet_visits;
+pub mod walk_tracks;
+pub mod water_records;
 pub mod work_goals;
 pub mod work_records;
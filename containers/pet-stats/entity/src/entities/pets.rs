@@ -3,7 +3,6 @@
 use super::sea_orm_active_enums::DateDurationType;
 use super::sea_orm_active_enums::FeedDurationType;
 use super::sea_orm_active_enums::PetSexType;
-use super::sea_orm_active_enums::PetSpeciesType;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -14,7 +13,7 @@ pub struct Model {
     pub user_id: i32,
     pub name: String,
     pub sex: PetSexType,
-    pub species: PetSpeciesType,
+    pub species_id: i32,
     pub birthday: Date,
     pub birthday_precision: DateDurationType,
     pub feed_count: Option<i32>,
@@ -22,14 +21,47 @@ pub struct Model {
     #[sea_orm(column_type = "Float", nullable)]
     pub weight: Option<f32>,
     pub is_disabled: bool,
+    pub breed_id: Option<i32>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::breeds::Entity",
+        from = "Column::BreedId",
+        to = "super::breeds::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Breeds,
+    #[sea_orm(has_many = "super::care_tasks::Entity")]
+    CareTasks,
+    #[sea_orm(has_many = "super::devices::Entity")]
+    Devices,
+    #[sea_orm(has_many = "super::elimination_records::Entity")]
+    EliminationRecords,
+    #[sea_orm(has_many = "super::expenses::Entity")]
+    Expenses,
     #[sea_orm(has_many = "super::feed_records::Entity")]
     FeedRecords,
+    #[sea_orm(has_many = "super::journal_entries::Entity")]
+    JournalEntries,
+    #[sea_orm(has_many = "super::medications::Entity")]
+    Medications,
+    #[sea_orm(has_many = "super::pet_invitations::Entity")]
+    PetInvitations,
+    #[sea_orm(has_many = "super::pet_members::Entity")]
+    PetMembers,
+    #[sea_orm(
+        belongs_to = "super::species::Entity",
+        from = "Column::SpeciesId",
+        to = "super::species::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Species,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -38,24 +70,108 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Users,
+    #[sea_orm(has_many = "super::vaccinations::Entity")]
+    Vaccinations,
+    #[sea_orm(has_many = "super::vet_visits::Entity")]
+    VetVisits,
+    #[sea_orm(has_many = "super::water_records::Entity")]
+    WaterRecords,
     #[sea_orm(has_many = "super::work_goals::Entity")]
     WorkGoals,
     #[sea_orm(has_many = "super::work_records::Entity")]
     WorkRecords,
 }
 
+impl Related<super::breeds::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Breeds.def()
+    }
+}
+
+impl Related<super::care_tasks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CareTasks.def()
+    }
+}
+
+impl Related<super::devices::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Devices.def()
+    }
+}
+
+impl Related<super::elimination_records::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EliminationRecords.def()
+    }
+}
+
+impl Related<super::expenses::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Expenses.def()
+    }
+}
+
 impl Related<super::feed_records::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::FeedRecords.def()
     }
 }
 
+impl Related<super::journal_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::JournalEntries.def()
+    }
+}
+
+impl Related<super::medications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Medications.def()
+    }
+}
+
+impl Related<super::pet_invitations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetInvitations.def()
+    }
+}
+
+impl Related<super::pet_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetMembers.def()
+    }
+}
+
+impl Related<super::species::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Species.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
     }
 }
 
+impl Related<super::vaccinations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vaccinations.def()
+    }
+}
+
+impl Related<super::vet_visits::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VetVisits.def()
+    }
+}
+
+impl Related<super::water_records::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WaterRecords.def()
+    }
+}
+
 impl Related<super::work_goals::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::WorkGoals.def()
@@ -1,9 +1,34 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+pub use super::api_keys::Entity as ApiKeys;
+pub use super::breeds::Entity as Breeds;
+pub use super::care_task_completions::Entity as CareTaskCompletions;
+pub use super::care_tasks::Entity as CareTasks;
+pub use super::device_events::Entity as DeviceEvents;
+pub use super::devices::Entity as Devices;
+pub use super::elimination_records::Entity as EliminationRecords;
+pub use super::expenses::Entity as Expenses;
 pub use super::feed_records::Entity as FeedRecords;
+pub use super::food_purchases::Entity as FoodPurchases;
+pub use super::foods::Entity as Foods;
+pub use super::journal_entries::Entity as JournalEntries;
+pub use super::medication_doses::Entity as MedicationDoses;
+pub use super::medications::Entity as Medications;
 pub use super::oauth_accounts::Entity as OauthAccounts;
+pub use super::pet_invitations::Entity as PetInvitations;
+pub use super::pet_members::Entity as PetMembers;
 pub use super::pets::Entity as Pets;
+pub use super::recovery_codes::Entity as RecoveryCodes;
+pub use super::revoked_access_tokens::Entity as RevokedAccessTokens;
+pub use super::security_events::Entity as SecurityEvents;
+pub use super::species::Entity as Species;
+pub use super::two_factor_challenges::Entity as TwoFactorChallenges;
 pub use super::user_tokens::Entity as UserTokens;
+pub use super::user_totp::Entity as UserTotp;
 pub use super::users::Entity as Users;
+pub use super::vaccinations::Entity as Vaccinations;
+pub use super::vet_visits::Entity as VetVisits;
+pub use super::walk_tracks::Entity as WalkTracks;
+pub use super::water_records::Entity as WaterRecords;
 pub use super::work_goals::Entity as WorkGoals;
 pub use super::work_records::Entity as WorkRecords;
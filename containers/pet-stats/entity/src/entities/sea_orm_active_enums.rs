@@ -13,6 +13,38 @@ pub enum DateDurationType {
     Year,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "dose_status")]
+pub enum DoseStatus {
+    #[sea_orm(string_value = "Given")]
+    Given,
+    #[sea_orm(string_value = "Skipped")]
+    Skipped,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "elimination_type")]
+pub enum EliminationType {
+    #[sea_orm(string_value = "Urine")]
+    Urine,
+    #[sea_orm(string_value = "Stool")]
+    Stool,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "expense_category")]
+pub enum ExpenseCategory {
+    #[sea_orm(string_value = "Food")]
+    Food,
+    #[sea_orm(string_value = "Vet")]
+    Vet,
+    #[sea_orm(string_value = "Grooming")]
+    Grooming,
+    #[sea_orm(string_value = "Toys")]
+    Toys,
+    #[sea_orm(string_value = "Insurance")]
+    Insurance,
+    #[sea_orm(string_value = "Other")]
+    Other,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "feed_duration_type")]
 pub enum FeedDurationType {
     #[sea_orm(string_value = "Day")]
@@ -23,6 +55,14 @@ pub enum FeedDurationType {
     Month,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "food_unit")]
+pub enum FoodUnit {
+    #[sea_orm(string_value = "Gram")]
+    Gram,
+    #[sea_orm(string_value = "Cup")]
+    Cup,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "login_type")]
 pub enum LoginType {
     #[sea_orm(string_value = "Oauth")]
@@ -30,6 +70,30 @@ pub enum LoginType {
     #[sea_orm(string_value = "Local")]
     Local,
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "medication_frequency"
+)]
+pub enum MedicationFrequency {
+    #[sea_orm(string_value = "Daily")]
+    Daily,
+    #[sea_orm(string_value = "EveryOtherDay")]
+    EveryOtherDay,
+    #[sea_orm(string_value = "Weekly")]
+    Weekly,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "pet_role")]
+pub enum PetRole {
+    #[sea_orm(string_value = "Owner")]
+    Owner,
+    #[sea_orm(string_value = "Caretaker")]
+    Caretaker,
+    #[sea_orm(string_value = "Viewer")]
+    Viewer,
+}
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "pet_sex_type")]
 pub enum PetSexType {
@@ -41,22 +105,6 @@ pub enum PetSexType {
     Other,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
-#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "pet_species_type")]
-pub enum PetSpeciesType {
-    #[sea_orm(string_value = "Dog")]
-    Dog,
-    #[sea_orm(string_value = "Cat")]
-    Cat,
-    #[sea_orm(string_value = "Fish")]
-    Fish,
-    #[sea_orm(string_value = "Lizard")]
-    Lizard,
-    #[sea_orm(string_value = "Turtle")]
-    Turtle,
-    #[sea_orm(string_value = "Snake")]
-    Snake,
-}
-#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "provider_type")]
 pub enum ProviderType {
     #[sea_orm(string_value = "Google")]
@@ -66,3 +114,37 @@ pub enum ProviderType {
     #[sea_orm(string_value = "Apple")]
     Apple,
 }
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "security_event_type"
+)]
+pub enum SecurityEventType {
+    #[sea_orm(string_value = "SignIn")]
+    SignIn,
+    #[sea_orm(string_value = "SignInFailed")]
+    SignInFailed,
+    #[sea_orm(string_value = "SignOut")]
+    SignOut,
+    #[sea_orm(string_value = "TokenRotated")]
+    TokenRotated,
+    #[sea_orm(string_value = "TokenRotationFailed")]
+    TokenRotationFailed,
+    #[sea_orm(string_value = "TwoFactorEnabled")]
+    TwoFactorEnabled,
+    #[sea_orm(string_value = "TwoFactorDisabled")]
+    TwoFactorDisabled,
+    #[sea_orm(string_value = "TwoFactorFailed")]
+    TwoFactorFailed,
+    #[sea_orm(string_value = "ReauthenticationFailed")]
+    ReauthenticationFailed,
+    #[sea_orm(string_value = "AccountDeletionScheduled")]
+    AccountDeletionScheduled,
+    #[sea_orm(string_value = "AccessTokenRejected")]
+    AccessTokenRejected,
+    #[sea_orm(string_value = "ApiKeyCreated")]
+    ApiKeyCreated,
+    #[sea_orm(string_value = "ApiKeyRevoked")]
+    ApiKeyRevoked,
+}
@@ -13,16 +13,60 @@ pub struct Model {
     pub login_type: LoginType,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub deletion_scheduled_at: Option<DateTimeWithTimeZone>,
+    pub tokens_revoked_before: Option<DateTimeWithTimeZone>,
+    pub disabled_at: Option<DateTimeWithTimeZone>,
+    pub timezone: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_keys::Entity")]
+    ApiKeys,
+    #[sea_orm(has_many = "super::devices::Entity")]
+    Devices,
+    #[sea_orm(has_many = "super::foods::Entity")]
+    Foods,
     #[sea_orm(has_many = "super::oauth_accounts::Entity")]
     OauthAccounts,
+    #[sea_orm(has_many = "super::pet_invitations::Entity")]
+    PetInvitations,
+    #[sea_orm(has_many = "super::pet_members::Entity")]
+    PetMembers,
     #[sea_orm(has_many = "super::pets::Entity")]
     Pets,
+    #[sea_orm(has_many = "super::recovery_codes::Entity")]
+    RecoveryCodes,
+    #[sea_orm(has_many = "super::revoked_access_tokens::Entity")]
+    RevokedAccessTokens,
+    #[sea_orm(has_many = "super::security_events::Entity")]
+    SecurityEvents,
+    #[sea_orm(has_many = "super::species::Entity")]
+    Species,
+    #[sea_orm(has_many = "super::two_factor_challenges::Entity")]
+    TwoFactorChallenges,
     #[sea_orm(has_many = "super::user_tokens::Entity")]
     UserTokens,
+    #[sea_orm(has_one = "super::user_totp::Entity")]
+    UserTotp,
+}
+
+impl Related<super::api_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiKeys.def()
+    }
+}
+
+impl Related<super::devices::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Devices.def()
+    }
+}
+
+impl Related<super::foods::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Foods.def()
+    }
 }
 
 impl Related<super::oauth_accounts::Entity> for Entity {
@@ -31,16 +75,64 @@ impl Related<super::oauth_accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::pet_invitations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetInvitations.def()
+    }
+}
+
+impl Related<super::pet_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetMembers.def()
+    }
+}
+
 impl Related<super::pets::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Pets.def()
     }
 }
 
+impl Related<super::recovery_codes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecoveryCodes.def()
+    }
+}
+
+impl Related<super::revoked_access_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RevokedAccessTokens.def()
+    }
+}
+
+impl Related<super::security_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SecurityEvents.def()
+    }
+}
+
+impl Related<super::species::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Species.def()
+    }
+}
+
+impl Related<super::two_factor_challenges::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TwoFactorChallenges.def()
+    }
+}
+
 impl Related<super::user_tokens::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserTokens.def()
     }
 }
 
+impl Related<super::user_totp::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserTotp.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
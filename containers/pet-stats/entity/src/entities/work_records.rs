@@ -16,6 +16,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::device_events::Entity")]
+    DeviceEvents,
     #[sea_orm(
         belongs_to = "super::pets::Entity",
         from = "Column::PetId",
@@ -24,6 +26,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Pets,
+    #[sea_orm(has_one = "super::walk_tracks::Entity")]
+    WalkTracks,
+}
+
+impl Related<super::device_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DeviceEvents.def()
+    }
 }
 
 impl Related<super::pets::Entity> for Entity {
@@ -32,4 +42,10 @@ impl Related<super::pets::Entity> for Entity {
     }
 }
 
+impl Related<super::walk_tracks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WalkTracks.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -3,6 +3,7 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation}
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{error, info, instrument};
+use uuid::Uuid;
 
 pub const DEFAULT_EXP: TimeDelta = TimeDelta::minutes(30);
 
@@ -15,6 +16,10 @@ pub struct Claims {
     pub email: Option<String>,
     pub iat: UnixTimestamp,
     pub exp: UnixTimestamp,
+    /// Unique id of the token, the key of the revocation list.
+    /// Tokens issued before it existed carry an empty one and can't be revoked individually.
+    #[serde(default)]
+    pub jti: String,
 }
 
 impl From<DateTime<Utc>> for UnixTimestamp {
@@ -85,6 +90,7 @@ pub fn create_jwt(
         email: email.map(|e| e.to_owned()),
         exp: exp.clone(),
         iat,
+        jti: Uuid::new_v4().to_string(),
     };
     let header = Header {
         alg: jsonwebtoken::Algorithm::HS256,
@@ -108,7 +114,6 @@ mod tests {
     use chrono::Duration;
     use chrono::Utc;
     use jsonwebtoken::encode;
-    use jsonwebtoken::errors::ErrorKind;
     use serde_json::json;
     use std::convert::TryInto;
     use tracing_test::traced_test;
@@ -144,6 +149,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_every_jwt_has_its_own_jti() {
+        let first = create_jwt(1, None, SECRET.to_string(), TimeDelta::hours(1)).unwrap();
+        let second = create_jwt(1, None, SECRET.to_string(), TimeDelta::hours(1)).unwrap();
+
+        let first = verify_jwt(&first, SECRET.to_string()).unwrap();
+        let second = verify_jwt(&second, SECRET.to_string()).unwrap();
+        assert!(!first.jti.is_empty());
+        assert_ne!(first.jti, second.jti);
+    }
+
     #[test]
     fn test_verify_jwt_with_wrong_secret() {
         let wrong_secret = "wrong_key";
@@ -193,6 +209,7 @@ mod tests {
             email: email.map(String::from),
             iat: expired_iat,
             exp: expired_exp,
+            jti: Uuid::new_v4().to_string(),
         };
 
         // Encode the token using the claims with expired times
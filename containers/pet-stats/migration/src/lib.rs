@@ -11,6 +11,26 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(migrators::m20250121_000001_create_user_table::Migration),
             Box::new(migrators::m20250808_000001_create_pet_table::Migration),
+            Box::new(migrators::m20261019_000001_add_user_deletion_schedule::Migration),
+            Box::new(migrators::m20261019_000002_create_two_factor_tables::Migration),
+            Box::new(migrators::m20261019_000003_create_security_events_table::Migration),
+            Box::new(migrators::m20261019_000004_create_api_keys_table::Migration),
+            Box::new(migrators::m20261019_000005_add_user_tokens_cleanup_indexes::Migration),
+            Box::new(migrators::m20261019_000006_create_revoked_access_tokens_table::Migration),
+            Box::new(migrators::m20261019_000007_create_medical_records_tables::Migration),
+            Box::new(migrators::m20261019_000008_create_medication_tables::Migration),
+            Box::new(migrators::m20261019_000009_create_care_task_tables::Migration),
+            Box::new(migrators::m20261019_000010_create_breeds_table::Migration),
+            Box::new(migrators::m20261019_000011_create_species_table::Migration),
+            Box::new(migrators::m20261019_000012_create_foods_table::Migration),
+            Box::new(migrators::m20261019_000013_create_food_purchases_table::Migration),
+            Box::new(migrators::m20261019_000014_create_expenses_table::Migration),
+            Box::new(migrators::m20261019_000015_create_journal_entries_table::Migration),
+            Box::new(migrators::m20261019_000016_add_search_vectors::Migration),
+            Box::new(migrators::m20261019_000017_create_elimination_and_water_records_tables::Migration),
+            Box::new(migrators::m20261019_000018_create_walk_tracks_table::Migration),
+            Box::new(migrators::m20261019_000019_create_devices_tables::Migration),
+            Box::new(migrators::m20261019_000020_create_pet_members_tables::Migration),
         ]
     }
 }
@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000001_add_user_deletion_schedule"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Accounts are hard deleted once this passes; child rows follow through the
+        // ON DELETE CASCADE foreign keys of pets, records, tokens and oauth accounts.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::DeletionScheduledAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-users-deletion-scheduled-at")
+                    .table(Users::Table)
+                    .col(Users::DeletionScheduledAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    DeletionScheduledAt,
+}
@@ -0,0 +1,201 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{m20250121_000001_create_user_table::Users, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000002_create_two_factor_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // TOTP secret per user. Enrollment is pending until `confirmed_at` is set.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(UserTotp::Table)
+                    .col(
+                        ColumnDef::new(UserTotp::UserId)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserTotp::Secret).var_binary(64).not_null())
+                    .col(
+                        ColumnDef::new(UserTotp::ConfirmedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    // Last accepted time step, so a code can't be replayed.
+                    .col(ColumnDef::new(UserTotp::LastUsedStep).big_integer().null())
+                    .col(current_timestamp_col(UserTotp::CreatedAt))
+                    .col(current_timestamp_col(UserTotp::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_totp_user_id")
+                            .from(UserTotp::Table, UserTotp::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One-time recovery codes, stored as HMAC hashes like refresh tokens.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(RecoveryCodes::Table)
+                    .col(
+                        ColumnDef::new(RecoveryCodes::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY".to_owned()),
+                    )
+                    .col(ColumnDef::new(RecoveryCodes::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(RecoveryCodes::CodeHash)
+                            .var_binary(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecoveryCodes::UsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(current_timestamp_col(RecoveryCodes::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recovery_codes_user_id")
+                            .from(RecoveryCodes::Table, RecoveryCodes::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-recovery-codes-user-code")
+                    .table(RecoveryCodes::Table)
+                    .col(RecoveryCodes::UserId)
+                    .col(RecoveryCodes::CodeHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Pending second sign-in steps. The token handed to the client is stored hashed.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(TwoFactorChallenges::Table)
+                    .col(
+                        ColumnDef::new(TwoFactorChallenges::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(TwoFactorChallenges::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TwoFactorChallenges::ChallengeHash)
+                            .var_binary(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TwoFactorChallenges::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(Expr::value(0)),
+                    )
+                    .col(
+                        ColumnDef::new(TwoFactorChallenges::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(TwoFactorChallenges::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_two_factor_challenges_user_id")
+                            .from(TwoFactorChallenges::Table, TwoFactorChallenges::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-two-factor-challenge-hash")
+                    .table(TwoFactorChallenges::Table)
+                    .col(TwoFactorChallenges::ChallengeHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum UserTotp {
+    Table,
+    UserId,
+    Secret,
+    ConfirmedAt,
+    LastUsedStep,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum RecoveryCodes {
+    Table,
+    Id,
+    UserId,
+    CodeHash,
+    UsedAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum TwoFactorChallenges {
+    Table,
+    Id,
+    UserId,
+    ChallengeHash,
+    Attempts,
+    ExpiresAt,
+    CreatedAt,
+}
@@ -0,0 +1,134 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{m20250121_000001_create_user_table::Users, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000003_create_security_events_table"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "security_event_type"
+)]
+pub enum SecurityEventType {
+    #[sea_orm(string_value = "SignIn")]
+    SignIn,
+    #[sea_orm(string_value = "SignInFailed")]
+    SignInFailed,
+    #[sea_orm(string_value = "SignOut")]
+    SignOut,
+    #[sea_orm(string_value = "TokenRotated")]
+    TokenRotated,
+    #[sea_orm(string_value = "TokenRotationFailed")]
+    TokenRotationFailed,
+    #[sea_orm(string_value = "TwoFactorEnabled")]
+    TwoFactorEnabled,
+    #[sea_orm(string_value = "TwoFactorDisabled")]
+    TwoFactorDisabled,
+    #[sea_orm(string_value = "TwoFactorFailed")]
+    TwoFactorFailed,
+    #[sea_orm(string_value = "ReauthenticationFailed")]
+    ReauthenticationFailed,
+    #[sea_orm(string_value = "AccountDeletionScheduled")]
+    AccountDeletionScheduled,
+    #[sea_orm(string_value = "AccessTokenRejected")]
+    AccessTokenRejected,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<SecurityEventType>())
+            .await?;
+
+        // Failed attempts can't always be tied to an account, so `user_id` is optional.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(SecurityEvents::Table)
+                    .col(
+                        ColumnDef::new(SecurityEvents::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY".to_owned()),
+                    )
+                    .col(ColumnDef::new(SecurityEvents::UserId).integer().null())
+                    .col(
+                        ColumnDef::new(SecurityEvents::EventType)
+                            .custom(SecurityEventType::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SecurityEvents::IpAddress)
+                            .string_len(45)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SecurityEvents::UserAgent)
+                            .string_len(512)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SecurityEvents::DeviceId)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(current_timestamp_col(SecurityEvents::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_security_events_user_id")
+                            .from(SecurityEvents::Table, SecurityEvents::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-security-events-user-created-at")
+                    .table(SecurityEvents::Table)
+                    .col(SecurityEvents::UserId)
+                    .col((SecurityEvents::CreatedAt, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum SecurityEvents {
+    Table,
+    Id,
+    UserId,
+    EventType,
+    IpAddress,
+    UserAgent,
+    DeviceId,
+    CreatedAt,
+}
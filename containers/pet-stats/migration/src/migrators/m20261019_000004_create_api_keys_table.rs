@@ -0,0 +1,132 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+use super::{m20250121_000001_create_user_table::Users, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000004_create_api_keys_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(SecurityEventType::Enum)
+                    .add_value(SecurityEventType::ApiKeyCreated),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(SecurityEventType::Enum)
+                    .add_value(SecurityEventType::ApiKeyRevoked),
+            )
+            .await?;
+
+        // Personal API keys. Only an HMAC of the key is stored, `key_prefix` lets users tell
+        // their keys apart.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(ApiKeys::Table)
+                    .col(
+                        ColumnDef::new(ApiKeys::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY".to_owned()),
+                    )
+                    .col(ColumnDef::new(ApiKeys::UserId).integer().not_null())
+                    .col(ColumnDef::new(ApiKeys::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(ApiKeys::KeyPrefix).string_len(16).not_null())
+                    .col(ColumnDef::new(ApiKeys::KeyHash).var_binary(32).not_null())
+                    .col(
+                        ColumnDef::new(ApiKeys::Scopes)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ApiKeys::LastUsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ApiKeys::RevokedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(current_timestamp_col(ApiKeys::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_api_keys_user_id")
+                            .from(ApiKeys::Table, ApiKeys::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-api-keys-key-hash")
+                    .table(ApiKeys::Table)
+                    .col(ApiKeys::KeyHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-api-keys-user-id")
+                    .table(ApiKeys::Table)
+                    .col(ApiKeys::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum SecurityEventType {
+    #[iden = "security_event_type"]
+    Enum,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+}
+
+#[derive(Iden)]
+pub enum ApiKeys {
+    Table,
+    Id,
+    UserId,
+    Name,
+    KeyPrefix,
+    KeyHash,
+    Scopes,
+    LastUsedAt,
+    RevokedAt,
+    CreatedAt,
+}
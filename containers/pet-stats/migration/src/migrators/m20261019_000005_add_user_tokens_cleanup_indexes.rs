@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000005_add_user_tokens_cleanup_indexes"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The token cleanup task looks rows up by expiry, and revoked rows by the time
+        // they were revoked (`updated_at`).
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user-tokens-expires-at")
+                    .table(UserTokens::Table)
+                    .col(UserTokens::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user-tokens-revoked-updated-at")
+                    .table(UserTokens::Table)
+                    .col(UserTokens::Revoked)
+                    .col(UserTokens::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum UserTokens {
+    Table,
+    ExpiresAt,
+    Revoked,
+    UpdatedAt,
+}
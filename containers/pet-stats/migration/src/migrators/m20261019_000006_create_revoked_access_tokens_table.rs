@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20250121_000001_create_user_table::Users, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000006_create_revoked_access_tokens_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rows are only needed until the token would have expired anyway.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(RevokedAccessTokens::Table)
+                    .col(
+                        ColumnDef::new(RevokedAccessTokens::Jti)
+                            .string_len(64)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RevokedAccessTokens::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RevokedAccessTokens::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(RevokedAccessTokens::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_revoked_access_tokens_user_id")
+                            .from(RevokedAccessTokens::Table, RevokedAccessTokens::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-revoked-access-tokens-created-at")
+                    .table(RevokedAccessTokens::Table)
+                    .col(RevokedAccessTokens::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-revoked-access-tokens-expires-at")
+                    .table(RevokedAccessTokens::Table)
+                    .col(RevokedAccessTokens::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Access tokens of the user issued before `tokens_revoked_before` are rejected, so
+        // every session ends at once without listing each token's jti. Disabled accounts
+        // can't sign in until an admin enables them again.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserAccess::Table)
+                    .add_column(
+                        ColumnDef::new(UserAccess::TokensRevokedBefore)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(UserAccess::DisabledAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-users-tokens-revoked-before")
+                    .table(UserAccess::Table)
+                    .col(UserAccess::TokensRevokedBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum RevokedAccessTokens {
+    Table,
+    Jti,
+    UserId,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum UserAccess {
+    #[iden = "users"]
+    Table,
+    TokensRevokedBefore,
+    DisabledAt,
+}
@@ -0,0 +1,137 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000007_create_medical_records_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Vaccinations::Table)
+                    .col(
+                        ColumnDef::new(Vaccinations::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(Vaccinations::PetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Vaccinations::Vaccine)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Vaccinations::GivenOn).date().not_null())
+                    .col(ColumnDef::new(Vaccinations::NextDueOn).date().null())
+                    .col(ColumnDef::new(Vaccinations::Clinic).string_len(200).null())
+                    .col(current_timestamp_col(Vaccinations::CreatedAt))
+                    .col(current_timestamp_col(Vaccinations::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_vaccinations_pet_id")
+                            .from(Vaccinations::Table, Vaccinations::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vaccinations-pet-id-next-due-on")
+                    .table(Vaccinations::Table)
+                    .col(Vaccinations::PetId)
+                    .col(Vaccinations::NextDueOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Costs are kept in minor currency units (cents) to stay exact.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(VetVisits::Table)
+                    .col(
+                        ColumnDef::new(VetVisits::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(VetVisits::PetId).integer().not_null())
+                    .col(ColumnDef::new(VetVisits::VisitedOn).date().not_null())
+                    .col(ColumnDef::new(VetVisits::Reason).string_len(200).not_null())
+                    .col(ColumnDef::new(VetVisits::Diagnosis).text().null())
+                    .col(ColumnDef::new(VetVisits::Notes).text().null())
+                    .col(ColumnDef::new(VetVisits::CostCents).integer().null())
+                    .col(current_timestamp_col(VetVisits::CreatedAt))
+                    .col(current_timestamp_col(VetVisits::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_vet_visits_pet_id")
+                            .from(VetVisits::Table, VetVisits::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vet-visits-pet-id-visited-on")
+                    .table(VetVisits::Table)
+                    .col(VetVisits::PetId)
+                    .col((VetVisits::VisitedOn, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum Vaccinations {
+    Table,
+    Id,
+    PetId,
+    Vaccine,
+    GivenOn,
+    NextDueOn,
+    Clinic,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum VetVisits {
+    Table,
+    Id,
+    PetId,
+    VisitedOn,
+    Reason,
+    Diagnosis,
+    Notes,
+    CostCents,
+    CreatedAt,
+    UpdatedAt,
+}
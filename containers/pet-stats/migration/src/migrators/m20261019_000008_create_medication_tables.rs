@@ -0,0 +1,210 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000008_create_medication_tables"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "medication_frequency"
+)]
+pub enum MedicationFrequency {
+    #[sea_orm(string_value = "Daily")]
+    Daily,
+    #[sea_orm(string_value = "EveryOtherDay")]
+    EveryOtherDay,
+    #[sea_orm(string_value = "Weekly")]
+    Weekly,
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "dose_status")]
+pub enum DoseStatus {
+    #[sea_orm(string_value = "Given")]
+    Given,
+    #[sea_orm(string_value = "Skipped")]
+    Skipped,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // IANA name, e.g. `Asia/Seoul`. Decides what "today" is for the user.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::Timezone)
+                            .string_len(64)
+                            .not_null()
+                            .default("UTC"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<MedicationFrequency>())
+            .await?;
+        manager
+            .create_type(schema.create_enum_from_active_enum::<DoseStatus>())
+            .await?;
+
+        // A dose is due on every `frequency` day from `start_on` through `end_on`,
+        // `doses_per_day` times.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Medications::Table)
+                    .col(
+                        ColumnDef::new(Medications::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(Medications::PetId).integer().not_null())
+                    .col(ColumnDef::new(Medications::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Medications::Dose).float().not_null())
+                    .col(ColumnDef::new(Medications::Unit).string_len(20).not_null())
+                    .col(
+                        ColumnDef::new(Medications::Frequency)
+                            .custom(MedicationFrequency::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Medications::DosesPerDay)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(ColumnDef::new(Medications::StartOn).date().not_null())
+                    .col(ColumnDef::new(Medications::EndOn).date().null())
+                    .col(current_timestamp_col(Medications::CreatedAt))
+                    .col(current_timestamp_col(Medications::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_medications_pet_id")
+                            .from(Medications::Table, Medications::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(MedicationDoses::Table)
+                    .col(
+                        ColumnDef::new(MedicationDoses::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(
+                        ColumnDef::new(MedicationDoses::MedicationId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MedicationDoses::ScheduledOn)
+                            .date()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MedicationDoses::DoseNumber)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MedicationDoses::Status)
+                            .custom(DoseStatus::name())
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(MedicationDoses::RecordedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_medication_doses_medication_id")
+                            .from(MedicationDoses::Table, MedicationDoses::MedicationId)
+                            .to(Medications::Table, Medications::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One log entry per scheduled dose, marking it again overwrites the status.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-medication-doses-medication-scheduled-dose")
+                    .table(MedicationDoses::Table)
+                    .col(MedicationDoses::MedicationId)
+                    .col(MedicationDoses::ScheduledOn)
+                    .col(MedicationDoses::DoseNumber)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Timezone,
+}
+
+#[derive(Iden)]
+pub enum Medications {
+    Table,
+    Id,
+    PetId,
+    Name,
+    Dose,
+    Unit,
+    Frequency,
+    DosesPerDay,
+    StartOn,
+    EndOn,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum MedicationDoses {
+    Table,
+    Id,
+    MedicationId,
+    ScheduledOn,
+    DoseNumber,
+    Status,
+    RecordedAt,
+}
@@ -0,0 +1,127 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000009_create_care_task_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Grooming, nail trimming, litter changes... recurring per an iCalendar RRULE
+        // starting from `starts_on`.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(CareTasks::Table)
+                    .col(
+                        ColumnDef::new(CareTasks::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(CareTasks::PetId).integer().not_null())
+                    .col(ColumnDef::new(CareTasks::Title).string_len(100).not_null())
+                    .col(ColumnDef::new(CareTasks::Notes).text().null())
+                    .col(ColumnDef::new(CareTasks::Rrule).string_len(255).not_null())
+                    .col(ColumnDef::new(CareTasks::StartsOn).date().not_null())
+                    .col(current_timestamp_col(CareTasks::CreatedAt))
+                    .col(current_timestamp_col(CareTasks::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_care_tasks_pet_id")
+                            .from(CareTasks::Table, CareTasks::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(CareTaskCompletions::Table)
+                    .col(
+                        ColumnDef::new(CareTaskCompletions::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(
+                        ColumnDef::new(CareTaskCompletions::CareTaskId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CareTaskCompletions::DueOn).date().not_null())
+                    .col(ColumnDef::new(CareTaskCompletions::Note).text().null())
+                    .col(current_timestamp_col(CareTaskCompletions::CompletedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_care_task_completions_care_task_id")
+                            .from(CareTaskCompletions::Table, CareTaskCompletions::CareTaskId)
+                            .to(CareTasks::Table, CareTasks::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One completion per occurrence, also serves the latest-completion lookup.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-care-task-completions-task-due-on")
+                    .table(CareTaskCompletions::Table)
+                    .col(CareTaskCompletions::CareTaskId)
+                    .col(CareTaskCompletions::DueOn)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum CareTasks {
+    Table,
+    Id,
+    PetId,
+    Title,
+    Notes,
+    Rrule,
+    StartsOn,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum CareTaskCompletions {
+    Table,
+    Id,
+    CareTaskId,
+    DueOn,
+    Note,
+    CompletedAt,
+}
@@ -0,0 +1,196 @@
+use sea_orm::{ActiveEnum, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::PetSpeciesType, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000010_create_breeds_table"
+    }
+}
+
+/// Name, weight range in kg and life expectancy in years.
+type BreedSeed = (&'static str, Option<(f32, f32)>, (i32, i32));
+
+const DOG_BREEDS: &[BreedSeed] = &[
+    ("Beagle", Some((9.0, 11.0)), (10, 15)),
+    ("Border Collie", Some((12.0, 20.0)), (12, 15)),
+    ("Bulldog", Some((18.0, 25.0)), (8, 10)),
+    ("Chihuahua", Some((1.5, 3.0)), (14, 16)),
+    ("Dachshund", Some((7.0, 15.0)), (12, 16)),
+    ("French Bulldog", Some((8.0, 14.0)), (10, 12)),
+    ("German Shepherd", Some((22.0, 40.0)), (9, 13)),
+    ("Golden Retriever", Some((25.0, 34.0)), (10, 12)),
+    ("Labrador Retriever", Some((25.0, 36.0)), (10, 12)),
+    ("Maltese", Some((2.0, 4.0)), (12, 15)),
+    ("Pomeranian", Some((1.4, 3.2)), (12, 16)),
+    ("Poodle", Some((18.0, 32.0)), (10, 18)),
+    ("Rottweiler", Some((35.0, 60.0)), (9, 10)),
+    ("Shiba Inu", Some((7.0, 11.0)), (13, 16)),
+    ("Shih Tzu", Some((4.0, 7.5)), (10, 18)),
+    ("Siberian Husky", Some((16.0, 27.0)), (12, 14)),
+    ("Yorkshire Terrier", Some((2.0, 3.2)), (11, 15)),
+];
+
+const CAT_BREEDS: &[BreedSeed] = &[
+    ("Bengal", Some((3.5, 7.0)), (12, 16)),
+    ("British Shorthair", Some((4.0, 8.0)), (12, 17)),
+    ("Domestic Shorthair", Some((3.5, 6.5)), (12, 18)),
+    ("Maine Coon", Some((5.5, 11.0)), (12, 15)),
+    ("Persian", Some((3.0, 5.5)), (12, 17)),
+    ("Ragdoll", Some((4.5, 9.0)), (12, 17)),
+    ("Russian Blue", Some((3.0, 5.5)), (15, 20)),
+    ("Scottish Fold", Some((2.7, 6.0)), (11, 15)),
+    ("Siamese", Some((3.5, 5.5)), (15, 20)),
+    ("Sphynx", Some((3.0, 5.0)), (8, 14)),
+];
+
+const FISH_BREEDS: &[BreedSeed] = &[("Betta", None, (3, 5)), ("Goldfish", None, (10, 15))];
+
+const LIZARD_BREEDS: &[BreedSeed] = &[
+    ("Bearded Dragon", Some((0.3, 0.6)), (10, 15)),
+    ("Leopard Gecko", Some((0.045, 0.08)), (10, 20)),
+];
+
+const TURTLE_BREEDS: &[BreedSeed] = &[("Red-eared Slider", Some((0.25, 1.5)), (20, 30))];
+
+const SNAKE_BREEDS: &[BreedSeed] = &[
+    ("Ball Python", Some((1.2, 1.8)), (20, 30)),
+    ("Corn Snake", Some((0.4, 0.9)), (15, 20)),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Reference data, weights are in kg like `pets.weight`.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Breeds::Table)
+                    .col(
+                        ColumnDef::new(Breeds::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(
+                        ColumnDef::new(Breeds::Species)
+                            .custom(PetSpeciesType::name())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Breeds::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Breeds::MinWeightKg).float().null())
+                    .col(ColumnDef::new(Breeds::MaxWeightKg).float().null())
+                    .col(
+                        ColumnDef::new(Breeds::MinLifeExpectancyYears)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Breeds::MaxLifeExpectancyYears)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(Breeds::CreatedAt))
+                    .check(Expr::cust("min_weight_kg <= max_weight_kg"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-breeds-species-name")
+                    .table(Breeds::Table)
+                    .col(Breeds::Species)
+                    .col(Breeds::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut seed = Query::insert()
+            .into_table(Breeds::Table)
+            .columns([
+                Breeds::Species,
+                Breeds::Name,
+                Breeds::MinWeightKg,
+                Breeds::MaxWeightKg,
+                Breeds::MinLifeExpectancyYears,
+                Breeds::MaxLifeExpectancyYears,
+            ])
+            .to_owned();
+        for (species, breeds) in [
+            (PetSpeciesType::Dog, DOG_BREEDS),
+            (PetSpeciesType::Cat, CAT_BREEDS),
+            (PetSpeciesType::Fish, FISH_BREEDS),
+            (PetSpeciesType::Lizard, LIZARD_BREEDS),
+            (PetSpeciesType::Turtle, TURTLE_BREEDS),
+            (PetSpeciesType::Snake, SNAKE_BREEDS),
+        ] {
+            for (name, weight, (min_life, max_life)) in breeds {
+                seed.values_panic([
+                    ActiveEnum::as_enum(&species),
+                    (*name).into(),
+                    weight.map(|(min, _)| min).into(),
+                    weight.map(|(_, max)| max).into(),
+                    (*min_life).into(),
+                    (*max_life).into(),
+                ]);
+            }
+        }
+        manager.exec_stmt(seed).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .add_column(ColumnDef::new(Pets::BreedId).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_pets_breed_id")
+                            .from_tbl(Pets::Table)
+                            .from_col(Pets::BreedId)
+                            .to_tbl(Breeds::Table)
+                            .to_col(Breeds::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum Pets {
+    Table,
+    BreedId,
+}
+
+#[derive(Iden)]
+pub enum Breeds {
+    Table,
+    Id,
+    Species,
+    Name,
+    MinWeightKg,
+    MaxWeightKg,
+    MinLifeExpectancyYears,
+    MaxLifeExpectancyYears,
+    CreatedAt,
+}
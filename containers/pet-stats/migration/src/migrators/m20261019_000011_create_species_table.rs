@@ -0,0 +1,220 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::{prelude::*, sea_query::extension::postgres::Type};
+
+use super::{
+    m20250121_000001_create_user_table::Users, m20261019_000010_create_breeds_table::Breeds,
+    utils::current_timestamp_col,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000011_create_species_table"
+    }
+}
+
+/// The former `pet_species_type` values with the months old they become adult and senior.
+const BUILT_IN_SPECIES: [(&str, i32, i32); 6] = [
+    ("Dog", 12, 7 * 12),
+    ("Cat", 12, 11 * 12),
+    ("Fish", 6, 5 * 12),
+    ("Lizard", 18, 10 * 12),
+    ("Turtle", 5 * 12, 30 * 12),
+    ("Snake", 2 * 12, 15 * 12),
+];
+
+/// Runs before a user row is deleted. Other users whose pets still use one of the user's
+/// custom species get a copy of their own, unless they already have one of the same name, and
+/// their pets switch to it.
+const HAND_OVER_CUSTOM_SPECIES: &str = r#"
+CREATE OR REPLACE FUNCTION hand_over_custom_species() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO "species" ("name", "user_id", "adult_from_months", "senior_from_months")
+    SELECT DISTINCT "gone"."name", "pets"."user_id", "gone"."adult_from_months",
+        "gone"."senior_from_months"
+    FROM "pets"
+    JOIN "species" AS "gone" ON "gone"."id" = "pets"."species_id"
+    WHERE "gone"."user_id" = OLD."id"
+        AND "pets"."user_id" <> OLD."id"
+    ON CONFLICT ("user_id", "name") DO NOTHING;
+
+    UPDATE "pets" SET "species_id" = "own"."id"
+    FROM "species" AS "gone", "species" AS "own"
+    WHERE "pets"."species_id" = "gone"."id"
+        AND "gone"."user_id" = OLD."id"
+        AND "pets"."user_id" <> OLD."id"
+        AND "own"."user_id" = "pets"."user_id"
+        AND "own"."name" = "gone"."name";
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER "trg_users_hand_over_custom_species"
+BEFORE DELETE ON "users"
+FOR EACH ROW EXECUTE FUNCTION hand_over_custom_species();
+"#;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Built-in species have a `code` and no owner. Users can add their own for anything
+        // else, those have an owner and no code.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Species::Table)
+                    .col(
+                        ColumnDef::new(Species::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(
+                        ColumnDef::new(Species::Code)
+                            .string_len(50)
+                            .null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Species::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Species::UserId).integer().null())
+                    .col(ColumnDef::new(Species::AdultFromMonths).integer().null())
+                    .col(ColumnDef::new(Species::SeniorFromMonths).integer().null())
+                    .col(current_timestamp_col(Species::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_species_user_id")
+                            .from(Species::Table, Species::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("(code IS NULL) <> (user_id IS NULL)"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-species-user-id-name")
+                    .table(Species::Table)
+                    .col(Species::UserId)
+                    .col(Species::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut seed = Query::insert()
+            .into_table(Species::Table)
+            .columns([
+                Species::Code,
+                Species::Name,
+                Species::AdultFromMonths,
+                Species::SeniorFromMonths,
+            ])
+            .to_owned();
+        for (code, adult_from, senior_from) in BUILT_IN_SPECIES {
+            seed.values_panic([
+                code.into(),
+                code.into(),
+                adult_from.into(),
+                senior_from.into(),
+            ]);
+        }
+        manager.exec_stmt(seed).await?;
+
+        // Point pets and breeds at the species rows, then retire the enum.
+        for table in ["pets", "breeds"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(ColumnDef::new(SpeciesColumn::SpeciesId).integer().null())
+                        .to_owned(),
+                )
+                .await?;
+            db.execute_unprepared(&format!(
+                r#"UPDATE "{table}" SET "species_id" = "species"."id"
+                FROM "species" WHERE "species"."code" = "{table}"."species"::text"#
+            ))
+            .await?;
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .modify_column(
+                            ColumnDef::new(SpeciesColumn::SpeciesId)
+                                .integer()
+                                .not_null(),
+                        )
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("fk_{}_species_id", table))
+                                .from_tbl(Alias::new(table))
+                                .from_col(SpeciesColumn::SpeciesId)
+                                .to_tbl(Species::Table)
+                                .to_col(Species::Id)
+                                .on_delete(ForeignKeyAction::Restrict)
+                                .on_update(ForeignKeyAction::Cascade),
+                        )
+                        .drop_column(SpeciesColumn::Species)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-breeds-species-id-name")
+                    .table(Breeds::Table)
+                    .col(SpeciesColumn::SpeciesId)
+                    .col(Breeds::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(Alias::new("pet_species_type")).to_owned())
+            .await?;
+
+        // Without it a custom species would be deleted with its owner, and `fk_pets_species_id`
+        // would refuse to delete the account while another user's pet still uses it.
+        db.execute_unprepared(HAND_OVER_CUSTOM_SPECIES).await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+/// Species columns of `pets` and `breeds`.
+#[derive(Iden)]
+enum SpeciesColumn {
+    Species,
+    SpeciesId,
+}
+
+#[derive(Iden)]
+pub enum Species {
+    Table,
+    Id,
+    Code,
+    Name,
+    UserId,
+    AdultFromMonths,
+    SeniorFromMonths,
+    CreatedAt,
+}
@@ -0,0 +1,145 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20250121_000001_create_user_table::Users, m20250808_000001_create_pet_table::FeedRecords,
+    utils::current_timestamp_col,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000012_create_foods_table"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "food_unit")]
+pub enum FoodUnit {
+    #[sea_orm(string_value = "Gram")]
+    Gram,
+    #[sea_orm(string_value = "Cup")]
+    Cup,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<FoodUnit>())
+            .await?;
+
+        // Foods the user feeds, with the energy from the label. `unit` is how it is usually
+        // measured out and has to have a known kcal value.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Foods::Table)
+                    .col(
+                        ColumnDef::new(Foods::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(Foods::UserId).integer().not_null())
+                    .col(ColumnDef::new(Foods::Brand).string_len(100).not_null())
+                    .col(ColumnDef::new(Foods::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Foods::KcalPer100g).float().null())
+                    .col(ColumnDef::new(Foods::KcalPerCup).float().null())
+                    .col(
+                        ColumnDef::new(Foods::Unit)
+                            .custom(FoodUnit::name())
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(Foods::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_foods_user_id")
+                            .from(Foods::Table, Foods::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("kcal_per_100g >= 0 AND kcal_per_cup >= 0"))
+                    .check(Expr::cust(
+                        "CASE unit WHEN 'Gram' THEN kcal_per_100g IS NOT NULL \
+                         ELSE kcal_per_cup IS NOT NULL END",
+                    ))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-foods-user-id-brand-name")
+                    .table(Foods::Table)
+                    .col(Foods::UserId)
+                    .col(Foods::Brand)
+                    .col(Foods::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // `amount` is now measured in `unit`, which may be a fraction of a cup.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FeedRecords::Table)
+                    .modify_column(ColumnDef::new(FeedRecords::Amount).float().null())
+                    .add_column(ColumnDef::new(FeedRecordFood::FoodId).integer().null())
+                    .add_column(
+                        ColumnDef::new(FeedRecordFood::Unit)
+                            .custom(FoodUnit::name())
+                            .null(),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_feed_records_food_id")
+                            .from_tbl(FeedRecords::Table)
+                            .from_col(FeedRecordFood::FoodId)
+                            .to_tbl(Foods::Table)
+                            .to_col(Foods::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum FeedRecordFood {
+    FoodId,
+    Unit,
+}
+
+#[derive(Iden)]
+pub enum Foods {
+    Table,
+    Id,
+    UserId,
+    Brand,
+    Name,
+    #[iden = "kcal_per_100g"]
+    KcalPer100g,
+    KcalPerCup,
+    Unit,
+    CreatedAt,
+}
@@ -0,0 +1,106 @@
+use sea_orm::{ActiveEnum, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261019_000012_create_foods_table::{FoodUnit, Foods},
+    utils::current_timestamp_col,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000013_create_food_purchases_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Bags of food bought, stock is what was bought less what was fed since.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(FoodPurchases::Table)
+                    .col(
+                        ColumnDef::new(FoodPurchases::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(FoodPurchases::FoodId).integer().not_null())
+                    .col(ColumnDef::new(FoodPurchases::Size).float().not_null())
+                    .col(
+                        ColumnDef::new(FoodPurchases::Unit)
+                            .custom(FoodUnit::name())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(FoodPurchases::PurchasedOn).date().not_null())
+                    .col(current_timestamp_col(FoodPurchases::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_food_purchases_food_id")
+                            .from(FoodPurchases::Table, FoodPurchases::FoodId)
+                            .to(Foods::Table, Foods::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("size > 0"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-food-purchases-food-id-purchased-on")
+                    .table(FoodPurchases::Table)
+                    .col(FoodPurchases::FoodId)
+                    .col(FoodPurchases::PurchasedOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Consumption is summed per food.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-feed-records-food-id-created-at")
+                    .table(FeedRecords::Table)
+                    .col(FeedRecords::FoodId)
+                    .col(FeedRecords::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+enum FeedRecords {
+    Table,
+    FoodId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum FoodPurchases {
+    Table,
+    Id,
+    FoodId,
+    Size,
+    Unit,
+    PurchasedOn,
+    CreatedAt,
+}
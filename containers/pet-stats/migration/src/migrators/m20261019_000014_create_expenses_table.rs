@@ -0,0 +1,114 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000014_create_expenses_table"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "expense_category")]
+pub enum ExpenseCategory {
+    #[sea_orm(string_value = "Food")]
+    Food,
+    #[sea_orm(string_value = "Vet")]
+    Vet,
+    #[sea_orm(string_value = "Grooming")]
+    Grooming,
+    #[sea_orm(string_value = "Toys")]
+    Toys,
+    #[sea_orm(string_value = "Insurance")]
+    Insurance,
+    #[sea_orm(string_value = "Other")]
+    Other,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<ExpenseCategory>())
+            .await?;
+
+        // Money spent on a pet, `currency` is an ISO 4217 code. Amounts in different
+        // currencies are never added up.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Expenses::Table)
+                    .col(
+                        ColumnDef::new(Expenses::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(Expenses::PetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Expenses::Category)
+                            .custom(ExpenseCategory::name())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Expenses::Amount).decimal_len(12, 2).not_null())
+                    .col(ColumnDef::new(Expenses::Currency).char_len(3).not_null())
+                    .col(ColumnDef::new(Expenses::SpentOn).date().not_null())
+                    .col(ColumnDef::new(Expenses::Note).string_len(255).null())
+                    .col(current_timestamp_col(Expenses::CreatedAt))
+                    .col(current_timestamp_col(Expenses::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_expenses_pet_id")
+                            .from(Expenses::Table, Expenses::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("amount >= 0"))
+                    .check(Expr::cust("currency ~ '^[A-Z]{3}$'"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-expenses-pet-id-spent-on")
+                    .table(Expenses::Table)
+                    .col(Expenses::PetId)
+                    .col(Expenses::SpentOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum Expenses {
+    Table,
+    Id,
+    PetId,
+    Category,
+    Amount,
+    Currency,
+    SpentOn,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
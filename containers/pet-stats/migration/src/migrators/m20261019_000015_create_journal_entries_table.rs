@@ -0,0 +1,105 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000015_create_journal_entries_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Owner's notes on a pet's health. Mood and energy are scored 1 to 5, symptoms are
+        // lowercase tags such as `vomiting` or `limping`.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(JournalEntries::Table)
+                    .col(
+                        ColumnDef::new(JournalEntries::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(JournalEntries::PetId).integer().not_null())
+                    .col(ColumnDef::new(JournalEntries::Body).text().not_null())
+                    .col(ColumnDef::new(JournalEntries::Mood).integer().null())
+                    .col(ColumnDef::new(JournalEntries::Energy).integer().null())
+                    .col(
+                        ColumnDef::new(JournalEntries::Symptoms)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default(Expr::cust("'{}'")),
+                    )
+                    .col(current_timestamp_col(JournalEntries::NotedAt))
+                    .col(current_timestamp_col(JournalEntries::CreatedAt))
+                    .col(current_timestamp_col(JournalEntries::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_journal_entries_pet_id")
+                            .from(JournalEntries::Table, JournalEntries::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("mood BETWEEN 1 AND 5"))
+                    .check(Expr::cust("energy BETWEEN 1 AND 5"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-journal-entries-pet-id-noted-at")
+                    .table(JournalEntries::Table)
+                    .col(JournalEntries::PetId)
+                    .col(JournalEntries::NotedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Symptom filters use `@>`, which GIN indexes on arrays support.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-journal-entries-symptoms")
+                    .table(JournalEntries::Table)
+                    .col(JournalEntries::Symptoms)
+                    .index_type(IndexType::Custom(Alias::new("GIN").into_iden()))
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum JournalEntries {
+    Table,
+    Id,
+    PetId,
+    Body,
+    Mood,
+    Energy,
+    Symptoms,
+    NotedAt,
+    CreatedAt,
+    UpdatedAt,
+}
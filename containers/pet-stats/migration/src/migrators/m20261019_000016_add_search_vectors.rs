@@ -0,0 +1,105 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20250808_000001_create_pet_table::Pets,
+    m20261019_000007_create_medical_records_tables::VetVisits,
+    m20261019_000015_create_journal_entries_table::JournalEntries,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000016_add_search_vectors"
+    }
+}
+
+/// Stored `tsvector` computed by Postgres from `expression`, so it follows every write.
+fn search_vector_col(expression: &str) -> ColumnDef {
+    ColumnDef::new(SearchVector::SearchVector)
+        .custom(Alias::new("tsvector"))
+        .not_null()
+        .extra(format!("GENERATED ALWAYS AS ({expression}) STORED"))
+        .to_owned()
+}
+
+fn search_vector_idx(name: &str, table: impl IntoTableRef) -> IndexCreateStatement {
+    Index::create()
+        .name(name)
+        .table(table)
+        .col(SearchVector::SearchVector)
+        .index_type(IndexType::Custom(Alias::new("GIN").into_iden()))
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Pet names aren't English words, so they are not stemmed.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .add_column(search_vector_col("to_tsvector('simple', name)"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(JournalEntries::Table)
+                    .add_column(search_vector_col("to_tsvector('english', body)"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The reason for the visit ranks above what the vet found, which ranks above notes.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VetVisits::Table)
+                    .add_column(search_vector_col(
+                        "setweight(to_tsvector('english', reason), 'A') \
+                         || setweight(to_tsvector('english', coalesce(diagnosis, '')), 'B') \
+                         || setweight(to_tsvector('english', coalesce(notes, '')), 'C')",
+                    ))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(search_vector_idx("idx-pets-search-vector", Pets::Table))
+            .await?;
+        manager
+            .create_index(search_vector_idx(
+                "idx-journal-entries-search-vector",
+                JournalEntries::Table,
+            ))
+            .await?;
+        manager
+            .create_index(search_vector_idx(
+                "idx-vet-visits-search-vector",
+                VetVisits::Table,
+            ))
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+/// Only read by search queries, so the column is not part of the entities.
+#[derive(Iden)]
+enum SearchVector {
+    SearchVector,
+}
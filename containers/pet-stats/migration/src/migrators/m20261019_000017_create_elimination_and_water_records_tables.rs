@@ -0,0 +1,157 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::Pets, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000017_create_elimination_and_water_records_tables"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "elimination_type")]
+pub enum EliminationType {
+    #[sea_orm(string_value = "Urine")]
+    Urine,
+    #[sea_orm(string_value = "Stool")]
+    Stool,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<EliminationType>())
+            .await?;
+
+        // Like feed records, `created_at` is when it happened. Stools may have a consistency
+        // score from 1 (hard) to 7 (liquid).
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(EliminationRecords::Table)
+                    .col(
+                        ColumnDef::new(EliminationRecords::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(
+                        ColumnDef::new(EliminationRecords::PetId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EliminationRecords::Kind)
+                            .custom(EliminationType::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EliminationRecords::Consistency)
+                            .integer()
+                            .null(),
+                    )
+                    .col(current_timestamp_col(EliminationRecords::CreatedAt))
+                    .col(current_timestamp_col(EliminationRecords::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_elimination_records_pet_id")
+                            .from(EliminationRecords::Table, EliminationRecords::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("consistency BETWEEN 1 AND 7"))
+                    .check(Expr::cust("consistency IS NULL OR kind = 'Stool'"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-elimination-records-pet-id-created-at")
+                    .table(EliminationRecords::Table)
+                    .col(EliminationRecords::PetId)
+                    .col(EliminationRecords::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(WaterRecords::Table)
+                    .col(
+                        ColumnDef::new(WaterRecords::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(WaterRecords::PetId).integer().not_null())
+                    .col(ColumnDef::new(WaterRecords::AmountMl).integer().not_null())
+                    .col(current_timestamp_col(WaterRecords::CreatedAt))
+                    .col(current_timestamp_col(WaterRecords::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_water_records_pet_id")
+                            .from(WaterRecords::Table, WaterRecords::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("amount_ml > 0"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-water-records-pet-id-created-at")
+                    .table(WaterRecords::Table)
+                    .col(WaterRecords::PetId)
+                    .col(WaterRecords::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum EliminationRecords {
+    Table,
+    Id,
+    PetId,
+    Kind,
+    Consistency,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum WaterRecords {
+    Table,
+    Id,
+    PetId,
+    AmountMl,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,80 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{m20250808_000001_create_pet_table::WorkRecords, utils::current_timestamp_col};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000018_create_walk_tracks_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Route of a walk as an encoded polyline. A walk has at most one, replaced when a new
+        // route is uploaded. The walk's distance and time are computed from it.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(WalkTracks::Table)
+                    .col(
+                        ColumnDef::new(WalkTracks::WorkRecordId)
+                            .integer()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WalkTracks::Polyline).text().not_null())
+                    .col(ColumnDef::new(WalkTracks::PointCount).integer().not_null())
+                    .col(ColumnDef::new(WalkTracks::DistanceM).integer().not_null())
+                    .col(
+                        ColumnDef::new(WalkTracks::StartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(WalkTracks::EndedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(current_timestamp_col(WalkTracks::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_walk_tracks_work_record_id")
+                            .from(WalkTracks::Table, WalkTracks::WorkRecordId)
+                            .to(WorkRecords::Table, WorkRecords::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("point_count >= 2 AND distance_m >= 0"))
+                    .check(Expr::cust("ended_at >= started_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum WalkTracks {
+    Table,
+    WorkRecordId,
+    Polyline,
+    PointCount,
+    DistanceM,
+    StartedAt,
+    EndedAt,
+    CreatedAt,
+}
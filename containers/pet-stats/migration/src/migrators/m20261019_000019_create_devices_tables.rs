@@ -0,0 +1,157 @@
+use sea_orm::TransactionTrait;
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20250121_000001_create_user_table::Users,
+    m20250808_000001_create_pet_table::{FeedRecords, Pets, WorkRecords},
+    utils::current_timestamp_col,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000019_create_devices_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        // Collars and feeders posting events for one pet. `uid` is public, the signing
+        // secret is derived from it on the server and never stored.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(Devices::Table)
+                    .col(
+                        ColumnDef::new(Devices::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(Devices::UserId).integer().not_null())
+                    .col(ColumnDef::new(Devices::PetId).integer().not_null())
+                    .col(ColumnDef::new(Devices::Uid).string_len(64).not_null())
+                    .col(ColumnDef::new(Devices::Name).string_len(100).not_null())
+                    .col(
+                        ColumnDef::new(Devices::LastSeenAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(current_timestamp_col(Devices::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_devices_user_id")
+                            .from(Devices::Table, Devices::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_devices_pet_id")
+                            .from(Devices::Table, Devices::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-devices-uid")
+                    .table(Devices::Table)
+                    .col(Devices::Uid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Events already ingested, keyed by the device's own event id so retried batches are
+        // not recorded twice. Points at the record the event became.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(DeviceEvents::Table)
+                    .col(ColumnDef::new(DeviceEvents::DeviceId).integer().not_null())
+                    .col(
+                        ColumnDef::new(DeviceEvents::EventId)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DeviceEvents::FeedRecordId).integer().null())
+                    .col(ColumnDef::new(DeviceEvents::WorkRecordId).integer().null())
+                    .col(current_timestamp_col(DeviceEvents::ReceivedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(DeviceEvents::DeviceId)
+                            .col(DeviceEvents::EventId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_device_events_device_id")
+                            .from(DeviceEvents::Table, DeviceEvents::DeviceId)
+                            .to(Devices::Table, Devices::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_device_events_feed_record_id")
+                            .from(DeviceEvents::Table, DeviceEvents::FeedRecordId)
+                            .to(FeedRecords::Table, FeedRecords::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_device_events_work_record_id")
+                            .from(DeviceEvents::Table, DeviceEvents::WorkRecordId)
+                            .to(WorkRecords::Table, WorkRecords::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum Devices {
+    Table,
+    Id,
+    UserId,
+    PetId,
+    Uid,
+    Name,
+    LastSeenAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum DeviceEvents {
+    Table,
+    DeviceId,
+    EventId,
+    FeedRecordId,
+    WorkRecordId,
+    ReceivedAt,
+}
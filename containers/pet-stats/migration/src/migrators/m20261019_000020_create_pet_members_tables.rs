@@ -0,0 +1,190 @@
+use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20250121_000001_create_user_table::Users, m20250808_000001_create_pet_table::Pets,
+    utils::current_timestamp_col,
+};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m_20261019_000020_create_pet_members_tables"
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "pet_role")]
+pub enum PetRole {
+    #[sea_orm(string_value = "Owner")]
+    Owner,
+    #[sea_orm(string_value = "Caretaker")]
+    Caretaker,
+    #[sea_orm(string_value = "Viewer")]
+    Viewer,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        let db = manager.get_connection();
+        let transaction = db.begin().await?;
+
+        manager
+            .create_type(schema.create_enum_from_active_enum::<PetRole>())
+            .await?;
+
+        // Who may see or look after a pet. Access goes through this table, `pets.user_id`
+        // only records who added the pet.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(PetMembers::Table)
+                    .col(ColumnDef::new(PetMembers::PetId).integer().not_null())
+                    .col(ColumnDef::new(PetMembers::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PetMembers::Role)
+                            .custom(PetRole::name())
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(PetMembers::CreatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(PetMembers::PetId)
+                            .col(PetMembers::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_members_pet_id")
+                            .from(PetMembers::Table, PetMembers::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_members_user_id")
+                            .from(PetMembers::Table, PetMembers::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pet_members-user_id")
+                    .table(PetMembers::Table)
+                    .col(PetMembers::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"INSERT INTO "pet_members" ("pet_id", "user_id", "role")
+            SELECT "id", "user_id", 'Owner' FROM "pets""#,
+        )
+        .await?;
+
+        // Single use codes inviting someone to a pet with a role. Only an HMAC of the code is
+        // stored, like API keys.
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(PetInvitations::Table)
+                    .col(
+                        ColumnDef::new(PetInvitations::Id)
+                            .integer()
+                            .primary_key()
+                            .extra("GENERATED ALWAYS AS IDENTITY"),
+                    )
+                    .col(ColumnDef::new(PetInvitations::PetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PetInvitations::InvitedBy)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PetInvitations::Role)
+                            .custom(PetRole::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PetInvitations::CodeHash)
+                            .var_binary(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PetInvitations::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(current_timestamp_col(PetInvitations::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_invitations_pet_id")
+                            .from(PetInvitations::Table, PetInvitations::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_invitations_invited_by")
+                            .from(PetInvitations::Table, PetInvitations::InvitedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pet_invitations-code_hash")
+                    .table(PetInvitations::Table)
+                    .col(PetInvitations::CodeHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Err(DbErr::Migration("We Don't Do That Here".to_owned()))
+    }
+}
+
+#[derive(Iden)]
+pub enum PetMembers {
+    Table,
+    PetId,
+    UserId,
+    Role,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum PetInvitations {
+    Table,
+    Id,
+    PetId,
+    InvitedBy,
+    Role,
+    CodeHash,
+    ExpiresAt,
+    CreatedAt,
+}
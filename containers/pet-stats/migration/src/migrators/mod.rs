@@ -1,4 +1,24 @@
 pub(crate) mod enums;
 pub mod m20250121_000001_create_user_table;
 pub mod m20250808_000001_create_pet_table;
+pub mod m20261019_000001_add_user_deletion_schedule;
+pub mod m20261019_000002_create_two_factor_tables;
+pub mod m20261019_000003_create_security_events_table;
+pub mod m20261019_000004_create_api_keys_table;
+pub mod m20261019_000005_add_user_tokens_cleanup_indexes;
+pub mod m20261019_000006_create_revoked_access_tokens_table;
+pub mod m20261019_000007_create_medical_records_tables;
+pub mod m20261019_000008_create_medication_tables;
+pub mod m20261019_000009_create_care_task_tables;
+pub mod m20261019_000010_create_breeds_table;
+pub mod m20261019_000011_create_species_table;
+pub mod m20261019_000012_create_foods_table;
+pub mod m20261019_000013_create_food_purchases_table;
+pub mod m20261019_000014_create_expenses_table;
+pub mod m20261019_000015_create_journal_entries_table;
+pub mod m20261019_000016_add_search_vectors;
+pub mod m20261019_000017_create_elimination_and_water_records_tables;
+pub mod m20261019_000018_create_walk_tracks_table;
+pub mod m20261019_000019_create_devices_tables;
+pub mod m20261019_000020_create_pet_members_tables;
 pub(crate) mod utils;
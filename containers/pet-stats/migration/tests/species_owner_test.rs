@@ -0,0 +1,158 @@
+//! Runs against a real Postgres, configured like the app through `PET_STAT_CENTRAL_DB_*`.
+//! Migrates it up and only touches rows it creates.
+
+use config::db_config::PetStatCentralDbConfig;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement, Value};
+use tokio::sync::Mutex;
+
+/// Whether this run already migrated the database, tests run in parallel.
+static MIGRATED: Mutex<bool> = Mutex::const_new(false);
+
+async fn migrated_db() -> DatabaseConnection {
+    let db = Database::connect(PetStatCentralDbConfig::from_env().unwrap().url)
+        .await
+        .unwrap();
+    let mut migrated = MIGRATED.lock().await;
+    if !*migrated {
+        Migrator::up(&db, None).await.unwrap();
+        *migrated = true;
+    }
+    db
+}
+
+/// Run `sql` and return the `id` of the first row it returns, if any.
+async fn query_id(
+    db: &DatabaseConnection,
+    sql: &str,
+    values: impl IntoIterator<Item = Value>,
+) -> Option<i32> {
+    db.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql,
+        values,
+    ))
+    .await
+    .unwrap()
+    .map(|row| row.try_get("", "id").unwrap())
+}
+
+async fn create_user(db: &DatabaseConnection) -> i32 {
+    query_id(
+        db,
+        r#"INSERT INTO "users" ("login_type") VALUES ('Oauth') RETURNING "id""#,
+        [],
+    )
+    .await
+    .unwrap()
+}
+
+async fn create_species(db: &DatabaseConnection, user_id: i32, name: &str) -> i32 {
+    query_id(
+        db,
+        r#"INSERT INTO "species" ("name", "user_id") VALUES ($1, $2) RETURNING "id""#,
+        [name.into(), user_id.into()],
+    )
+    .await
+    .unwrap()
+}
+
+async fn create_pet(db: &DatabaseConnection, user_id: i32, species_id: i32) -> i32 {
+    query_id(
+        db,
+        r#"INSERT INTO "pets" ("user_id", "name", "sex", "birthday", "birthday_precision", "species_id")
+        VALUES ($1, 'Bun', 'Female', '2024-03-01', 'FullDate', $2) RETURNING "id""#,
+        [user_id.into(), species_id.into()],
+    )
+    .await
+    .unwrap()
+}
+
+async fn delete_user(db: &DatabaseConnection, user_id: i32) {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"DELETE FROM "users" WHERE "id" = $1"#,
+        [user_id.into()],
+    ))
+    .await
+    .unwrap();
+}
+
+async fn species_owner(db: &DatabaseConnection, species_id: i32) -> Option<i32> {
+    query_id(
+        db,
+        r#"SELECT "user_id" AS "id" FROM "species" WHERE "id" = $1"#,
+        [species_id.into()],
+    )
+    .await
+}
+
+async fn pet_species(db: &DatabaseConnection, pet_id: i32) -> Option<i32> {
+    query_id(
+        db,
+        r#"SELECT "species_id" AS "id" FROM "pets" WHERE "id" = $1"#,
+        [pet_id.into()],
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres, run with `cargo test -- --ignored`
+async fn test_user_with_custom_species_on_own_pet_can_be_deleted() {
+    let db = migrated_db().await;
+    let user = create_user(&db).await;
+    let rabbit = create_species(&db, user, "Rabbit").await;
+    let pet = create_pet(&db, user, rabbit).await;
+
+    delete_user(&db, user).await;
+
+    assert_eq!(species_owner(&db, rabbit).await, None);
+    assert_eq!(pet_species(&db, pet).await, None);
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres, run with `cargo test -- --ignored`
+async fn test_custom_species_in_use_is_copied_to_each_pets_owner() {
+    let db = migrated_db().await;
+    let leaving = create_user(&db).await;
+    let first = create_user(&db).await;
+    let second = create_user(&db).await;
+    let rabbit = create_species(&db, leaving, "Rabbit").await;
+    let unused = create_species(&db, leaving, "Axolotl").await;
+    let first_pet = create_pet(&db, first, rabbit).await;
+    let second_pets = [
+        create_pet(&db, second, rabbit).await,
+        create_pet(&db, second, rabbit).await,
+    ];
+
+    delete_user(&db, leaving).await;
+
+    assert_eq!(species_owner(&db, rabbit).await, None);
+    assert_eq!(species_owner(&db, unused).await, None);
+    let first_rabbit = pet_species(&db, first_pet).await.unwrap();
+    assert_eq!(species_owner(&db, first_rabbit).await, Some(first));
+    let second_rabbit = pet_species(&db, second_pets[0]).await.unwrap();
+    assert_eq!(species_owner(&db, second_rabbit).await, Some(second));
+    assert_eq!(pet_species(&db, second_pets[1]).await, Some(second_rabbit));
+
+    delete_user(&db, first).await;
+    delete_user(&db, second).await;
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres, run with `cargo test -- --ignored`
+async fn test_pets_owner_keeps_own_species_of_the_same_name() {
+    let db = migrated_db().await;
+    let leaving = create_user(&db).await;
+    let staying = create_user(&db).await;
+    let theirs = create_species(&db, leaving, "Rabbit").await;
+    let own = create_species(&db, staying, "Rabbit").await;
+    let pet = create_pet(&db, staying, theirs).await;
+
+    delete_user(&db, leaving).await;
+
+    assert_eq!(species_owner(&db, theirs).await, None);
+    assert_eq!(pet_species(&db, pet).await, Some(own));
+
+    delete_user(&db, staying).await;
+}
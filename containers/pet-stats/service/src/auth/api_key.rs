@@ -0,0 +1,167 @@
+use std::{fmt, str::FromStr};
+
+use config::auth_config::AuthConfig;
+use entity::entities::api_keys;
+use sea_orm::DbConn;
+use tracing::{info, instrument};
+
+use crate::mutations::api_key::ApiKeyMutation;
+
+use super::{
+    error::AuthError,
+    refresh_token::{generate_opaque_token, hmac_sha256},
+};
+
+/// Marks a bearer token as an API key rather than a JWT.
+pub const API_KEY_PREFIX: &str = "psk_";
+
+/// Characters of the key kept in clear text so users can tell their keys apart.
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// What an API key may be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiScope {
+    PetsRead,
+    PetsWrite,
+    FeedRead,
+    FeedWrite,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PetsRead => "pets:read",
+            Self::PetsWrite => "pets:write",
+            Self::FeedRead => "feed:read",
+            Self::FeedWrite => "feed:write",
+        }
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = AuthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pets:read" => Ok(Self::PetsRead),
+            "pets:write" => Ok(Self::PetsWrite),
+            "feed:read" => Ok(Self::FeedRead),
+            "feed:write" => Ok(Self::FeedWrite),
+            _ => Err(AuthError::InvalidToken),
+        }
+    }
+}
+
+/// Personal API key, e.g. `psk_3q2-…`. Stored hashed like refresh tokens.
+#[derive(Debug, Clone)]
+pub struct ApiKey(pub String);
+
+impl ApiKey {
+    pub fn generate() -> Result<Self, AuthError> {
+        Ok(Self(format!(
+            "{}{}",
+            API_KEY_PREFIX,
+            generate_opaque_token()?
+        )))
+    }
+
+    pub fn is_api_key(token: &str) -> bool {
+        token.starts_with(API_KEY_PREFIX)
+    }
+
+    pub fn hash(&self, secret: &[u8]) -> [u8; 32] {
+        hmac_sha256(secret, self.0.as_bytes())
+    }
+
+    pub fn display_prefix(&self) -> String {
+        self.0.chars().take(DISPLAY_PREFIX_LEN).collect()
+    }
+}
+
+/// Key that passed authentication, with the scopes it was granted.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedApiKey {
+    pub key_id: i32,
+    pub user_id: i32,
+    pub scopes: Vec<ApiScope>,
+}
+
+impl AuthenticatedApiKey {
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+pub struct ApiKeyService;
+
+impl ApiKeyService {
+    /// Create a key for the user. The plain key is only returned here, the database keeps its hash.
+    #[instrument(skip(db, auth_config), fields())]
+    pub async fn create(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        name: String,
+        scopes: &[ApiScope],
+    ) -> Result<(ApiKey, api_keys::Model), AuthError> {
+        let key = ApiKey::generate()?;
+        let hash = key.hash(auth_config.refresh_key_hashing_secret.as_bytes());
+        let model =
+            ApiKeyMutation::create_api_key(db, user_id, name, key.display_prefix(), &hash, scopes)
+                .await?;
+        Ok((key, model))
+    }
+
+    /// Resolve a presented key. Unknown and revoked keys, and keys of an account pending
+    /// deletion or disabled, are `InvalidToken`.
+    #[instrument(skip(db, auth_config, key), fields())]
+    pub async fn authenticate(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        key: &ApiKey,
+    ) -> Result<AuthenticatedApiKey, AuthError> {
+        let hash = key.hash(auth_config.refresh_key_hashing_secret.as_bytes());
+        let model = ApiKeyMutation::use_api_key(db, &hash)
+            .await?
+            .ok_or(AuthError::InvalidToken)?;
+        info!("API key {} used by user_id: {}", model.id, model.user_id);
+
+        Ok(AuthenticatedApiKey {
+            key_id: model.id,
+            user_id: model.user_id,
+            scopes: model.scopes.iter().filter_map(|s| s.parse().ok()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_is_recognised() {
+        let key = ApiKey::generate().unwrap();
+        assert!(ApiKey::is_api_key(&key.0));
+        assert!(key.display_prefix().starts_with(API_KEY_PREFIX));
+        assert!(!ApiKey::is_api_key("eyJhbGciOiJIUzI1NiJ9.e30.sig"));
+    }
+
+    #[test]
+    fn test_scope_round_trip() {
+        for scope in [
+            ApiScope::PetsRead,
+            ApiScope::PetsWrite,
+            ApiScope::FeedRead,
+            ApiScope::FeedWrite,
+        ] {
+            assert_eq!(scope.as_str().parse::<ApiScope>().unwrap(), scope);
+        }
+        assert!("admin".parse::<ApiScope>().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use config::auth_config::AuthConfig;
+use entity::entities::devices;
+use sea_orm::DbConn;
+use tracing::{info, instrument};
+
+use crate::mutations::device::DeviceMutation;
+
+use super::{
+    error::AuthError,
+    refresh_token::{generate_opaque_token, hmac_sha256},
+};
+
+/// Marks a device uid, e.g. in ingest URLs.
+pub const DEVICE_UID_PREFIX: &str = "dev_";
+
+/// Prefix of the `X-Signature` header value.
+pub const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Secret a device signs its requests with. Derived from the uid with the server's hashing
+/// secret like refresh token hashes, so it is never stored and can be checked before any
+/// database lookup.
+pub fn signing_secret(hashing_secret: &[u8], uid: &str) -> String {
+    use base64::{engine::general_purpose, Engine};
+
+    let key = hmac_sha256(hashing_secret, format!("device:{uid}").as_bytes());
+    general_purpose::URL_SAFE_NO_PAD.encode(key)
+}
+
+/// `sha256=` and the hex HMAC-SHA256 of `body` keyed with the device's signing secret.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mac = hmac_sha256(secret.as_bytes(), body);
+    format!(
+        "{}{}",
+        SIGNATURE_PREFIX,
+        data_encoding::HEXLOWER.encode(&mac)
+    )
+}
+
+/// Whether `signature` is the signature of `body`, compared in constant time.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Some(mac) = signature.strip_prefix(SIGNATURE_PREFIX).and_then(|hex| {
+        data_encoding::HEXLOWER_PERMISSIVE
+            .decode(hex.as_bytes())
+            .ok()
+    }) else {
+        return false;
+    };
+    let mut expected = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    expected.update(body);
+    expected.verify_slice(&mac).is_ok()
+}
+
+pub struct DeviceService;
+
+impl DeviceService {
+    /// Register a device posting events for the pet. The signing secret is only returned
+    /// here.
+    #[instrument(skip(db, auth_config), fields())]
+    pub async fn register(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        pet_id: i32,
+        name: String,
+    ) -> Result<(String, devices::Model), AuthError> {
+        let uid = format!("{}{}", DEVICE_UID_PREFIX, generate_opaque_token()?);
+        let secret = signing_secret(auth_config.refresh_key_hashing_secret.as_bytes(), &uid);
+        let device = DeviceMutation::add_device(db, user_id, pet_id, uid, name).await?;
+        info!("Device {} registered for pet_id: {}", device.id, pet_id);
+        Ok((secret, device))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip() {
+        let secret = signing_secret(b"hashing-secret", "dev_abc");
+        let body = br#"{"events":[]}"#;
+        let signature = sign(&secret, body);
+
+        assert!(signature.starts_with(SIGNATURE_PREFIX));
+        assert!(verify_signature(&secret, body, &signature));
+        assert!(verify_signature(
+            &secret,
+            body,
+            &signature.to_uppercase().replace("SHA256=", "sha256=")
+        ));
+        assert!(!verify_signature(
+            &secret,
+            br#"{"events":[{}]}"#,
+            &signature
+        ));
+        assert!(!verify_signature(
+            &secret,
+            body,
+            &signature[SIGNATURE_PREFIX.len()..]
+        ));
+        assert!(!verify_signature(&secret, body, "sha256=zz"));
+    }
+
+    #[test]
+    fn test_secret_depends_on_uid_and_server_secret() {
+        let secret = signing_secret(b"hashing-secret", "dev_abc");
+        assert_eq!(secret, signing_secret(b"hashing-secret", "dev_abc"));
+        assert_ne!(secret, signing_secret(b"hashing-secret", "dev_abd"));
+        assert_ne!(secret, signing_secret(b"other-secret", "dev_abc"));
+    }
+}
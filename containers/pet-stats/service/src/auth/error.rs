@@ -1,4 +1,6 @@
+use jwt::JwtAuthError;
 use rand::rand_core;
+use sea_orm::DbErr;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,6 +11,22 @@ pub enum AuthError {
     NetworkError(String),
     #[error("Token expired")]
     TokenExpired,
+    #[error("Recent sign-in required")]
+    StaleAuthentication,
+    #[error("Invalid email or password")]
+    InvalidCredentials,
+    #[error("Invalid two-factor code")]
+    InvalidTwoFactorCode,
+    #[error("Two-factor authentication is not enabled")]
+    TwoFactorNotEnabled,
+    #[error("Two-factor authentication is already enabled")]
+    TwoFactorAlreadyEnabled,
+    #[error("Only available for email and password accounts")]
+    NotLocalAccount,
+    #[error("Account is disabled")]
+    AccountDisabled,
+    #[error("Password hashing error: {0}")]
+    PasswordHash(String),
     #[error("Initilizing error")]
     InitilizingError,
 
@@ -19,6 +37,11 @@ pub enum AuthError {
 
     #[error("OS-CSPRNG failure: {0}")]
     Rng(#[from] rand_core::OsError),
+
+    #[error("Database error: {0}")]
+    Db(#[from] DbErr),
+    #[error("JWT error: {0}")]
+    Jwt(#[from] JwtAuthError),
 }
 
 #[derive(Error, Debug)]
@@ -1,17 +1,12 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use config::{auth_config::AuthConfig, base_config::Config};
-use jsonwebtoken::{
-    decode, decode_header,
-    jwk::{Jwk, JwkSet},
-    DecodingKey, Validation,
-};
-use rest::client::{HttpClient, HttpClientBuilder};
+use jsonwebtoken::{decode, decode_header, errors::ErrorKind, jwk::Jwk, DecodingKey, Validation};
+use rest::client::HttpClientBuilder;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
-use super::{error::AuthError, oauth_provider::OAuthProvider};
+use super::{error::AuthError, jwks_cache::JwksCache, oauth_provider::OAuthProvider};
 
 static GOOGLE_ISSUERS: &[&str] = &["https://accounts.google.com", "accounts.google.com"];
 
@@ -27,24 +22,51 @@ pub struct GoogleClaims {
     pub name: Option<String>,
 }
 
+/// Google ID token verifier.
+///
+/// Cloning is cheap and clones share the same JWKS cache, so build it once and
+/// hand it out to every request.
+#[derive(Clone)]
 pub struct GoogleOAuth {
     client_id: String,
-    http_client: HttpClient,
+    jwks: Arc<JwksCache>,
 }
 
 impl GoogleOAuth {
-    pub fn new(client_id: String) -> Result<Self, AuthError> {
+    pub fn new(client_id: String, public_key_url: String) -> Result<Self, AuthError> {
         let http_client = HttpClientBuilder::new()
             .timeout(Duration::from_secs(30))
             .build()
             .map_err(|_| AuthError::InitilizingError)?;
         Ok(Self {
             client_id,
-            http_client,
+            jwks: Arc::new(JwksCache::new(public_key_url, http_client)),
         })
     }
 }
 
+/// Read `sub` from an ID token without checking its signature.
+///
+/// Only meant for keying rate limits before the token is verified; never trust the result
+/// for authentication.
+pub fn unverified_subject(id_token: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Subject {
+        sub: String,
+    }
+
+    let header = decode_header(id_token).ok()?;
+    let mut validation = Validation::new(header.alg);
+    validation.insecure_disable_signature_validation();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    validation.required_spec_claims.clear();
+
+    decode::<Subject>(id_token, &DecodingKey::from_secret(&[]), &validation)
+        .ok()
+        .map(|data| data.claims.sub)
+}
+
 #[async_trait]
 impl OAuthProvider for GoogleOAuth {
     type Claims = GoogleClaims;
@@ -66,25 +88,19 @@ impl OAuthProvider for GoogleOAuth {
             .inspect(|x| info!("Inspect !{:?}", x.header.alg))
             .map_err(|e| {
                 error!("Token Error! {:?}", e.to_string());
-                AuthError::NetworkError(e.to_string())
+                match e.kind() {
+                    ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                    _ => AuthError::InvalidToken,
+                }
             })?;
         Ok(token_data.claims)
     }
 
     async fn fetch_public_key(&self, kid: &str) -> Result<Jwk, super::error::AuthError> {
-        let auth_config = AuthConfig::new()?;
-
-        let res = self
-            .http_client
-            .get(auth_config.google_oauth_public_key_url)
-            .await
-            .map_err(|e| AuthError::NetworkError(e.to_string()))?;
-
-        let jwk_set: JwkSet = res.json().await.map_err(|_| AuthError::InvalidToken)?;
-        let jwk: &Jwk = jwk_set.find(kid).ok_or(AuthError::InvalidToken)?;
+        let jwk = self.jwks.get(kid).await?;
 
         debug!("jwk algorithm: {:?}", jwk.algorithm);
 
-        Ok(jwk.to_owned())
+        Ok(jwk)
     }
 }
@@ -0,0 +1,175 @@
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use reqwest::header::CACHE_CONTROL;
+use rest::client::HttpClient;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, instrument, warn};
+
+use super::error::AuthError;
+
+/// TTL applied when the JWKS response has no usable `Cache-Control: max-age`.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Minimum time between two downloads triggered by an unknown `kid`.
+/// Keeps tokens with random key ids from turning into a download per request.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+struct CachedJwks {
+    jwk_set: JwkSet,
+    fetched_at: Instant,
+    expires_at: Instant,
+}
+
+enum Lookup {
+    Hit(Box<Jwk>),
+    UnknownKid { fetched_at: Instant },
+    Miss,
+}
+
+/// Shared JWKS cache for an OAuth provider.
+///
+/// Keys are kept until the `max-age` announced by the provider runs out, and are
+/// re-downloaded early when a token references a `kid` that is not in the cached set.
+/// Downloads are single-flight: concurrent callers wait for the one in-progress fetch
+/// instead of issuing their own, and use its result even when it is already stale.
+pub struct JwksCache {
+    url: String,
+    http_client: HttpClient,
+    entry: RwLock<Option<CachedJwks>>,
+    fetch_lock: Mutex<()>,
+}
+
+impl JwksCache {
+    pub fn new(url: String, http_client: HttpClient) -> Self {
+        Self {
+            url,
+            http_client,
+            entry: RwLock::new(None),
+            fetch_lock: Mutex::new(()),
+        }
+    }
+
+    /// Find the JWK for `kid`, downloading the key set when needed.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::NetworkError` when the key set can't be downloaded.
+    /// - `AuthError::InvalidToken` when `kid` is unknown even after a refresh.
+    #[instrument(skip(self))]
+    pub async fn get(&self, kid: &str) -> Result<Jwk, AuthError> {
+        if let Lookup::Hit(jwk) = self.lookup(kid, None).await {
+            return Ok(*jwk);
+        }
+
+        let waiting_since = Instant::now();
+        let _fetching = self.fetch_lock.lock().await;
+
+        // Another caller may have refreshed the set while we were waiting. That set counts as
+        // fresh for us even with `max-age=0`, otherwise every waiter would download it again.
+        match self.lookup(kid, Some(waiting_since)).await {
+            Lookup::Hit(jwk) => return Ok(*jwk),
+            Lookup::UnknownKid { fetched_at } if fetched_at.elapsed() < MIN_REFRESH_INTERVAL => {
+                warn!("Unknown kid on a freshly fetched key set: {:?}", kid);
+                return Err(AuthError::InvalidToken);
+            }
+            Lookup::UnknownKid { .. } | Lookup::Miss => {}
+        }
+
+        let cached = self.fetch().await?;
+        let jwk = cached.jwk_set.find(kid).cloned();
+        *self.entry.write().await = Some(cached);
+
+        jwk.ok_or(AuthError::InvalidToken)
+    }
+
+    /// Look `kid` up in the cached set if it is still fresh, or was fetched after
+    /// `fetched_since`.
+    async fn lookup(&self, kid: &str, fetched_since: Option<Instant>) -> Lookup {
+        let entry = self.entry.read().await;
+        let usable = |cached: &CachedJwks| {
+            cached.expires_at > Instant::now()
+                || fetched_since.is_some_and(|since| cached.fetched_at >= since)
+        };
+        match entry.as_ref() {
+            Some(cached) if usable(cached) => match cached.jwk_set.find(kid) {
+                Some(jwk) => Lookup::Hit(Box::new(jwk.to_owned())),
+                None => Lookup::UnknownKid {
+                    fetched_at: cached.fetched_at,
+                },
+            },
+            _ => Lookup::Miss,
+        }
+    }
+
+    async fn fetch(&self) -> Result<CachedJwks, AuthError> {
+        info!("Downloading JWKS from {}", self.url);
+        let res = self
+            .http_client
+            .get(self.url.to_owned())
+            .await
+            .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+        let max_age = res
+            .headers()
+            .get(CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_max_age)
+            .unwrap_or(DEFAULT_MAX_AGE);
+        debug!("JWKS max-age: {:?}", max_age);
+
+        let jwk_set: JwkSet = res
+            .json()
+            .await
+            .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+        let fetched_at = Instant::now();
+        Ok(CachedJwks {
+            jwk_set,
+            fetched_at,
+            expires_at: fetched_at + max_age,
+        })
+    }
+}
+
+/// Read the freshness lifetime from a `Cache-Control` header value.
+/// `no-cache` and `no-store` are treated as a zero lifetime.
+fn parse_max_age(cache_control: &str) -> Option<Duration> {
+    let mut max_age = None;
+    for directive in cache_control.split(',').map(str::trim) {
+        let directive = directive.to_ascii_lowercase();
+        if directive == "no-cache" || directive == "no-store" {
+            return Some(Duration::ZERO);
+        }
+        if let Some(secs) = directive.strip_prefix("max-age=") {
+            max_age = secs
+                .trim_matches('"')
+                .parse::<u64>()
+                .ok()
+                .map(Duration::from_secs);
+        }
+    }
+    max_age
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_age_from_google_header() {
+        let header = "public, max-age=21595, must-revalidate, no-transform";
+        assert_eq!(parse_max_age(header), Some(Duration::from_secs(21595)));
+    }
+
+    #[test]
+    fn test_parse_max_age_without_directive() {
+        assert_eq!(parse_max_age("public, must-revalidate"), None);
+        assert_eq!(parse_max_age("max-age=abc"), None);
+    }
+
+    #[test]
+    fn test_parse_max_age_no_store_wins() {
+        assert_eq!(parse_max_age("max-age=600, no-store"), Some(Duration::ZERO));
+    }
+}
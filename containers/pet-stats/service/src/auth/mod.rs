@@ -1,5 +1,14 @@
+pub mod api_key;
+pub mod device;
 pub mod error;
 pub mod google;
+pub mod jwks_cache;
 pub mod model;
 pub mod oauth_provider;
+pub mod password;
 pub mod refresh_token;
+pub mod rejected_tokens;
+pub mod sign_in;
+pub mod token_revocation;
+pub mod totp;
+pub mod two_factor;
@@ -0,0 +1,62 @@
+use std::sync::LazyLock;
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use rand::TryRngCore;
+
+use super::error::AuthError;
+
+/// Verified instead of a real hash when the account does not exist, so unknown emails take
+/// as long to reject as wrong passwords.
+static DUMMY_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password("dummy-password").expect("hash dummy password"));
+
+/// Hash a password into an Argon2id PHC string for `users.password_hash`.
+pub fn hash_password(password: &str) -> Result<String, AuthError> {
+    use rand::rngs::OsRng;
+
+    let mut salt = [0u8; 16];
+    OsRng.try_fill_bytes(&mut salt)?;
+    let salt = SaltString::encode_b64(&salt).map_err(|e| AuthError::PasswordHash(e.to_string()))?;
+
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AuthError::PasswordHash(e.to_string()))
+}
+
+/// Check `password` against a stored PHC string. A missing hash never matches.
+pub fn verify_password(password: &str, password_hash: Option<&str>) -> bool {
+    let matches = |hash: &str| {
+        PasswordHash::new(hash)
+            .map(|parsed| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &parsed)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    };
+
+    match password_hash {
+        Some(hash) => matches(hash),
+        None => {
+            matches(&DUMMY_HASH);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_password_round_trip() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(verify_password("correct horse", Some(&hash)));
+        assert!(!verify_password("battery staple", Some(&hash)));
+        assert!(!verify_password("correct horse", None));
+    }
+}
@@ -7,22 +7,32 @@ pub struct RefreshToken(pub String);
 
 impl RefreshToken {
     pub fn generate() -> Result<Self, AuthError> {
-        use base64::{engine::general_purpose, Engine};
-        use rand::rngs::OsRng;
-
-        let mut buf = [0u8; 32];
-        OsRng.try_fill_bytes(&mut buf)?;
-
-        Ok(Self(general_purpose::URL_SAFE_NO_PAD.encode(buf)))
+        Ok(Self(generate_opaque_token()?))
     }
 
     pub fn hash(&self, secret: &[u8]) -> [u8; 32] {
-        use hmac::{Hmac, Mac};
-        use sha2::Sha256;
-        type HmacSha256 = Hmac<Sha256>;
-
-        let mut mac = HmacSha256::new_from_slice(secret).unwrap();
-        mac.update(self.0.as_bytes());
-        mac.finalize().into_bytes().into()
+        hmac_sha256(secret, self.0.as_bytes())
     }
 }
+
+/// 32 random bytes from the OS CSPRNG, URL-safe base64 encoded.
+pub(crate) fn generate_opaque_token() -> Result<String, AuthError> {
+    use base64::{engine::general_purpose, Engine};
+    use rand::rngs::OsRng;
+
+    let mut buf = [0u8; 32];
+    OsRng.try_fill_bytes(&mut buf)?;
+
+    Ok(general_purpose::URL_SAFE_NO_PAD.encode(buf))
+}
+
+/// Keyed hash used for every server-side token lookup, so a leaked table can't be replayed.
+pub(crate) fn hmac_sha256(secret: &[u8], data: &[u8]) -> [u8; 32] {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac = HmacSha256::new_from_slice(secret).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
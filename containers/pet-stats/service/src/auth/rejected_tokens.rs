@@ -0,0 +1,128 @@
+use std::{
+    collections::HashMap,
+    mem,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use entity::entities::sea_orm_active_enums::SecurityEventType;
+use sea_orm::{DbConn, DbErr};
+use tracing::{instrument, warn};
+
+use crate::mutations::security_event::{EventOrigin, SecurityEventMutation};
+
+/// A client keeps sending a bad token until it notices; it is recorded once per window.
+const REPORT_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Clients remembered within a window. Past this, new rejections are only logged.
+const MAX_TRACKED: usize = 10_000;
+
+/// Client IP and, for revoked tokens, the account a rejection is reported for.
+type Reporter = (Option<String>, Option<i32>);
+
+/// Access tokens that failed verification, waiting to be written to the audit log.
+///
+/// Verification runs synchronously in the resolvers, so rejections are queued here and
+/// `flush` writes them from a background task. A rejection is reported at most once per
+/// window for the same client and account, so a client replaying a bad token can't flood
+/// the log.
+#[derive(Debug, Default)]
+pub struct RejectedAccessTokens {
+    reported: Mutex<HashMap<Reporter, Instant>>,
+    pending: Mutex<Vec<(Option<i32>, EventOrigin)>>,
+}
+
+impl RejectedAccessTokens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a rejection. `user_id` is only known for tokens that verified but were revoked.
+    pub fn report(&self, user_id: Option<i32>, origin: EventOrigin) {
+        self.report_at(user_id, origin, Instant::now());
+    }
+
+    fn report_at(&self, user_id: Option<i32>, origin: EventOrigin, now: Instant) {
+        let mut reported = self.reported.lock().unwrap_or_else(|e| e.into_inner());
+        let key: Reporter = (origin.ip_address.clone(), user_id);
+
+        if let Some(at) = reported.get(&key) {
+            if now.saturating_duration_since(*at) < REPORT_WINDOW {
+                return;
+            }
+        } else if reported.len() >= MAX_TRACKED {
+            warn!("Too many rejected access tokens to record, from {:?}", key);
+            return;
+        }
+        reported.insert(key, now);
+        drop(reported);
+
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((user_id, origin));
+    }
+
+    /// Write the queued rejections and forget clients whose window has passed. Returns how
+    /// many were written; the rest are dropped on error.
+    #[instrument(skip(self, db))]
+    pub async fn flush(&self, db: &DbConn) -> Result<usize, DbErr> {
+        self.forget_before(Instant::now());
+        let pending = mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+
+        let mut written = 0;
+        for (user_id, origin) in pending {
+            SecurityEventMutation::record(
+                db,
+                user_id,
+                SecurityEventType::AccessTokenRejected,
+                &origin,
+            )
+            .await?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    fn forget_before(&self, now: Instant) {
+        self.reported
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, at| now.saturating_duration_since(*at) < REPORT_WINDOW);
+    }
+
+    #[cfg(test)]
+    fn pending_len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from(ip: &str) -> EventOrigin {
+        EventOrigin {
+            ip_address: Some(ip.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_repeated_rejections_are_reported_once_per_window() {
+        let rejections = RejectedAccessTokens::new();
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            rejections.report_at(None, from("10.0.0.1"), now);
+        }
+        rejections.report_at(Some(3), from("10.0.0.1"), now);
+        rejections.report_at(None, from("10.0.0.2"), now);
+        assert_eq!(rejections.pending_len(), 3);
+
+        let later = now + REPORT_WINDOW;
+        rejections.forget_before(later);
+        rejections.report_at(None, from("10.0.0.1"), later);
+        assert_eq!(rejections.pending_len(), 4);
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{DateTime, FixedOffset, Local, TimeDelta, Utc};
+use config::auth_config::AuthConfig;
+use entity::entities::{sea_orm_active_enums::ProviderType, users};
+use jwt::{create_jwt, DEFAULT_EXP};
+use sea_orm::{DbConn, DbErr};
+use tracing::{error, info, instrument, warn};
+
+use crate::{
+    mutations::{two_factor::TwoFactorMutation, user::UserMutation},
+    queries::{two_factor::TwoFactorQuery, user::UserQuery},
+};
+
+use super::{
+    error::AuthError,
+    google::GoogleOAuth,
+    oauth_provider::OAuthProvider,
+    password::verify_password,
+    refresh_token::RefreshToken,
+    two_factor::{ChallengeToken, TwoFactorService},
+};
+
+/// How old an ID token may be to count as a re-authentication for sensitive operations.
+const REAUTH_MAX_AGE: TimeDelta = TimeDelta::minutes(5);
+
+/// How long the second sign-in step may take.
+const CHALLENGE_TTL: TimeDelta = TimeDelta::minutes(5);
+
+/// Wrong codes accepted per challenge before the password has to be entered again.
+pub const CHALLENGE_MAX_ATTEMPTS: i32 = 5;
+
+/// Tokens handed out after a successful sign-in.
+#[derive(Debug)]
+pub struct SignInTokens {
+    pub user: users::Model,
+    pub access_token: String,
+    pub refresh_token: RefreshToken,
+}
+
+/// Handed out instead of tokens when the account requires a second factor.
+#[derive(Debug)]
+pub struct TwoFactorChallenge {
+    pub token: ChallengeToken,
+    pub expires_at: DateTime<FixedOffset>,
+}
+
+/// Outcome of the password step of a local sign-in.
+#[derive(Debug)]
+pub enum LocalSignIn {
+    Completed(SignInTokens),
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+pub struct SignInService;
+
+impl SignInService {
+    /// Sign in with a Google ID token.
+    ///
+    /// 1. Verifying the ID token against Google JWKS.
+    /// 2. Finding the user by provider subject, creating one on first sign-in.
+    /// 3. Generate access token and refresh token.
+    /// 4. Storing hashed refresh token to datastore.
+    ///
+    /// # Errors
+    ///
+    /// - Invalid, expired or foreign ID token.
+    /// - DB connection error.
+    /// - JWT creation error.
+    #[instrument(skip(db, google_oauth, auth_config, id_token))]
+    pub async fn sign_in_with_google(
+        db: &DbConn,
+        google_oauth: &GoogleOAuth,
+        auth_config: &AuthConfig,
+        id_token: &str,
+    ) -> Result<SignInTokens, AuthError> {
+        info!("Verifying OAuth token");
+        let claim = google_oauth.verify_token(id_token).await?;
+        info!(
+            "OAuth token verified successfully for user_id: {:?}",
+            claim.sub
+        );
+
+        let user = match UserQuery::user_by_provider_user_id(db, claim.sub.clone()).await {
+            Ok(user) => user,
+            Err(DbErr::RecordNotFound(e)) => {
+                info!("Record Not Found {:?}", e);
+                UserMutation::create_oauth_user(db, claim.email, ProviderType::Google, claim.sub)
+                    .await?
+            }
+            Err(err) => {
+                error!("{:?}", err.to_string());
+                return Err(err.into());
+            }
+        };
+
+        let tokens = Self::issue_tokens(db, auth_config, user).await?;
+        info!(
+            "OAuth sign-in completed successfully for user_id: {}",
+            tokens.user.id
+        );
+        Ok(tokens)
+    }
+
+    /// First step of an email and password sign-in.
+    ///
+    /// Accounts without two-factor authentication get their tokens right away. Accounts with
+    /// TOTP enabled get a short-lived challenge token instead, which has to be completed with
+    /// [`SignInService::complete_two_factor`].
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::InvalidCredentials` for an unknown email or a wrong password.
+    /// - DB connection error.
+    #[instrument(skip(db, auth_config, email, password))]
+    pub async fn sign_in_local(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        email: &str,
+        password: &str,
+    ) -> Result<LocalSignIn, AuthError> {
+        let user = UserQuery::local_user_by_email(db, email).await?;
+        let password_hash = user.as_ref().and_then(|u| u.password_hash.as_deref());
+        if !verify_password(password, password_hash) {
+            warn!("Local sign-in rejected");
+            return Err(AuthError::InvalidCredentials);
+        }
+        let user = user.ok_or(AuthError::InvalidCredentials)?;
+
+        if !TwoFactorQuery::totp_enabled(db, user.id).await? {
+            let tokens = Self::issue_tokens(db, auth_config, user).await?;
+            return Ok(LocalSignIn::Completed(tokens));
+        }
+
+        info!("Two-factor challenge issued for user_id: {}", user.id);
+        let token = ChallengeToken::generate()?;
+        let expires_at = Local::now().fixed_offset() + CHALLENGE_TTL;
+        TwoFactorMutation::create_challenge(
+            db,
+            user.id,
+            &token.hash(auth_config.refresh_key_hashing_secret.as_bytes()),
+            expires_at,
+        )
+        .await?;
+
+        Ok(LocalSignIn::TwoFactorRequired(TwoFactorChallenge {
+            token,
+            expires_at,
+        }))
+    }
+
+    /// Second step of an email and password sign-in: a TOTP code or a recovery code.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::InvalidToken` when the challenge is unknown, expired or out of attempts.
+    /// - `AuthError::InvalidTwoFactorCode` for a wrong code.
+    #[instrument(skip(db, auth_config, challenge_token, code))]
+    pub async fn complete_two_factor(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        challenge_token: &str,
+        code: &str,
+    ) -> Result<SignInTokens, AuthError> {
+        let hash = ChallengeToken(challenge_token.to_owned())
+            .hash(auth_config.refresh_key_hashing_secret.as_bytes());
+        let Some(challenge) =
+            TwoFactorMutation::attempt_challenge(db, &hash, CHALLENGE_MAX_ATTEMPTS).await?
+        else {
+            warn!("Unknown, expired or exhausted two-factor challenge");
+            return Err(AuthError::InvalidToken);
+        };
+
+        TwoFactorService::verify_second_factor(db, auth_config, challenge.user_id, code).await?;
+        TwoFactorMutation::delete_challenge(db, challenge.id).await?;
+
+        let user = UserQuery::user_by_id(db, challenge.user_id).await?;
+        Self::issue_tokens(db, auth_config, user).await
+    }
+
+    /// Issue an access token and a stored refresh token for an authenticated user.
+    /// A pending account deletion is cancelled, signing in during the grace period keeps the
+    /// account. Disabled accounts get no tokens.
+    async fn issue_tokens(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user: users::Model,
+    ) -> Result<SignInTokens, AuthError> {
+        if user.disabled_at.is_some() {
+            warn!("Sign-in to disabled user_id: {} refused", user.id);
+            return Err(AuthError::AccountDisabled);
+        }
+
+        if user.deletion_scheduled_at.is_some() {
+            info!("Signed in during deletion grace period, keeping the account");
+            UserMutation::cancel_account_deletion(db, user.id).await?;
+        }
+
+        info!("Generating JWT for user_id: {:?}", user.id);
+        let access_token = create_jwt(
+            user.id,
+            user.email.to_owned(),
+            auth_config.jwt_sign_secret.to_owned(),
+            DEFAULT_EXP,
+        )?;
+
+        info!("Generating refresh token for user_id: {}", user.id);
+        let refresh_token = RefreshToken::generate()?;
+        let token_hash = refresh_token.hash(auth_config.refresh_key_hashing_secret.as_bytes());
+
+        info!("Storing refresh token for user_id: {}", user.id);
+        UserMutation::store_refresh_token(db, user.id, &token_hash).await?;
+
+        Ok(SignInTokens {
+            user,
+            access_token,
+            refresh_token,
+        })
+    }
+
+    /// Confirm that the signed-in user just proved control of their Google account again.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::StaleAuthentication` when the ID token was issued too long ago.
+    /// - `AuthError::InvalidToken` when the ID token belongs to another account.
+    #[instrument(skip(db, google_oauth, id_token))]
+    pub async fn reauthenticate_with_google(
+        db: &DbConn,
+        google_oauth: &GoogleOAuth,
+        user_id: i32,
+        id_token: &str,
+    ) -> Result<(), AuthError> {
+        let claim = google_oauth.verify_token(id_token).await?;
+
+        if Utc::now().timestamp() - claim.iat > REAUTH_MAX_AGE.num_seconds() {
+            warn!("ID token issued too long ago for re-authentication");
+            return Err(AuthError::StaleAuthentication);
+        }
+
+        match UserQuery::user_by_provider_user_id(db, claim.sub).await {
+            Ok(user) if user.id == user_id => Ok(()),
+            Ok(_) | Err(DbErr::RecordNotFound(_)) => {
+                warn!("ID token does not belong to the signed-in user");
+                Err(AuthError::InvalidToken)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Confirm that the signed-in local user just entered their password again, and a second
+    /// factor when two-factor authentication is enabled.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::NotLocalAccount` when the account has no password.
+    /// - `AuthError::InvalidCredentials` for a wrong password.
+    /// - `AuthError::InvalidTwoFactorCode` for a missing or wrong second factor.
+    #[instrument(skip(db, auth_config, password, code))]
+    pub async fn reauthenticate_with_password(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        password: &str,
+        code: Option<&str>,
+    ) -> Result<(), AuthError> {
+        let user = UserQuery::user_by_id(db, user_id).await?;
+        let Some(password_hash) = user.password_hash.as_deref() else {
+            return Err(AuthError::NotLocalAccount);
+        };
+        if !verify_password(password, Some(password_hash)) {
+            warn!("Password re-authentication rejected");
+            return Err(AuthError::InvalidCredentials);
+        }
+
+        if TwoFactorQuery::totp_enabled(db, user_id).await? {
+            let Some(code) = code else {
+                warn!("Second factor missing for re-authentication");
+                return Err(AuthError::InvalidTwoFactorCode);
+            };
+            TwoFactorService::verify_second_factor(db, auth_config, user_id, code).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, RwLock},
+};
+
+use chrono::{DateTime, Duration, Local, Utc};
+use jwt::{Claims, DEFAULT_EXP};
+use sea_orm::{prelude::DateTimeWithTimeZone, DbConn, DbErr};
+use tracing::{info, instrument, warn};
+
+use crate::{
+    mutations::token_revocation::TokenRevocationMutation,
+    queries::token_revocation::TokenRevocationQuery,
+};
+
+/// Revocations made this long before the previous sync are fetched again, so rows committed
+/// late by another instance are not missed.
+const SYNC_OVERLAP: Duration = Duration::minutes(1);
+
+/// Access tokens revoked before their `exp`, keyed by `jti`, and per user cutoffs before
+/// which every access token of the user is revoked.
+///
+/// Checked on every verification, so lookups stay in memory. The database is the source of
+/// truth shared by all instances, `sync` pulls revocations made elsewhere. Entries are dropped
+/// once the token would have expired anyway.
+#[derive(Debug, Default)]
+pub struct TokenRevocationList {
+    revoked: RwLock<HashMap<String, i64>>,
+    revoked_before: RwLock<HashMap<i32, i64>>,
+    synced_at: Mutex<Option<DateTimeWithTimeZone>>,
+}
+
+impl TokenRevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_revoked(&self, claims: &Claims) -> bool {
+        let cut_off = self
+            .revoked_before
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&claims.sub)
+            .is_some_and(|cutoff| claims.iat.0 < *cutoff);
+        if cut_off {
+            return true;
+        }
+        if claims.jti.is_empty() {
+            return false;
+        }
+        let revoked = self.revoked.read().unwrap_or_else(|e| e.into_inner());
+        revoked
+            .get(&claims.jti)
+            .is_some_and(|exp| *exp > Utc::now().timestamp())
+    }
+
+    /// Reject the user's access tokens issued before `at`, on this instance right away.
+    /// The cutoff itself is stored with the account, see `UserMutation::disable_account`.
+    pub fn revoke_issued_before(&self, user_id: i32, at: DateTimeWithTimeZone) {
+        self.revoked_before
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(user_id)
+            .and_modify(|cutoff| *cutoff = (*cutoff).max(at.timestamp()))
+            .or_insert(at.timestamp());
+    }
+
+    /// Revoke the token until its own expiry, here and for every other instance.
+    #[instrument(skip(self, db, claims), fields(user_id = claims.sub))]
+    pub async fn revoke(&self, db: &DbConn, claims: &Claims) -> Result<(), DbErr> {
+        if claims.jti.is_empty() {
+            warn!("Access token has no jti, it stays valid until it expires");
+            return Ok(());
+        }
+        let expires_at = DateTime::<Utc>::from_timestamp(claims.exp.0, 0)
+            .ok_or_else(|| DbErr::Custom("Invalid token expiry".to_owned()))?
+            .fixed_offset();
+
+        TokenRevocationMutation::revoke(db, claims.jti.to_owned(), claims.sub, expires_at).await?;
+        self.insert(claims.jti.to_owned(), claims.exp.0);
+        Ok(())
+    }
+
+    /// Load revocations persisted since the last sync, everything on the first one, and drop
+    /// expired entries. Returns the number of entries held afterwards.
+    #[instrument(skip(self, db), fields())]
+    pub async fn sync(&self, db: &DbConn) -> Result<usize, DbErr> {
+        let started_at = Local::now().fixed_offset();
+        let since = self
+            .synced_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|at| at - SYNC_OVERLAP);
+
+        let rows = TokenRevocationQuery::active(db, since).await?;
+        let users = TokenRevocationQuery::active_cutoffs(db, since).await?;
+        let fetched = rows.len() + users.len();
+
+        let now = Utc::now().timestamp();
+        let mut revoked = self.revoked.write().unwrap_or_else(|e| e.into_inner());
+        revoked.retain(|_, exp| *exp > now);
+        for row in rows {
+            revoked.insert(row.jti, row.expires_at.timestamp());
+        }
+        let mut held = revoked.len();
+        drop(revoked);
+
+        let mut revoked_before = self
+            .revoked_before
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        revoked_before.retain(|_, cutoff| *cutoff + DEFAULT_EXP.num_seconds() > now);
+        for user in users {
+            if let Some(at) = user.tokens_revoked_before {
+                revoked_before.insert(user.id, at.timestamp());
+            }
+        }
+        held += revoked_before.len();
+        drop(revoked_before);
+
+        *self.synced_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(started_at);
+        if fetched > 0 {
+            info!("Synced {} access token revocations, {} held", fetched, held);
+        }
+        Ok(held)
+    }
+
+    fn insert(&self, jti: String, exp: i64) {
+        self.revoked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(jti, exp);
+    }
+}
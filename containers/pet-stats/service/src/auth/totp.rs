@@ -0,0 +1,127 @@
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::TryRngCore;
+use reqwest::Url;
+use sha1::Sha1;
+
+use super::error::AuthError;
+
+/// Time step of an authenticator code (RFC 6238 default, what authenticator apps expect).
+pub const TOTP_STEP_SECS: i64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+
+/// Steps accepted on either side of the current one, to absorb clock drift on the phone.
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+
+/// Shared secret of a TOTP authenticator (HMAC-SHA1, 6 digits, 30 seconds).
+#[derive(Debug, Clone)]
+pub struct TotpSecret(pub Vec<u8>);
+
+impl TotpSecret {
+    /// 160-bit secret, the key size RFC 4226 recommends for HMAC-SHA1.
+    pub fn generate() -> Result<Self, AuthError> {
+        use rand::rngs::OsRng;
+
+        let mut buf = vec![0u8; 20];
+        OsRng.try_fill_bytes(&mut buf)?;
+        Ok(Self(buf))
+    }
+
+    pub fn to_base32(&self) -> String {
+        BASE32_NOPAD.encode(&self.0)
+    }
+
+    /// Key URI understood by authenticator apps, usually rendered as a QR code.
+    pub fn otpauth_uri(&self, issuer: &str, account: &str) -> String {
+        let mut uri = Url::parse("otpauth://totp/").expect("static otpauth url");
+        uri.set_path(&format!("/{}:{}", issuer, account));
+        uri.query_pairs_mut()
+            .append_pair("secret", &self.to_base32())
+            .append_pair("issuer", issuer)
+            .append_pair("algorithm", "SHA1")
+            .append_pair("digits", &TOTP_DIGITS.to_string())
+            .append_pair("period", &TOTP_STEP_SECS.to_string());
+        uri.to_string()
+    }
+
+    pub fn code_at_step(&self, step: i64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.0).unwrap();
+        mac.update(&step.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Dynamic truncation, RFC 4226 section 5.3.
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        format!(
+            "{:0width$}",
+            binary % 10u32.pow(TOTP_DIGITS),
+            width = TOTP_DIGITS as usize
+        )
+    }
+
+    /// Check `code` against the steps around `unix_time`.
+    ///
+    /// Returns the matching time step, so the caller can refuse to accept it twice.
+    pub fn verify(&self, code: &str, unix_time: i64) -> Option<i64> {
+        let code = code.trim();
+        if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let current = unix_time / TOTP_STEP_SECS;
+        (current - ALLOWED_DRIFT_STEPS..=current + ALLOWED_DRIFT_STEPS)
+            .find(|step| constant_time_eq(self.code_at_step(*step).as_bytes(), code.as_bytes()))
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 appendix B secret for SHA1.
+    fn rfc_secret() -> TotpSecret {
+        TotpSecret(b"12345678901234567890".to_vec())
+    }
+
+    #[test]
+    fn test_code_matches_rfc_6238_vectors() {
+        let secret = rfc_secret();
+        // The RFC lists 8 digit codes, authenticator apps show the last 6.
+        assert_eq!(secret.code_at_step(59 / TOTP_STEP_SECS), "287082");
+        assert_eq!(secret.code_at_step(1111111109 / TOTP_STEP_SECS), "081804");
+        assert_eq!(secret.code_at_step(1234567890 / TOTP_STEP_SECS), "005924");
+    }
+
+    #[test]
+    fn test_verify_accepts_adjacent_step_only() {
+        let secret = rfc_secret();
+        let now = 1234567890;
+        let previous = secret.code_at_step(now / TOTP_STEP_SECS - 1);
+        assert_eq!(
+            secret.verify(&previous, now),
+            Some(now / TOTP_STEP_SECS - 1)
+        );
+
+        let stale = secret.code_at_step(now / TOTP_STEP_SECS - 2);
+        assert_eq!(secret.verify(&stale, now), None);
+        assert_eq!(secret.verify("12345", now), None);
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let secret = rfc_secret();
+        let uri = secret.otpauth_uri("Pet Stats", "me@example.com");
+        assert!(uri.starts_with("otpauth://totp/Pet%20Stats:me@example.com?"));
+        assert!(uri.contains("secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"));
+        assert!(uri.contains("issuer=Pet+Stats"));
+    }
+}
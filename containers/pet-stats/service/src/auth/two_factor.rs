@@ -0,0 +1,202 @@
+use chrono::Utc;
+use config::auth_config::AuthConfig;
+use data_encoding::BASE32_NOPAD;
+use entity::entities::sea_orm_active_enums::LoginType;
+use rand::TryRngCore;
+use sea_orm::DbConn;
+use tracing::{info, instrument, warn};
+
+use crate::{
+    mutations::two_factor::TwoFactorMutation,
+    queries::{two_factor::TwoFactorQuery, user::UserQuery},
+};
+
+use super::{
+    error::AuthError,
+    refresh_token::{generate_opaque_token, hmac_sha256},
+    totp::TotpSecret,
+};
+
+/// Shown as the account issuer in authenticator apps.
+const TOTP_ISSUER: &str = "Pet Stats";
+
+/// Recovery codes handed out when TOTP is confirmed.
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// One-time code that stands in for the authenticator, e.g. `k3vq9-x2mfa`.
+#[derive(Debug, Clone)]
+pub struct RecoveryCode(pub String);
+
+impl RecoveryCode {
+    pub fn generate() -> Result<Self, AuthError> {
+        use rand::rngs::OsRng;
+
+        let mut buf = [0u8; 8];
+        OsRng.try_fill_bytes(&mut buf)?;
+        let code = BASE32_NOPAD.encode(&buf).to_ascii_lowercase();
+        Ok(Self(format!("{}-{}", &code[..5], &code[5..10])))
+    }
+
+    /// Hashed like refresh tokens. Case and separators typed by the user don't matter.
+    pub fn hash(&self, secret: &[u8]) -> [u8; 32] {
+        let normalized: String = self
+            .0
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        hmac_sha256(secret, normalized.as_bytes())
+    }
+}
+
+/// Opaque token that carries a password sign-in over to the second factor step.
+#[derive(Debug, Clone)]
+pub struct ChallengeToken(pub String);
+
+impl ChallengeToken {
+    pub fn generate() -> Result<Self, AuthError> {
+        Ok(Self(generate_opaque_token()?))
+    }
+
+    pub fn hash(&self, secret: &[u8]) -> [u8; 32] {
+        hmac_sha256(secret, self.0.as_bytes())
+    }
+}
+
+/// Pending TOTP enrollment, to be confirmed with a code from the authenticator.
+#[derive(Debug)]
+pub struct TotpEnrollment {
+    pub secret: TotpSecret,
+    pub otpauth_uri: String,
+}
+
+pub struct TwoFactorService;
+
+impl TwoFactorService {
+    /// Create a new TOTP secret for a local account.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::NotLocalAccount` for OAuth accounts, their provider handles 2FA.
+    /// - `AuthError::TwoFactorAlreadyEnabled` when TOTP is already confirmed.
+    #[instrument(skip(db), fields())]
+    pub async fn enroll_totp(db: &DbConn, user_id: i32) -> Result<TotpEnrollment, AuthError> {
+        let user = UserQuery::user_by_id(db, user_id).await?;
+        if user.login_type != LoginType::Local {
+            return Err(AuthError::NotLocalAccount);
+        }
+
+        let secret = TotpSecret::generate()?;
+        if !TwoFactorMutation::store_pending_totp(db, user_id, secret.0.to_owned()).await? {
+            return Err(AuthError::TwoFactorAlreadyEnabled);
+        }
+
+        let account = user.email.unwrap_or_else(|| user.id.to_string());
+        let otpauth_uri = secret.otpauth_uri(TOTP_ISSUER, &account);
+        info!("TOTP enrollment started for user_id: {}", user_id);
+        Ok(TotpEnrollment {
+            secret,
+            otpauth_uri,
+        })
+    }
+
+    /// Confirm the pending enrollment with a code from the authenticator app.
+    ///
+    /// Returns fresh recovery codes in plain text. Only their hashes are stored, so this is
+    /// the one time the user gets to see them.
+    #[instrument(skip(db, auth_config, code), fields())]
+    pub async fn confirm_totp(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        code: &str,
+    ) -> Result<Vec<RecoveryCode>, AuthError> {
+        let totp = match TwoFactorQuery::totp_by_user_id(db, user_id).await? {
+            Some(totp) if totp.confirmed_at.is_some() => {
+                return Err(AuthError::TwoFactorAlreadyEnabled)
+            }
+            Some(totp) => totp,
+            None => return Err(AuthError::TwoFactorNotEnabled),
+        };
+
+        let step = TotpSecret(totp.secret)
+            .verify(code, Utc::now().timestamp())
+            .ok_or(AuthError::InvalidTwoFactorCode)?;
+
+        let codes = (0..RECOVERY_CODE_COUNT)
+            .map(|_| RecoveryCode::generate())
+            .collect::<Result<Vec<_>, _>>()?;
+        let secret = auth_config.refresh_key_hashing_secret.as_bytes();
+        let hashes = codes.iter().map(|code| code.hash(secret)).collect();
+
+        TwoFactorMutation::confirm_totp(db, user_id, step, hashes).await?;
+        Ok(codes)
+    }
+
+    /// Turn TOTP off. Requires a current code or an unused recovery code.
+    #[instrument(skip(db, auth_config, code), fields())]
+    pub async fn disable_totp(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        code: &str,
+    ) -> Result<(), AuthError> {
+        Self::verify_second_factor(db, auth_config, user_id, code).await?;
+        TwoFactorMutation::disable_totp(db, user_id).await?;
+        Ok(())
+    }
+
+    /// Accept either a TOTP code or a recovery code for the user, each at most once.
+    ///
+    /// # Errors
+    ///
+    /// - `AuthError::TwoFactorNotEnabled` when there's no confirmed TOTP secret.
+    /// - `AuthError::InvalidTwoFactorCode` for a wrong, reused or spent code.
+    #[instrument(skip(db, auth_config, code), fields())]
+    pub async fn verify_second_factor(
+        db: &DbConn,
+        auth_config: &AuthConfig,
+        user_id: i32,
+        code: &str,
+    ) -> Result<(), AuthError> {
+        let Some(totp) = TwoFactorQuery::totp_by_user_id(db, user_id)
+            .await?
+            .filter(|totp| totp.confirmed_at.is_some())
+        else {
+            return Err(AuthError::TwoFactorNotEnabled);
+        };
+
+        let accepted = match TotpSecret(totp.secret).verify(code, Utc::now().timestamp()) {
+            Some(step) => TwoFactorMutation::use_totp_step(db, user_id, step).await?,
+            None => {
+                let hash = RecoveryCode(code.to_owned())
+                    .hash(auth_config.refresh_key_hashing_secret.as_bytes());
+                let used = TwoFactorMutation::use_recovery_code(db, user_id, &hash).await?;
+                if used {
+                    info!("Recovery code used for user_id: {}", user_id);
+                }
+                used
+            }
+        };
+
+        if !accepted {
+            warn!("Second factor rejected for user_id: {}", user_id);
+            return Err(AuthError::InvalidTwoFactorCode);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_code_hash_ignores_formatting() {
+        let code = RecoveryCode::generate().unwrap();
+        assert_eq!(code.0.len(), 11);
+
+        let typed = RecoveryCode(code.0.to_uppercase().replace('-', " "));
+        assert_eq!(code.hash(b"secret"), typed.hash(b"secret"));
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Upper bound for events in one request.
+pub const MAX_BATCH_EVENTS: usize = 500;
+
+/// Upper bound for the length of a device's event id.
+pub const MAX_EVENT_ID_LEN: usize = 128;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IngestError {
+    #[error("A batch can have at most {MAX_BATCH_EVENTS} events")]
+    TooManyEvents,
+    #[error("Event {0} has an empty or too long id")]
+    InvalidEventId(usize),
+    #[error("Event {0} has a negative amount, distance or duration")]
+    NegativeValue(usize),
+}
+
+/// Body of an ingest request.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EventBatch {
+    pub events: Vec<DeviceEvent>,
+}
+
+/// Event reported by a device. `id` is the device's own id for it, unique per device.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceEvent {
+    /// Food dispensed by a feeder, becomes a feed record.
+    Feed {
+        id: String,
+        at: DateTime<FixedOffset>,
+        amount_g: Option<f32>,
+    },
+    /// Activity measured by a collar, becomes a work record.
+    Activity {
+        id: String,
+        at: DateTime<FixedOffset>,
+        distance_m: Option<i32>,
+        duration_minutes: Option<i32>,
+    },
+}
+
+impl DeviceEvent {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Feed { id, .. } | Self::Activity { id, .. } => id,
+        }
+    }
+}
+
+impl EventBatch {
+    pub fn validate(&self) -> Result<(), IngestError> {
+        if self.events.len() > MAX_BATCH_EVENTS {
+            return Err(IngestError::TooManyEvents);
+        }
+        for (i, event) in self.events.iter().enumerate() {
+            if event.id().is_empty() || event.id().len() > MAX_EVENT_ID_LEN {
+                return Err(IngestError::InvalidEventId(i));
+            }
+            let negative = match event {
+                DeviceEvent::Feed { amount_g, .. } => amount_g.is_some_and(|a| a < 0.0),
+                DeviceEvent::Activity {
+                    distance_m,
+                    duration_minutes,
+                    ..
+                } => distance_m.is_some_and(|d| d < 0) || duration_minutes.is_some_and(|d| d < 0),
+            };
+            if negative {
+                return Err(IngestError::NegativeValue(i));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_is_parsed_by_type() {
+        let batch: EventBatch = serde_json::from_str(
+            r#"{"events": [
+                {"type": "feed", "id": "f-1", "at": "2026-10-18T07:00:00+09:00", "amount_g": 40},
+                {"type": "activity", "id": "a-1", "at": "2026-10-18T08:00:00Z",
+                 "distance_m": 1800, "duration_minutes": 32}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(batch.events[0].id(), "f-1");
+        assert!(matches!(
+            batch.events[1],
+            DeviceEvent::Activity {
+                distance_m: Some(1800),
+                duration_minutes: Some(32),
+                ..
+            }
+        ));
+        assert_eq!(batch.validate(), Ok(()));
+
+        assert!(serde_json::from_str::<EventBatch>(
+            r#"{"events": [{"type": "sleep", "id": "s-1", "at": "2026-10-18T08:00:00Z"}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_batches() {
+        let at = DateTime::parse_from_rfc3339("2026-10-18T07:00:00Z").unwrap();
+        let feed = |id: &str, amount_g| DeviceEvent::Feed {
+            id: id.to_owned(),
+            at,
+            amount_g,
+        };
+
+        let batch = EventBatch {
+            events: vec![feed("ok", Some(10.0)), feed("", None)],
+        };
+        assert_eq!(batch.validate(), Err(IngestError::InvalidEventId(1)));
+
+        let batch = EventBatch {
+            events: vec![feed("f-1", Some(-1.0))],
+        };
+        assert_eq!(batch.validate(), Err(IngestError::NegativeValue(0)));
+
+        let batch = EventBatch {
+            events: vec![feed("f-1", None); MAX_BATCH_EVENTS + 1],
+        };
+        assert_eq!(batch.validate(), Err(IngestError::TooManyEvents));
+    }
+}
@@ -0,0 +1,30 @@
+/// Symptom tags as stored: trimmed, lowercase, single spaced, without duplicates and in
+/// the order given. `" Vomiting"` and `"vomiting "` are one tag.
+pub fn normalize_symptoms(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut symptoms: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        if !tag.is_empty() && !symptoms.contains(&tag) {
+            symptoms.push(tag);
+        }
+    }
+    symptoms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symptoms_are_normalized() {
+        let tags = [" Vomiting", "vomiting ", "Loss  of appetite", "", "LIMPING"];
+        assert_eq!(
+            normalize_symptoms(tags.map(str::to_owned)),
+            ["vomiting", "loss of appetite", "limping"]
+        );
+    }
+}
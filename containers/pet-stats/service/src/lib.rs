@@ -1,6 +1,17 @@
 pub mod auth;
+pub mod ingest;
+pub mod journal;
 pub mod jwt;
+pub mod membership;
 pub mod mutations;
+pub mod nutrition;
+pub mod pantry;
+pub mod pet_age;
 pub mod queries;
+pub mod rate_limit;
+pub mod recurrence;
+pub mod schedule;
+pub mod species;
+pub mod walk_track;
 pub(crate) mod utils;
 
@@ -0,0 +1,87 @@
+use chrono::Duration;
+use entity::entities::sea_orm_active_enums::PetRole;
+use sea_orm::{DbErr, Iterable};
+use thiserror::Error;
+
+use crate::auth::{
+    error::AuthError,
+    refresh_token::{generate_opaque_token, hmac_sha256},
+};
+
+/// Marks an invitation code, e.g. `inv_3q2-…`.
+pub const INVITATION_CODE_PREFIX: &str = "inv_";
+
+/// How long an invitation can be accepted.
+pub const INVITATION_TTL: Duration = Duration::days(7);
+
+#[derive(Debug, Error)]
+pub enum MembershipError {
+    #[error("Invitation is invalid or has expired")]
+    InvalidInvitation,
+    #[error("A pet must keep at least one owner")]
+    LastOwner,
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// How much a role may do, each role can do everything the lower ones can: viewers see the
+/// pet and its records, caretakers also log and edit records, owners also edit the pet and
+/// manage its members.
+pub fn rank(role: &PetRole) -> u8 {
+    match role {
+        PetRole::Viewer => 0,
+        PetRole::Caretaker => 1,
+        PetRole::Owner => 2,
+    }
+}
+
+/// Roles allowed to do what `min_role` may do.
+pub fn roles_at_least(min_role: &PetRole) -> Vec<PetRole> {
+    PetRole::iter()
+        .filter(|role| rank(role) >= rank(min_role))
+        .collect()
+}
+
+/// Code inviting someone to a pet, shared out of band. Stored hashed like refresh tokens.
+#[derive(Debug, Clone)]
+pub struct InvitationCode(pub String);
+
+impl InvitationCode {
+    pub fn generate() -> Result<Self, AuthError> {
+        Ok(Self(format!(
+            "{}{}",
+            INVITATION_CODE_PREFIX,
+            generate_opaque_token()?
+        )))
+    }
+
+    pub fn hash(&self, secret: &[u8]) -> [u8; 32] {
+        hmac_sha256(secret, self.0.trim().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_include_higher_ones() {
+        assert_eq!(roles_at_least(&PetRole::Owner), vec![PetRole::Owner]);
+        assert_eq!(
+            roles_at_least(&PetRole::Caretaker),
+            vec![PetRole::Owner, PetRole::Caretaker]
+        );
+        assert_eq!(roles_at_least(&PetRole::Viewer).len(), 3);
+    }
+
+    #[test]
+    fn test_invitation_code_hash_ignores_surrounding_whitespace() {
+        let code = InvitationCode::generate().unwrap();
+        assert!(code.0.starts_with(INVITATION_CODE_PREFIX));
+        assert_eq!(
+            code.hash(b"secret"),
+            InvitationCode(format!(" {}\n", code.0)).hash(b"secret")
+        );
+        assert_ne!(code.hash(b"secret"), code.hash(b"other"));
+    }
+}
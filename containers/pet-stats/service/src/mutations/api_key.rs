@@ -0,0 +1,86 @@
+use chrono::Local;
+use entity::entities::{
+    api_keys::{self, Column as C, Entity as ApiKeys},
+    users,
+};
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::auth::api_key::ApiScope;
+
+pub struct ApiKeyMutation;
+
+impl ApiKeyMutation {
+    #[instrument(skip(db, key_hash), fields())]
+    pub async fn create_api_key(
+        db: &DbConn,
+        user_id: i32,
+        name: String,
+        key_prefix: String,
+        key_hash: &[u8; 32],
+        scopes: &[ApiScope],
+    ) -> Result<api_keys::Model, DbErr> {
+        let api_key = api_keys::ActiveModel {
+            user_id: Set(user_id),
+            name: Set(name),
+            key_prefix: Set(key_prefix),
+            key_hash: Set(key_hash.to_vec()),
+            scopes: Set(scopes.iter().map(|s| s.as_str().to_owned()).collect()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!("API key {} created for user_id: {}", api_key.id, user_id);
+        Ok(api_key)
+    }
+
+    /// Revoke one of the user's keys. Returns `None` when the user has no such active key.
+    #[instrument(skip(db), fields())]
+    pub async fn revoke_api_key(
+        db: &DbConn,
+        user_id: i32,
+        id: i32,
+    ) -> Result<Option<api_keys::Model>, DbErr> {
+        let revoked = ApiKeys::update_many()
+            .col_expr(C::RevokedAt, Expr::value(Local::now().fixed_offset()))
+            .filter(C::Id.eq(id))
+            .filter(C::UserId.eq(user_id))
+            .filter(C::RevokedAt.is_null())
+            .exec_with_returning(db)
+            .await?;
+        Ok(revoked.into_iter().next())
+    }
+
+    /// Look up an active key by hash and stamp `last_used_at` in the same statement.
+    ///
+    /// Keys of an account pending deletion or disabled are not accepted, and work again once
+    /// the deletion is cancelled or the account enabled.
+    #[instrument(skip(db, key_hash), fields())]
+    pub async fn use_api_key(
+        db: &DbConn,
+        key_hash: &[u8; 32],
+    ) -> Result<Option<api_keys::Model>, DbErr> {
+        let used = ApiKeys::update_many()
+            .col_expr(C::LastUsedAt, Expr::value(Local::now().fixed_offset()))
+            .filter(C::KeyHash.eq(key_hash.as_slice()))
+            .filter(C::RevokedAt.is_null())
+            .filter(
+                C::UserId.in_subquery(
+                    Query::select()
+                        .column(users::Column::Id)
+                        .from(users::Entity)
+                        .and_where(users::Column::DeletionScheduledAt.is_null())
+                        .and_where(users::Column::DisabledAt.is_null())
+                        .to_owned(),
+                ),
+            )
+            .exec_with_returning(db)
+            .await?;
+        Ok(used.into_iter().next())
+    }
+}
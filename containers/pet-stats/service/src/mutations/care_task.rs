@@ -0,0 +1,67 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    care_task_completions::{self, Column as CompletionColumn, Entity as CareTaskCompletions},
+    care_tasks::{self, Column as C, Entity as CareTasks},
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr,
+    EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::utils::user_pet_ids;
+
+pub struct CareTaskMutation;
+
+impl CareTaskMutation {
+    #[instrument(skip(db))]
+    pub async fn add_care_task(
+        db: &DbConn,
+        care_task: care_tasks::ActiveModel,
+    ) -> Result<care_tasks::Model, DbErr> {
+        let care_task = care_task.insert(db).await?;
+        info!(
+            "Care task {} added for pet_id: {}",
+            care_task.id, care_task.pet_id
+        );
+        Ok(care_task)
+    }
+
+    /// Delete a care task of one of the user's pets along with its completions.
+    /// Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_care_task(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = CareTasks::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+
+    /// Mark the occurrence due on `due_on` done. Completing it again replaces the note.
+    #[instrument(skip(db))]
+    pub async fn complete_care_task(
+        db: &DbConn,
+        care_task_id: i32,
+        due_on: NaiveDate,
+        note: Option<String>,
+    ) -> Result<care_task_completions::Model, DbErr> {
+        let completion = care_task_completions::ActiveModel {
+            care_task_id: Set(care_task_id),
+            due_on: Set(due_on),
+            note: Set(note),
+            completed_at: Set(Local::now().fixed_offset()),
+            ..Default::default()
+        };
+        CareTaskCompletions::insert(completion)
+            .on_conflict(
+                OnConflict::columns([CompletionColumn::CareTaskId, CompletionColumn::DueOn])
+                    .update_columns([CompletionColumn::Note, CompletionColumn::CompletedAt])
+                    .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await
+    }
+}
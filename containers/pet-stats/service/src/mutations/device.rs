@@ -0,0 +1,149 @@
+use entity::entities::{
+    device_events::{self, Column as EventColumn, Entity as DeviceEvents},
+    devices::{self, Column as C, Entity as Devices},
+    feed_records::{self, Entity as FeedRecords},
+    sea_orm_active_enums::FoodUnit,
+    work_records::{self, Entity as WorkRecords},
+};
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::ingest::DeviceEvent;
+use crate::mutations::walk::set_time;
+use crate::utils::{commit_transaction, get_current_time, start_transaction};
+
+/// Outcome of an ingest request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IngestSummary {
+    /// Events stored as new records.
+    pub accepted: u32,
+    /// Events the device had already sent.
+    pub duplicates: u32,
+}
+
+pub struct DeviceMutation;
+
+impl DeviceMutation {
+    #[instrument(skip(db), fields())]
+    pub async fn add_device(
+        db: &DbConn,
+        user_id: i32,
+        pet_id: i32,
+        uid: String,
+        name: String,
+    ) -> Result<devices::Model, DbErr> {
+        devices::ActiveModel {
+            user_id: Set(user_id),
+            pet_id: Set(pet_id),
+            uid: Set(uid),
+            name: Set(name),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+
+    /// Remove one of the user's devices. Returns `false` when the user has no such device.
+    #[instrument(skip(db), fields())]
+    pub async fn remove_device(db: &DbConn, user_id: i32, id: i32) -> Result<bool, DbErr> {
+        let result = Devices::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::UserId.eq(user_id))
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Store the events as records of the device's pet. Events whose id the device already
+    /// sent are skipped, so a batch can safely be retried.
+    #[instrument(skip(db, device, events), fields(device_id = device.id))]
+    pub async fn ingest(
+        db: &DbConn,
+        device: &devices::Model,
+        events: &[DeviceEvent],
+    ) -> Result<IngestSummary, DbErr> {
+        let txn = start_transaction(db).await?;
+        let now = get_current_time();
+        let mut summary = IngestSummary::default();
+
+        for event in events {
+            let claimed = DeviceEvents::insert(device_events::ActiveModel {
+                device_id: Set(device.id),
+                event_id: Set(event.id().to_owned()),
+                feed_record_id: Set(None),
+                work_record_id: Set(None),
+                received_at: Set(now),
+            })
+            .on_conflict(
+                OnConflict::columns([EventColumn::DeviceId, EventColumn::EventId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+            if claimed == 0 {
+                summary.duplicates += 1;
+                continue;
+            }
+
+            let (column, record_id) = match event {
+                DeviceEvent::Feed { at, amount_g, .. } => {
+                    let record = feed_records::ActiveModel {
+                        pet_id: Set(device.pet_id),
+                        amount: Set(*amount_g),
+                        unit: Set(amount_g.map(|_| FoodUnit::Gram)),
+                        created_at: Set(*at),
+                        updated_at: Set(now),
+                        ..Default::default()
+                    };
+                    let id = FeedRecords::insert(record).exec(&txn).await?.last_insert_id;
+                    (EventColumn::FeedRecordId, id)
+                }
+                DeviceEvent::Activity {
+                    at,
+                    distance_m,
+                    duration_minutes,
+                    ..
+                } => {
+                    let record = work_records::ActiveModel {
+                        pet_id: Set(device.pet_id),
+                        distance_m: Set(*distance_m),
+                        created_at: Set(*at),
+                        updated_at: Set(now),
+                        ..Default::default()
+                    };
+                    let id = WorkRecords::insert(record).exec(&txn).await?.last_insert_id;
+                    if let Some(minutes) = duration_minutes {
+                        set_time(&txn, id, i64::from(*minutes) * 60).await?;
+                    }
+                    (EventColumn::WorkRecordId, id)
+                }
+            };
+            DeviceEvents::update_many()
+                .col_expr(column, Expr::value(record_id))
+                .filter(EventColumn::DeviceId.eq(device.id))
+                .filter(EventColumn::EventId.eq(event.id()))
+                .exec(&txn)
+                .await?;
+            summary.accepted += 1;
+        }
+
+        Devices::update_many()
+            .col_expr(C::LastSeenAt, Expr::value(now))
+            .filter(C::Id.eq(device.id))
+            .exec(&txn)
+            .await?;
+
+        commit_transaction(txn).await?;
+        info!(
+            "Device {} sent {} new and {} duplicate events",
+            device.id, summary.accepted, summary.duplicates
+        );
+        Ok(summary)
+    }
+}
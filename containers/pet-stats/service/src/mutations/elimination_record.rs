@@ -0,0 +1,35 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::{elimination_records, sea_orm_active_enums::EliminationType};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbConn, DbErr, NotSet};
+use tracing::{info, instrument};
+
+pub struct EliminationRecordMutation;
+
+impl EliminationRecordMutation {
+    /// Log a urination or stool. `consistency` is only kept for stools. `occurred_at`
+    /// defaults to now.
+    #[instrument(skip(db))]
+    pub async fn add_elimination_record(
+        db: &DbConn,
+        pet_id: i32,
+        kind: EliminationType,
+        consistency: Option<i32>,
+        occurred_at: Option<DateTime<FixedOffset>>,
+    ) -> Result<elimination_records::Model, DbErr> {
+        let consistency = consistency.filter(|_| kind == EliminationType::Stool);
+        let record = elimination_records::ActiveModel {
+            pet_id: Set(pet_id),
+            kind: Set(kind),
+            consistency: Set(consistency),
+            created_at: occurred_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!(
+            "Elimination record {} added for pet_id: {}",
+            record.id, pet_id
+        );
+        Ok(record)
+    }
+}
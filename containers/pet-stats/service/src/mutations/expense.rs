@@ -0,0 +1,47 @@
+use entity::entities::{
+    expenses::{self, Column as C, Entity as Expenses},
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::utils::{get_current_time, user_pet_ids};
+
+pub struct ExpenseMutation;
+
+impl ExpenseMutation {
+    #[instrument(skip(db))]
+    pub async fn add_expense(
+        db: &DbConn,
+        expense: expenses::ActiveModel,
+    ) -> Result<expenses::Model, DbErr> {
+        let expense = expense.insert(db).await?;
+        info!(
+            "Expense {} added for pet_id: {}",
+            expense.id, expense.pet_id
+        );
+        Ok(expense)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn update_expense(
+        db: &DbConn,
+        mut expense: expenses::ActiveModel,
+    ) -> Result<expenses::Model, DbErr> {
+        expense.updated_at = Set(get_current_time());
+        expense.update(db).await
+    }
+
+    /// Delete an expense of one of the user's pets. Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_expense(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = Expenses::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
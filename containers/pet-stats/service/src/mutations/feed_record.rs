@@ -0,0 +1,33 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::{feed_records, sea_orm_active_enums::FoodUnit};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbConn, DbErr, NotSet};
+use tracing::{info, instrument};
+
+pub struct FeedRecordMutation;
+
+impl FeedRecordMutation {
+    /// Log a feeding, `amount` measured in `unit` of the food. `fed_at` defaults to now,
+    /// devices may report it late.
+    #[instrument(skip(db))]
+    pub async fn add_feed_record(
+        db: &DbConn,
+        pet_id: i32,
+        amount: Option<f32>,
+        food_id: Option<i32>,
+        unit: Option<FoodUnit>,
+        fed_at: Option<DateTime<FixedOffset>>,
+    ) -> Result<feed_records::Model, DbErr> {
+        let record = feed_records::ActiveModel {
+            pet_id: Set(pet_id),
+            amount: Set(amount),
+            food_id: Set(food_id),
+            unit: Set(unit),
+            created_at: fed_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!("Feed record {} added for pet_id: {}", record.id, pet_id);
+        Ok(record)
+    }
+}
@@ -0,0 +1,55 @@
+use entity::entities::{
+    food_purchases::{self, Entity as FoodPurchases},
+    foods::{self, Column as C, Entity as Foods},
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter};
+use tracing::{info, instrument};
+
+use crate::utils::user_food_ids;
+
+pub struct FoodMutation;
+
+impl FoodMutation {
+    #[instrument(skip(db))]
+    pub async fn add_food(db: &DbConn, food: foods::ActiveModel) -> Result<foods::Model, DbErr> {
+        let food = food.insert(db).await?;
+        info!("Food {} added for user_id: {}", food.id, food.user_id);
+        Ok(food)
+    }
+
+    /// Delete one of the user's foods. Feedings of it are kept without a food.
+    /// Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_food(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = Foods::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::UserId.eq(user_id))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn record_purchase(
+        db: &DbConn,
+        purchase: food_purchases::ActiveModel,
+    ) -> Result<food_purchases::Model, DbErr> {
+        let purchase = purchase.insert(db).await?;
+        info!(
+            "Purchase {} recorded for food_id: {}",
+            purchase.id, purchase.food_id
+        );
+        Ok(purchase)
+    }
+
+    /// Delete a purchase of one of the user's foods. Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_purchase(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = FoodPurchases::delete_many()
+            .filter(food_purchases::Column::Id.eq(id))
+            .filter(food_purchases::Column::FoodId.in_subquery(user_food_ids(user_id)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
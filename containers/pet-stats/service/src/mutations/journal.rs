@@ -0,0 +1,47 @@
+use entity::entities::{
+    journal_entries::{self, Column as C, Entity as JournalEntries},
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::utils::{get_current_time, user_pet_ids};
+
+pub struct JournalMutation;
+
+impl JournalMutation {
+    #[instrument(skip(db, entry))]
+    pub async fn add_entry(
+        db: &DbConn,
+        entry: journal_entries::ActiveModel,
+    ) -> Result<journal_entries::Model, DbErr> {
+        let entry = entry.insert(db).await?;
+        info!(
+            "Journal entry {} added for pet_id: {}",
+            entry.id, entry.pet_id
+        );
+        Ok(entry)
+    }
+
+    #[instrument(skip(db, entry))]
+    pub async fn update_entry(
+        db: &DbConn,
+        mut entry: journal_entries::ActiveModel,
+    ) -> Result<journal_entries::Model, DbErr> {
+        entry.updated_at = Set(get_current_time());
+        entry.update(db).await
+    }
+
+    /// Delete an entry about one of the user's pets. Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_entry(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = JournalEntries::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
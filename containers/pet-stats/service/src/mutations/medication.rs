@@ -0,0 +1,73 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    medication_doses::{self, Column as DoseColumn, Entity as MedicationDoses},
+    medications::{self, Column as C, Entity as Medications},
+    sea_orm_active_enums::{DoseStatus, PetRole},
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr,
+    EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::utils::user_pet_ids;
+
+pub struct MedicationMutation;
+
+impl MedicationMutation {
+    #[instrument(skip(db))]
+    pub async fn add_medication(
+        db: &DbConn,
+        medication: medications::ActiveModel,
+    ) -> Result<medications::Model, DbErr> {
+        let medication = medication.insert(db).await?;
+        info!(
+            "Medication {} added for pet_id: {}",
+            medication.id, medication.pet_id
+        );
+        Ok(medication)
+    }
+
+    /// Delete a medication of one of the user's pets, its dose log goes with it.
+    /// Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_medication(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = Medications::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+
+    /// Mark a scheduled dose given or skipped. Marking it again replaces the status.
+    #[instrument(skip(db))]
+    pub async fn record_dose(
+        db: &DbConn,
+        medication_id: i32,
+        scheduled_on: NaiveDate,
+        dose_number: i32,
+        status: DoseStatus,
+    ) -> Result<medication_doses::Model, DbErr> {
+        let dose = medication_doses::ActiveModel {
+            medication_id: Set(medication_id),
+            scheduled_on: Set(scheduled_on),
+            dose_number: Set(dose_number),
+            status: Set(status),
+            recorded_at: Set(Local::now().fixed_offset()),
+            ..Default::default()
+        };
+        MedicationDoses::insert(dose)
+            .on_conflict(
+                OnConflict::columns([
+                    DoseColumn::MedicationId,
+                    DoseColumn::ScheduledOn,
+                    DoseColumn::DoseNumber,
+                ])
+                .update_columns([DoseColumn::Status, DoseColumn::RecordedAt])
+                .to_owned(),
+            )
+            .exec_with_returning(db)
+            .await
+    }
+}
@@ -1,16 +1,25 @@
-use entity::entities::pets;
+use entity::entities::{pet_members, pets, sea_orm_active_enums::PetRole};
 use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbConn, DbErr, DeleteResult, EntityTrait};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, instrument};
 
 use crate::utils::{commit_transaction, get_current_time, start_transaction};
 
 pub struct PetMutationService;
 
 impl PetMutationService {
+    /// Add the pet with the user who added it as its owner.
     #[instrument(skip(db))]
     pub async fn add_pet(db: &DbConn, pet: pets::ActiveModel) -> Result<pets::Model, DbErr> {
         let txn = start_transaction(db).await?;
         let new_pet = pet.insert(&txn).await?;
+        pet_members::ActiveModel {
+            pet_id: Set(new_pet.id),
+            user_id: Set(new_pet.user_id),
+            role: Set(PetRole::Owner),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
         commit_transaction(txn).await?;
         Ok(new_pet)
     }
@@ -0,0 +1,133 @@
+use entity::entities::{
+    devices::{self, Entity as Devices},
+    pet_invitations::{self, Column as InvitationColumn, Entity as PetInvitations},
+    pet_members::{self, Column as C, Entity as PetMembers},
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+    QuerySelect,
+};
+use tracing::{info, instrument};
+
+use crate::membership::{rank, MembershipError, INVITATION_TTL};
+use crate::utils::{commit_transaction, get_current_time, start_transaction};
+
+pub struct PetMemberMutation;
+
+impl PetMemberMutation {
+    /// Store an invitation to the pet, valid for [`INVITATION_TTL`].
+    #[instrument(skip(db, code_hash), fields())]
+    pub async fn create_invitation(
+        db: &DbConn,
+        pet_id: i32,
+        invited_by: i32,
+        role: PetRole,
+        code_hash: &[u8; 32],
+    ) -> Result<pet_invitations::Model, DbErr> {
+        let invitation = pet_invitations::ActiveModel {
+            pet_id: Set(pet_id),
+            invited_by: Set(invited_by),
+            role: Set(role),
+            code_hash: Set(code_hash.to_vec()),
+            expires_at: Set(get_current_time() + INVITATION_TTL),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!(
+            "Invitation {} created for pet_id: {}",
+            invitation.id, pet_id
+        );
+        Ok(invitation)
+    }
+
+    /// Use up the invitation with the hash and make the user a member of its pet. Someone
+    /// already a member keeps their role unless the invitation grants a higher one.
+    #[instrument(skip(db, code_hash), fields())]
+    pub async fn accept_invitation(
+        db: &DbConn,
+        user_id: i32,
+        code_hash: &[u8; 32],
+    ) -> Result<pet_members::Model, MembershipError> {
+        let txn = start_transaction(db).await?;
+
+        let invitation = PetInvitations::delete_many()
+            .filter(InvitationColumn::CodeHash.eq(code_hash.as_slice()))
+            .filter(InvitationColumn::ExpiresAt.gt(get_current_time()))
+            .exec_with_returning(&txn)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(MembershipError::InvalidInvitation)?;
+
+        let member = match PetMembers::find_by_id((invitation.pet_id, user_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+        {
+            None => {
+                pet_members::ActiveModel {
+                    pet_id: Set(invitation.pet_id),
+                    user_id: Set(user_id),
+                    role: Set(invitation.role),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?
+            }
+            Some(member) if rank(&member.role) < rank(&invitation.role) => {
+                let mut member: pet_members::ActiveModel = member.into();
+                member.role = Set(invitation.role);
+                member.update(&txn).await?
+            }
+            Some(member) => member,
+        };
+
+        commit_transaction(txn).await?;
+        info!(
+            "User {} joined pet_id: {} as {:?}",
+            user_id, member.pet_id, member.role
+        );
+        Ok(member)
+    }
+
+    /// Remove the user from the pet, either leaving or removed by an owner. Returns `false`
+    /// when the user isn't a member. The last owner can't be removed. The user's devices of the
+    /// pet go with the membership, so they can't keep writing records into it.
+    #[instrument(skip(db), fields())]
+    pub async fn remove_member(
+        db: &DbConn,
+        pet_id: i32,
+        user_id: i32,
+    ) -> Result<bool, MembershipError> {
+        let txn = start_transaction(db).await?;
+
+        // Locking the owners keeps two owners from leaving at the same time.
+        let owners = PetMembers::find()
+            .filter(C::PetId.eq(pet_id))
+            .filter(C::Role.eq(PetRole::Owner))
+            .lock_exclusive()
+            .all(&txn)
+            .await?;
+        if owners.len() == 1 && owners[0].user_id == user_id {
+            return Err(MembershipError::LastOwner);
+        }
+
+        let removed = PetMembers::delete_by_id((pet_id, user_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        if removed > 0 {
+            Devices::delete_many()
+                .filter(devices::Column::PetId.eq(pet_id))
+                .filter(devices::Column::UserId.eq(user_id))
+                .exec(&txn)
+                .await?;
+        }
+
+        commit_transaction(txn).await?;
+        info!("User {} removed from pet_id: {}", user_id, pet_id);
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,49 @@
+use entity::entities::{sea_orm_active_enums::SecurityEventType, security_events};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbConn, DbErr};
+use tracing::{info, instrument};
+
+/// Where a security relevant request came from.
+#[derive(Debug, Clone, Default)]
+pub struct EventOrigin {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device_id: Option<String>,
+}
+
+pub struct SecurityEventMutation;
+
+impl SecurityEventMutation {
+    /// Append an entry to the audit log. `user_id` is `None` when a failed attempt can't be
+    /// tied to an account.
+    #[instrument(skip(db, origin), fields())]
+    pub async fn record(
+        db: &DbConn,
+        user_id: Option<i32>,
+        event_type: SecurityEventType,
+        origin: &EventOrigin,
+    ) -> Result<security_events::Model, DbErr> {
+        // Header values are client controlled, cut them down to the column sizes.
+        let clip = |value: &Option<String>, max: usize| {
+            value
+                .as_ref()
+                .map(|v| v.chars().take(max).collect::<String>())
+        };
+
+        let event = security_events::ActiveModel {
+            user_id: Set(user_id),
+            event_type: Set(event_type),
+            ip_address: Set(clip(&origin.ip_address, 45)),
+            user_agent: Set(clip(&origin.user_agent, 512)),
+            device_id: Set(clip(&origin.device_id, 255)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        info!(
+            "Security event {:?} recorded for user_id: {:?}",
+            event.event_type, event.user_id
+        );
+        Ok(event)
+    }
+}
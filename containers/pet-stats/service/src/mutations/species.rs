@@ -0,0 +1,39 @@
+use entity::entities::species::{self, Column as C, Entity as Species};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument};
+
+pub struct SpeciesMutation;
+
+impl SpeciesMutation {
+    /// Define a species of the user's own for pets none of the shared species fit.
+    #[instrument(skip(db))]
+    pub async fn add_species(
+        db: &DbConn,
+        user_id: i32,
+        name: String,
+    ) -> Result<species::Model, DbErr> {
+        let species = species::ActiveModel {
+            name: Set(name),
+            user_id: Set(Some(user_id)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!("Species {} added for user_id: {}", species.id, user_id);
+        Ok(species)
+    }
+
+    /// Delete one of the user's own species. Fails while pets still have it.
+    /// Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_species(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = Species::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::UserId.eq(user_id))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::Local;
+use entity::entities::revoked_access_tokens::{self, Column as C, Entity as RevokedAccessTokens};
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, sea_query::OnConflict, ActiveValue::Set, ColumnTrait, DbConn,
+    DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{error, info, instrument};
+
+pub struct TokenRevocationMutation;
+
+impl TokenRevocationMutation {
+    /// Persist a revoked access token. Revoking the same `jti` twice is a no-op.
+    #[instrument(skip(db), fields())]
+    pub async fn revoke(
+        db: &DbConn,
+        jti: String,
+        user_id: i32,
+        expires_at: DateTimeWithTimeZone,
+    ) -> Result<(), DbErr> {
+        let revoked = revoked_access_tokens::ActiveModel {
+            jti: Set(jti),
+            user_id: Set(user_id),
+            expires_at: Set(expires_at),
+            ..Default::default()
+        };
+        RevokedAccessTokens::insert(revoked)
+            .on_conflict(OnConflict::column(C::Jti).do_nothing().to_owned())
+            .do_nothing()
+            .exec(db)
+            .await?;
+        info!("Access token revoked for user_id: {}", user_id);
+        Ok(())
+    }
+
+    /// Drop revocations of tokens that have expired on their own by now.
+    #[instrument(skip(db), fields())]
+    pub async fn purge_expired(db: &DbConn) -> Result<u64, DbErr> {
+        let res = RevokedAccessTokens::delete_many()
+            .filter(C::ExpiresAt.lt(Local::now().fixed_offset()))
+            .exec(db)
+            .await
+            .inspect_err(|e| error!("Failed to purge expired revocations: {:?}", e))?;
+        Ok(res.rows_affected)
+    }
+}
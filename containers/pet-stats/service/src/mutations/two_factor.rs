@@ -0,0 +1,214 @@
+use chrono::{DateTime, FixedOffset, Local};
+use entity::entities::{
+    recovery_codes::{self, Entity as RecoveryCodes},
+    two_factor_challenges::{self, Entity as TwoFactorChallenges},
+    user_totp::{self, Entity as UserTotp},
+};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ColumnTrait, Condition, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::{info, instrument, warn};
+
+use crate::utils::{commit_transaction, start_transaction};
+
+pub struct TwoFactorMutation;
+
+impl TwoFactorMutation {
+    /// Store a new, unconfirmed TOTP secret, replacing an earlier pending enrollment.
+    ///
+    /// Returns `false` without touching it when a confirmed secret exists.
+    #[instrument(skip(db, secret), fields())]
+    pub async fn store_pending_totp(
+        db: &DbConn,
+        user_id: i32,
+        secret: Vec<u8>,
+    ) -> Result<bool, DbErr> {
+        let now = Local::now().fixed_offset();
+        let res = UserTotp::insert(user_totp::ActiveModel {
+            user_id: Set(user_id),
+            secret: Set(secret),
+            confirmed_at: Set(None),
+            last_used_step: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .on_conflict(
+            OnConflict::column(user_totp::Column::UserId)
+                .update_columns([
+                    user_totp::Column::Secret,
+                    user_totp::Column::LastUsedStep,
+                    user_totp::Column::UpdatedAt,
+                ])
+                .action_and_where(user_totp::Column::ConfirmedAt.is_null())
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        if res == 0 {
+            warn!("TOTP already enabled, keeping the confirmed secret");
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Activate the pending TOTP secret and replace the recovery codes in one transaction.
+    #[instrument(skip(db, recovery_code_hashes), fields())]
+    pub async fn confirm_totp(
+        db: &DbConn,
+        user_id: i32,
+        step: i64,
+        recovery_code_hashes: Vec<[u8; 32]>,
+    ) -> Result<(), DbErr> {
+        let txn = start_transaction(db).await?;
+        let now = Local::now().fixed_offset();
+
+        let res = UserTotp::update_many()
+            .col_expr(user_totp::Column::ConfirmedAt, Expr::value(now))
+            .col_expr(user_totp::Column::LastUsedStep, Expr::value(step))
+            .col_expr(user_totp::Column::UpdatedAt, Expr::value(now))
+            .filter(user_totp::Column::UserId.eq(user_id))
+            .filter(user_totp::Column::ConfirmedAt.is_null())
+            .exec(&txn)
+            .await?;
+        if res.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Pending TOTP Not Found".to_owned()));
+        }
+
+        RecoveryCodes::delete_many()
+            .filter(recovery_codes::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        RecoveryCodes::insert_many(recovery_code_hashes.iter().map(|hash| {
+            recovery_codes::ActiveModel {
+                user_id: Set(user_id),
+                code_hash: Set(hash.to_vec()),
+                ..Default::default()
+            }
+        }))
+        .exec_without_returning(&txn)
+        .await?;
+
+        commit_transaction(txn).await?;
+        info!("TOTP confirmed for user_id: {}", user_id);
+        Ok(())
+    }
+
+    /// Remove the TOTP secret and every recovery code of the user.
+    #[instrument(skip(db), fields())]
+    pub async fn disable_totp(db: &DbConn, user_id: i32) -> Result<(), DbErr> {
+        let txn = start_transaction(db).await?;
+        UserTotp::delete_by_id(user_id).exec(&txn).await?;
+        RecoveryCodes::delete_many()
+            .filter(recovery_codes::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        commit_transaction(txn).await?;
+        info!("TOTP disabled for user_id: {}", user_id);
+        Ok(())
+    }
+
+    /// Record `step` as used. Fails when the same or a later step was already accepted,
+    /// which stops a code from being replayed within its validity window.
+    #[instrument(skip(db), fields())]
+    pub async fn use_totp_step(db: &DbConn, user_id: i32, step: i64) -> Result<bool, DbErr> {
+        let res = UserTotp::update_many()
+            .col_expr(user_totp::Column::LastUsedStep, Expr::value(step))
+            .col_expr(
+                user_totp::Column::UpdatedAt,
+                Expr::value(Local::now().fixed_offset()),
+            )
+            .filter(user_totp::Column::UserId.eq(user_id))
+            .filter(user_totp::Column::ConfirmedAt.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(user_totp::Column::LastUsedStep.is_null())
+                    .add(user_totp::Column::LastUsedStep.lt(step)),
+            )
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected == 1)
+    }
+
+    /// Spend an unused recovery code. Returns `false` when no such code is left.
+    #[instrument(skip(db, code_hash), fields())]
+    pub async fn use_recovery_code(
+        db: &DbConn,
+        user_id: i32,
+        code_hash: &[u8; 32],
+    ) -> Result<bool, DbErr> {
+        let res = RecoveryCodes::update_many()
+            .col_expr(
+                recovery_codes::Column::UsedAt,
+                Expr::value(Local::now().fixed_offset()),
+            )
+            .filter(recovery_codes::Column::UserId.eq(user_id))
+            .filter(recovery_codes::Column::CodeHash.eq(code_hash.as_slice()))
+            .filter(recovery_codes::Column::UsedAt.is_null())
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected == 1)
+    }
+
+    #[instrument(skip(db, challenge_hash), fields())]
+    pub async fn create_challenge(
+        db: &DbConn,
+        user_id: i32,
+        challenge_hash: &[u8; 32],
+        expires_at: DateTime<FixedOffset>,
+    ) -> Result<(), DbErr> {
+        TwoFactorChallenges::insert(two_factor_challenges::ActiveModel {
+            user_id: Set(user_id),
+            challenge_hash: Set(challenge_hash.to_vec()),
+            expires_at: Set(expires_at),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Count one verification attempt against a live challenge.
+    ///
+    /// Returns `None` when the challenge is unknown, expired or out of attempts.
+    #[instrument(skip(db, challenge_hash), fields())]
+    pub async fn attempt_challenge(
+        db: &DbConn,
+        challenge_hash: &[u8; 32],
+        max_attempts: i32,
+    ) -> Result<Option<two_factor_challenges::Model>, DbErr> {
+        let challenges = TwoFactorChallenges::update_many()
+            .col_expr(
+                two_factor_challenges::Column::Attempts,
+                Expr::col(two_factor_challenges::Column::Attempts).add(1),
+            )
+            .filter(two_factor_challenges::Column::ChallengeHash.eq(challenge_hash.as_slice()))
+            .filter(two_factor_challenges::Column::ExpiresAt.gt(Local::now().fixed_offset()))
+            .filter(two_factor_challenges::Column::Attempts.lt(max_attempts))
+            .exec_with_returning(db)
+            .await?;
+        Ok(challenges.into_iter().next())
+    }
+
+    #[instrument(skip(db), fields())]
+    pub async fn delete_challenge(db: &DbConn, id: i32) -> Result<(), DbErr> {
+        TwoFactorChallenges::delete_by_id(id).exec(db).await?;
+        Ok(())
+    }
+
+    /// Drop challenges that expired or ran out of attempts, they can't be completed anymore.
+    #[instrument(skip(db), fields())]
+    pub async fn purge_stale_challenges(db: &DbConn, max_attempts: i32) -> Result<u64, DbErr> {
+        let res = TwoFactorChallenges::delete_many()
+            .filter(
+                Condition::any()
+                    .add(two_factor_challenges::Column::ExpiresAt.lte(Local::now().fixed_offset()))
+                    .add(two_factor_challenges::Column::Attempts.gte(max_attempts)),
+            )
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
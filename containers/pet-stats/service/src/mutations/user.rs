@@ -1,16 +1,53 @@
 use chrono::{Duration, Local};
 use entity::entities::sea_orm_active_enums::{LoginType, ProviderType};
 use entity::entities::user_tokens::{self, Column as C, Entity as UserTokens, Model};
-use entity::entities::{oauth_accounts, users};
+use entity::entities::{oauth_accounts, users, users::Entity as Users};
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, IntoActiveModel,
-    QueryFilter, QuerySelect,
+    prelude::DateTimeWithTimeZone, sea_query::Expr, sea_query::Query, ActiveModelTrait,
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, DbConn, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, QuerySelect, Statement,
 };
 use tracing::{error, info, instrument, warn};
 
 use crate::utils::{commit_transaction, start_transaction};
 
+/// Pets the account about to be purged owns, or added, that would be left without an owner
+/// whose account stays. The member who joined first among those staying becomes an owner, so
+/// `HAND_OVER_SHARED_PETS` has someone to hand them to.
+const PROMOTE_SUCCESSORS: &str = r#"WITH "due" AS (
+    SELECT "id" FROM "users" WHERE "deletion_scheduled_at" <= $2
+), "orphaned" AS (
+    SELECT "pet_id" AS "id" FROM "pet_members" WHERE "user_id" = $1 AND "role" = 'Owner'
+    UNION
+    SELECT "id" FROM "pets" WHERE "user_id" = $1
+    EXCEPT
+    SELECT "pet_id" FROM "pet_members"
+    WHERE "role" = 'Owner' AND "user_id" NOT IN (SELECT "id" FROM "due")
+), "successors" AS (
+    SELECT DISTINCT ON ("pet_id") "pet_id", "user_id" FROM "pet_members"
+    WHERE "pet_id" IN (SELECT "id" FROM "orphaned") AND "user_id" NOT IN (SELECT "id" FROM "due")
+    ORDER BY "pet_id", "created_at", "user_id"
+)
+UPDATE "pet_members" SET "role" = 'Owner'
+FROM "successors"
+WHERE "pet_members"."pet_id" = "successors"."pet_id"
+    AND "pet_members"."user_id" = "successors"."user_id""#;
+
+/// Pets added by the account about to be purged that have another owner, whose account stays,
+/// are handed to the owner who joined first, so they survive the purge. Custom species of
+/// those pets follow them, see the `hand_over_custom_species` trigger on `users`.
+const HAND_OVER_SHARED_PETS: &str = r#"WITH "heirs" AS (
+    SELECT DISTINCT ON ("pet_id") "pet_id", "user_id" FROM "pet_members"
+    WHERE "role" = 'Owner' AND "user_id" NOT IN (
+        SELECT "id" FROM "users" WHERE "deletion_scheduled_at" <= $2
+    )
+    ORDER BY "pet_id", "created_at", "user_id"
+)
+UPDATE "pets" SET "user_id" = "heirs"."user_id"
+FROM "heirs"
+WHERE "heirs"."pet_id" = "pets"."id" AND "pets"."user_id" = $1"#;
+
 pub struct UserMutation;
 
 impl UserMutation {
@@ -122,7 +159,7 @@ impl UserMutation {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// let new_token = rotate_refresh_token(&conn, old_hash, new_hash).await?;
     /// ```
     #[instrument(skip(db), fields())]
@@ -159,4 +196,263 @@ impl UserMutation {
 
         Ok(user_token)
     }
+
+    /// Schedule the account for hard deletion after `grace`.
+    /// Every refresh token of the user is revoked in the same transaction, and access tokens
+    /// issued until now are cut off, so all devices are signed out right away.
+    ///
+    /// # Errors
+    ///
+    /// - Not found user by id.
+    /// - DB connection error.
+    #[instrument(skip(db), fields())]
+    pub async fn schedule_account_deletion(
+        db: &DbConn,
+        user_id: i32,
+        grace: Duration,
+    ) -> Result<users::Model, DbErr> {
+        let txn = start_transaction(db).await?;
+        let now = Local::now().fixed_offset();
+
+        let user = Users::find_by_id(user_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("User Not Found".to_owned()))?;
+
+        let mut am = user.into_active_model();
+        am.deletion_scheduled_at = Set(Some(now + grace));
+        am.tokens_revoked_before = Set(Some(now));
+        am.updated_at = Set(now);
+        let user = am.update(&txn).await?;
+
+        let revoked = Self::revoke_refresh_tokens(&txn, user_id, now).await?;
+        info!(
+            "Account deletion scheduled at {:?}, revoked {} refresh tokens",
+            user.deletion_scheduled_at, revoked
+        );
+
+        commit_transaction(txn).await?;
+
+        Ok(user)
+    }
+
+    /// Disable the account, e.g. by an admin. Like a scheduled deletion it signs out every
+    /// device right away, and signing in is refused until the account is enabled again.
+    ///
+    /// # Errors
+    ///
+    /// - Not found user by id.
+    /// - DB connection error.
+    #[instrument(skip(db), fields())]
+    pub async fn disable_account(db: &DbConn, user_id: i32) -> Result<users::Model, DbErr> {
+        let txn = start_transaction(db).await?;
+        let now = Local::now().fixed_offset();
+
+        let user = Users::find_by_id(user_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("User Not Found".to_owned()))?;
+
+        let mut am = user.into_active_model();
+        am.disabled_at = Set(Some(now));
+        am.tokens_revoked_before = Set(Some(now));
+        am.updated_at = Set(now);
+        let user = am.update(&txn).await?;
+
+        let revoked = Self::revoke_refresh_tokens(&txn, user_id, now).await?;
+        info!("Account disabled, revoked {} refresh tokens", revoked);
+
+        commit_transaction(txn).await?;
+
+        Ok(user)
+    }
+
+    /// Allow a disabled account to sign in again. Tokens revoked on disabling stay revoked.
+    #[instrument(skip(db), fields())]
+    pub async fn enable_account(db: &DbConn, user_id: i32) -> Result<users::Model, DbErr> {
+        let updated = Users::update_many()
+            .col_expr(
+                users::Column::DisabledAt,
+                Expr::value(Option::<DateTimeWithTimeZone>::None),
+            )
+            .col_expr(
+                users::Column::UpdatedAt,
+                Expr::value(Local::now().fixed_offset()),
+            )
+            .filter(users::Column::Id.eq(user_id))
+            .exec_with_returning(db)
+            .await?;
+        updated
+            .into_iter()
+            .next()
+            .ok_or_else(|| DbErr::RecordNotFound("User Not Found".to_owned()))
+    }
+
+    /// Revoke every active refresh token of the user. Returns how many were revoked.
+    async fn revoke_refresh_tokens(
+        db: &impl ConnectionTrait,
+        user_id: i32,
+        now: DateTimeWithTimeZone,
+    ) -> Result<u64, DbErr> {
+        let revoked = UserTokens::update_many()
+            .col_expr(C::Revoked, Expr::value(true))
+            .col_expr(C::UpdatedAt, Expr::value(now))
+            .filter(C::UserId.eq(user_id))
+            .filter(C::Revoked.eq(false))
+            .exec(db)
+            .await?;
+        Ok(revoked.rows_affected)
+    }
+
+    /// Clear a pending account deletion, e.g. when the user signs in again during the grace
+    /// period.
+    #[instrument(skip(db), fields())]
+    pub async fn cancel_account_deletion(db: &DbConn, user_id: i32) -> Result<(), DbErr> {
+        let res = Users::update_many()
+            .col_expr(
+                users::Column::DeletionScheduledAt,
+                Expr::value(Option::<DateTimeWithTimeZone>::None),
+            )
+            .col_expr(
+                users::Column::UpdatedAt,
+                Expr::value(Local::now().fixed_offset()),
+            )
+            .filter(users::Column::Id.eq(user_id))
+            .exec(db)
+            .await?;
+        info!("Account deletion cancelled, rows: {}", res.rows_affected);
+        Ok(())
+    }
+
+    /// Set the IANA timezone used to work out the user's calendar days. Validate it first.
+    #[instrument(skip(db), fields())]
+    pub async fn update_timezone(
+        db: &DbConn,
+        user_id: i32,
+        timezone: String,
+    ) -> Result<users::Model, DbErr> {
+        let updated = Users::update_many()
+            .col_expr(users::Column::Timezone, Expr::value(timezone))
+            .col_expr(
+                users::Column::UpdatedAt,
+                Expr::value(Local::now().fixed_offset()),
+            )
+            .filter(users::Column::Id.eq(user_id))
+            .exec_with_returning(db)
+            .await?;
+        updated
+            .into_iter()
+            .next()
+            .ok_or_else(|| DbErr::RecordNotFound("User Not Found".to_owned()))
+    }
+
+    /// Hard delete every account whose deletion grace period has ended.
+    /// Pets, their records, tokens and oauth accounts go with it through cascading foreign keys.
+    /// Pets shared with anyone who stays are handed over to them first, promoting the member
+    /// who joined first to owner when no other owner is left.
+    ///
+    /// Each account is purged in its own transaction. One that fails is logged and left for the
+    /// next run, it doesn't hold back the others.
+    ///
+    /// Returns the number of deleted accounts.
+    #[instrument(skip(db), fields())]
+    pub async fn purge_scheduled_accounts(db: &DbConn) -> Result<u64, DbErr> {
+        let now = Local::now().fixed_offset();
+        let due: Vec<i32> = Users::find()
+            .select_only()
+            .column(users::Column::Id)
+            .filter(users::Column::DeletionScheduledAt.lte(now))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let mut purged = 0;
+        for user_id in due {
+            match Self::purge_account(db, user_id, now).await {
+                Ok(true) => purged += 1,
+                Ok(false) => info!("Deletion of user_id: {} was cancelled meanwhile", user_id),
+                Err(e) => error!("Failed to purge user_id: {}: {:?}", user_id, e),
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Hand over the shared pets of an account that is due, and delete it.
+    /// Returns `false` when its deletion was cancelled in the meantime.
+    async fn purge_account(
+        db: &DbConn,
+        user_id: i32,
+        now: DateTimeWithTimeZone,
+    ) -> Result<bool, DbErr> {
+        let txn = start_transaction(db).await?;
+
+        let due = Users::find_by_id(user_id)
+            .filter(users::Column::DeletionScheduledAt.lte(now))
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+        if due.is_none() {
+            return Ok(false);
+        }
+
+        let promoted = txn
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                PROMOTE_SUCCESSORS,
+                [user_id.into(), now.into()],
+            ))
+            .await?
+            .rows_affected();
+        if promoted > 0 {
+            info!("Promoted {} members to owner before purging", promoted);
+        }
+
+        let handed_over = txn
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                HAND_OVER_SHARED_PETS,
+                [user_id.into(), now.into()],
+            ))
+            .await?
+            .rows_affected();
+        if handed_over > 0 {
+            info!("Handed over {} shared pets before purging", handed_over);
+        }
+
+        Users::delete_by_id(user_id).exec(&txn).await?;
+
+        commit_transaction(txn).await?;
+        Ok(true)
+    }
+
+    /// Delete up to `batch_size` refresh tokens that expired, or were revoked, before `cutoff`.
+    /// Revoked tokens count from their `updated_at`, which is set when they are revoked.
+    ///
+    /// Returns the number of deleted tokens, less than `batch_size` once nothing is left.
+    #[instrument(skip(db), fields())]
+    pub async fn purge_stale_tokens(
+        db: &DbConn,
+        cutoff: DateTimeWithTimeZone,
+        batch_size: u64,
+    ) -> Result<u64, DbErr> {
+        let stale = Condition::any()
+            .add(C::ExpiresAt.lt(cutoff))
+            .add(C::Revoked.eq(true).and(C::UpdatedAt.lt(cutoff)));
+
+        let batch = Query::select()
+            .column(C::Id)
+            .from(UserTokens)
+            .cond_where(stale)
+            .limit(batch_size)
+            .to_owned();
+
+        let res = UserTokens::delete_many()
+            .filter(C::Id.in_subquery(batch))
+            .exec(db)
+            .await
+            .inspect_err(|e| error!("Failed to purge stale refresh tokens: {:?}", e))?;
+        Ok(res.rows_affected)
+    }
 }
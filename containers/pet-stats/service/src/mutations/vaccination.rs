@@ -0,0 +1,36 @@
+use entity::entities::{
+    sea_orm_active_enums::PetRole,
+    vaccinations::{self, Column as C, Entity as Vaccinations},
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter};
+use tracing::{info, instrument};
+
+use crate::utils::user_pet_ids;
+
+pub struct VaccinationMutation;
+
+impl VaccinationMutation {
+    #[instrument(skip(db))]
+    pub async fn add_vaccination(
+        db: &DbConn,
+        vaccination: vaccinations::ActiveModel,
+    ) -> Result<vaccinations::Model, DbErr> {
+        let vaccination = vaccination.insert(db).await?;
+        info!(
+            "Vaccination {} added for pet_id: {}",
+            vaccination.id, vaccination.pet_id
+        );
+        Ok(vaccination)
+    }
+
+    /// Delete a vaccination of one of the user's pets. Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_vaccination(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = Vaccinations::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
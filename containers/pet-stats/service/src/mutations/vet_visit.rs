@@ -0,0 +1,33 @@
+use entity::entities::{
+    sea_orm_active_enums::PetRole,
+    vet_visits::{self, Column as C, Entity as VetVisits},
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter};
+use tracing::{info, instrument};
+
+use crate::utils::user_pet_ids;
+
+pub struct VetVisitMutation;
+
+impl VetVisitMutation {
+    #[instrument(skip(db))]
+    pub async fn add_vet_visit(
+        db: &DbConn,
+        visit: vet_visits::ActiveModel,
+    ) -> Result<vet_visits::Model, DbErr> {
+        let visit = visit.insert(db).await?;
+        info!("Vet visit {} added for pet_id: {}", visit.id, visit.pet_id);
+        Ok(visit)
+    }
+
+    /// Delete a vet visit of one of the user's pets. Returns the number of deleted rows.
+    #[instrument(skip(db))]
+    pub async fn remove_vet_visit(db: &DbConn, user_id: i32, id: i32) -> Result<u64, DbErr> {
+        let res = VetVisits::delete_many()
+            .filter(C::Id.eq(id))
+            .filter(C::PetId.in_subquery(user_pet_ids(user_id, PetRole::Caretaker)))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::{
+    sea_orm_active_enums::PetRole,
+    walk_tracks::{self, Column as TrackColumn, Entity as WalkTracks},
+    work_records::{self, Column as C, Entity as WorkRecords},
+};
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, NotSet, QueryFilter,
+};
+use tracing::{info, instrument};
+
+use crate::queries::walk::{Walk, WalkQuery};
+use crate::utils::{commit_transaction, get_current_time, start_transaction};
+use crate::walk_track::Track;
+
+/// Store `seconds` in the interval column `work_records.time`.
+pub(crate) async fn set_time<Db: ConnectionTrait>(
+    db: &Db,
+    id: i32,
+    seconds: i64,
+) -> Result<(), DbErr> {
+    WorkRecords::update_many()
+        .col_expr(
+            C::Time,
+            Expr::cust_with_values("make_interval(secs => $1)", [seconds]),
+        )
+        .filter(C::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub struct WalkMutation;
+
+impl WalkMutation {
+    /// Log a walk of one of the user's pets. `walked_at` defaults to now.
+    #[instrument(skip(db))]
+    pub async fn add_walk(
+        db: &DbConn,
+        user_id: i32,
+        pet_id: i32,
+        distance_m: Option<i32>,
+        duration_minutes: Option<i32>,
+        walked_at: Option<DateTime<FixedOffset>>,
+    ) -> Result<Walk, DbErr> {
+        let txn = start_transaction(db).await?;
+
+        // `time` is an interval, which can't be bound as text through the entity.
+        let record = work_records::ActiveModel {
+            pet_id: Set(pet_id),
+            distance_m: Set(distance_m),
+            created_at: walked_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        };
+        let id = WorkRecords::insert(record).exec(&txn).await?.last_insert_id;
+        if let Some(minutes) = duration_minutes {
+            set_time(&txn, id, i64::from(minutes) * 60).await?;
+        }
+        let walk = WalkQuery::user_walk(&txn, user_id, PetRole::Caretaker, id).await?;
+
+        commit_transaction(txn).await?;
+        info!("Walk {} added for pet_id: {}", id, pet_id);
+        Ok(walk)
+    }
+
+    /// Attach a route to the walk, replacing any earlier one. The walk's distance, and time
+    /// when the route is timed, are set from the route.
+    #[instrument(skip(db, track))]
+    pub async fn attach_track(
+        db: &DbConn,
+        walk_id: i32,
+        track: Track,
+    ) -> Result<walk_tracks::Model, DbErr> {
+        let txn = start_transaction(db).await?;
+
+        let duration = track.duration_seconds();
+        let row = walk_tracks::ActiveModel {
+            work_record_id: Set(walk_id),
+            polyline: Set(track.polyline),
+            point_count: Set(track.point_count),
+            distance_m: Set(track.distance_m),
+            started_at: Set(track.started_at),
+            ended_at: Set(track.ended_at),
+            created_at: Set(get_current_time()),
+        };
+        let stored = WalkTracks::insert(row)
+            .on_conflict(
+                OnConflict::column(TrackColumn::WorkRecordId)
+                    .update_columns([
+                        TrackColumn::Polyline,
+                        TrackColumn::PointCount,
+                        TrackColumn::DistanceM,
+                        TrackColumn::StartedAt,
+                        TrackColumn::EndedAt,
+                        TrackColumn::CreatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(&txn)
+            .await?;
+
+        WorkRecords::update_many()
+            .col_expr(C::DistanceM, Expr::value(stored.distance_m))
+            .col_expr(C::UpdatedAt, Expr::value(get_current_time()))
+            .filter(C::Id.eq(walk_id))
+            .exec(&txn)
+            .await?;
+        if let Some(seconds) = duration {
+            set_time(&txn, walk_id, seconds).await?;
+        }
+
+        commit_transaction(txn).await?;
+        info!(
+            "Track of {} points attached to walk {}",
+            stored.point_count, walk_id
+        );
+        Ok(stored)
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::water_records;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DbConn, DbErr, NotSet};
+use tracing::{info, instrument};
+
+pub struct WaterRecordMutation;
+
+impl WaterRecordMutation {
+    /// Log water the pet drank. `drank_at` defaults to now.
+    #[instrument(skip(db))]
+    pub async fn add_water_record(
+        db: &DbConn,
+        pet_id: i32,
+        amount_ml: i32,
+        drank_at: Option<DateTime<FixedOffset>>,
+    ) -> Result<water_records::Model, DbErr> {
+        let record = water_records::ActiveModel {
+            pet_id: Set(pet_id),
+            amount_ml: Set(amount_ml),
+            created_at: drank_at.map(Set).unwrap_or(NotSet),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        info!("Water record {} added for pet_id: {}", record.id, pet_id);
+        Ok(record)
+    }
+}
@@ -0,0 +1,147 @@
+use entity::entities::{feed_records, foods, sea_orm_active_enums::FoodUnit};
+
+use crate::species::PetSpeciesType;
+
+/// kcal in `amount` of the food measured in `unit`. `None` when the food has no energy
+/// value for the unit.
+pub fn kcal(food: &foods::Model, amount: f32, unit: &FoodUnit) -> Option<f64> {
+    let amount = f64::from(amount);
+    match unit {
+        FoodUnit::Gram => food
+            .kcal_per_100g
+            .map(|kcal| f64::from(kcal) * amount / 100.0),
+        FoodUnit::Cup => food.kcal_per_cup.map(|kcal| f64::from(kcal) * amount),
+    }
+}
+
+/// `amount` of the food measured in `from` expressed in `to`, going through the kcal values
+/// when the units differ.
+pub fn convert(food: &foods::Model, amount: f64, from: &FoodUnit, to: &FoodUnit) -> Option<f64> {
+    if from == to {
+        return Some(amount);
+    }
+    let kcal_from = kcal(food, 1.0, from)?;
+    let kcal_to = kcal(food, 1.0, to).filter(|kcal| *kcal > 0.0)?;
+    Some(amount * kcal_from / kcal_to)
+}
+
+/// Energy a resting dog or cat needs per day in kcal, `70 × kg^0.75`. The formula is for
+/// mammals, other species have no target.
+pub fn resting_energy_requirement(species: PetSpeciesType, weight_kg: f32) -> Option<f64> {
+    match species {
+        PetSpeciesType::Dog | PetSpeciesType::Cat if weight_kg > 0.0 => {
+            Some(70.0 * f64::from(weight_kg).powf(0.75))
+        }
+        _ => None,
+    }
+}
+
+/// Calories eaten over a span of feedings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalorieIntake {
+    pub kcal: f64,
+    /// Feedings without a food, amount or known energy value, left out of `kcal`.
+    pub unknown_feedings: u32,
+}
+
+impl CalorieIntake {
+    pub fn of<'a>(
+        feedings: impl IntoIterator<Item = &'a (feed_records::Model, Option<foods::Model>)>,
+    ) -> Self {
+        feedings
+            .into_iter()
+            .fold(Self::default(), |mut intake, (record, food)| {
+                let kcal = match (food, record.amount, &record.unit) {
+                    (Some(food), Some(amount), Some(unit)) => kcal(food, amount, unit),
+                    _ => None,
+                };
+                match kcal {
+                    Some(kcal) => intake.kcal += kcal,
+                    None => intake.unknown_feedings += 1,
+                }
+                intake
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+
+    fn kibble() -> foods::Model {
+        foods::Model {
+            id: 1,
+            user_id: 1,
+            brand: "Acme".to_owned(),
+            name: "Adult Kibble".to_owned(),
+            kcal_per_100g: Some(360.0),
+            kcal_per_cup: Some(400.0),
+            unit: FoodUnit::Cup,
+            created_at: Local::now().fixed_offset(),
+        }
+    }
+
+    fn feeding(amount: Option<f32>, unit: Option<FoodUnit>) -> feed_records::Model {
+        let now = Local::now().fixed_offset();
+        feed_records::Model {
+            id: 1,
+            pet_id: 1,
+            amount,
+            created_at: now,
+            updated_at: now,
+            food_id: Some(1),
+            unit,
+        }
+    }
+
+    #[test]
+    fn test_kcal_per_unit() {
+        let mut food = kibble();
+        assert_eq!(kcal(&food, 50.0, &FoodUnit::Gram), Some(180.0));
+        assert_eq!(kcal(&food, 0.5, &FoodUnit::Cup), Some(200.0));
+
+        food.kcal_per_cup = None;
+        assert_eq!(kcal(&food, 1.0, &FoodUnit::Cup), None);
+    }
+
+    #[test]
+    fn test_convert_between_units() {
+        let mut food = kibble();
+        let grams = convert(&food, 1.0, &FoodUnit::Cup, &FoodUnit::Gram).unwrap();
+        assert!((grams - 111.1).abs() < 0.1);
+        assert_eq!(
+            convert(&food, 250.0, &FoodUnit::Gram, &FoodUnit::Gram),
+            Some(250.0)
+        );
+
+        food.kcal_per_100g = None;
+        assert_eq!(convert(&food, 1.0, &FoodUnit::Cup, &FoodUnit::Gram), None);
+    }
+
+    #[test]
+    fn test_resting_energy_requirement() {
+        let rer = resting_energy_requirement(PetSpeciesType::Dog, 10.0).unwrap();
+        assert!((rer - 393.6).abs() < 0.1);
+        assert_eq!(resting_energy_requirement(PetSpeciesType::Dog, 0.0), None);
+        assert_eq!(resting_energy_requirement(PetSpeciesType::Snake, 1.2), None);
+    }
+
+    #[test]
+    fn test_intake_counts_feedings_without_energy() {
+        let feedings = [
+            (feeding(Some(1.0), Some(FoodUnit::Cup)), Some(kibble())),
+            (feeding(Some(100.0), Some(FoodUnit::Gram)), Some(kibble())),
+            (feeding(None, Some(FoodUnit::Cup)), Some(kibble())),
+            (feeding(Some(1.0), None), None),
+        ];
+        assert_eq!(
+            CalorieIntake::of(&feedings),
+            CalorieIntake {
+                kcal: 760.0,
+                unknown_feedings: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{Days, NaiveDate};
+use entity::entities::{feed_records, food_purchases, foods};
+
+use crate::nutrition::convert;
+
+/// Days of feedings the daily use is averaged over.
+pub const USAGE_WINDOW_DAYS: u64 = 14;
+
+/// Estimated stock of a food, amounts are in the food's unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoodStock {
+    pub food: foods::Model,
+    pub bought: f64,
+    /// What was bought less what was fed since the first purchase, never below zero.
+    pub remaining: f64,
+    /// Average over the last [`USAGE_WINDOW_DAYS`] days.
+    pub daily_use: f64,
+    /// `None` while none of the food is being used, or when the stock lasts past the end of
+    /// the calendar.
+    pub runs_out_on: Option<NaiveDate>,
+    /// Feedings of the food without an amount, or in a unit it can't be converted from.
+    pub untracked_feedings: u32,
+}
+
+impl FoodStock {
+    /// Estimate the stock on `today` from the purchases of the food and the feedings of it
+    /// since, each feeding paired with the day it happened on.
+    pub fn estimate(
+        food: foods::Model,
+        purchases: &[food_purchases::Model],
+        feedings: &[(NaiveDate, feed_records::Model)],
+        today: NaiveDate,
+    ) -> Self {
+        let bought: f64 = purchases
+            .iter()
+            .filter_map(|purchase| {
+                convert(&food, f64::from(purchase.size), &purchase.unit, &food.unit)
+            })
+            .sum();
+        let since = purchases
+            .iter()
+            .map(|purchase| purchase.purchased_on)
+            .min()
+            .unwrap_or(today);
+        let window_start = since.max(
+            today
+                .checked_sub_days(Days::new(USAGE_WINDOW_DAYS - 1))
+                .unwrap_or(NaiveDate::MIN),
+        );
+
+        let mut used = 0.0;
+        let mut recently_used = 0.0;
+        let mut untracked_feedings = 0;
+        for (fed_on, feeding) in feedings.iter().filter(|(fed_on, _)| *fed_on >= since) {
+            let amount = match (feeding.amount, &feeding.unit) {
+                (Some(amount), Some(unit)) => convert(&food, f64::from(amount), unit, &food.unit),
+                _ => None,
+            };
+            let Some(amount) = amount else {
+                untracked_feedings += 1;
+                continue;
+            };
+            used += amount;
+            if *fed_on >= window_start && *fed_on <= today {
+                recently_used += amount;
+            }
+        }
+
+        let remaining = (bought - used).max(0.0);
+        let window_days = ((today - window_start).num_days() + 1).max(1);
+        let daily_use = recently_used / window_days as f64;
+        // The float to int cast saturates, so a huge stock stays within `u64`.
+        let runs_out_on = (daily_use > 0.0)
+            .then(|| today.checked_add_days(Days::new((remaining / daily_use).floor() as u64)))
+            .flatten();
+
+        Self {
+            food,
+            bought,
+            remaining,
+            daily_use,
+            runs_out_on,
+            untracked_feedings,
+        }
+    }
+
+    /// Whether the food runs out on or before `date` and should be bought again.
+    pub fn runs_low_by(&self, date: NaiveDate) -> bool {
+        self.runs_out_on.is_some_and(|on| on <= date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use entity::entities::sea_orm_active_enums::FoodUnit;
+
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn kibble() -> foods::Model {
+        foods::Model {
+            id: 1,
+            user_id: 1,
+            brand: "Acme".to_owned(),
+            name: "Adult Kibble".to_owned(),
+            kcal_per_100g: Some(360.0),
+            kcal_per_cup: Some(360.0),
+            unit: FoodUnit::Gram,
+            created_at: Local::now().fixed_offset(),
+        }
+    }
+
+    fn purchase(size: f32, purchased_on: NaiveDate) -> food_purchases::Model {
+        food_purchases::Model {
+            id: 1,
+            food_id: 1,
+            size,
+            unit: FoodUnit::Gram,
+            purchased_on,
+            created_at: Local::now().fixed_offset(),
+        }
+    }
+
+    fn feeding(
+        fed_on: NaiveDate,
+        amount: Option<f32>,
+        unit: FoodUnit,
+    ) -> (NaiveDate, feed_records::Model) {
+        let now = Local::now().fixed_offset();
+        (
+            fed_on,
+            feed_records::Model {
+                id: 1,
+                pet_id: 1,
+                amount,
+                created_at: now,
+                updated_at: now,
+                food_id: Some(1),
+                unit: Some(unit),
+            },
+        )
+    }
+
+    #[test]
+    fn test_stock_runs_out_at_recent_daily_use() {
+        let purchases = [purchase(2000.0, day(1)), purchase(1000.0, day(10))];
+        let mut feedings: Vec<_> = (1..=10)
+            .map(|d| feeding(day(d), Some(200.0), FoodUnit::Gram))
+            .collect();
+        // Fed before the first bag was bought, not taken from this stock.
+        feedings.push(feeding(
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+            Some(500.0),
+            FoodUnit::Gram,
+        ));
+        // One cup is 100g going by the kcal values.
+        feedings.push(feeding(day(10), Some(1.0), FoodUnit::Cup));
+        feedings.push(feeding(day(10), None, FoodUnit::Gram));
+
+        let stock = FoodStock::estimate(kibble(), &purchases, &feedings, day(10));
+        assert_eq!(stock.bought, 3000.0);
+        assert_eq!(stock.remaining, 900.0);
+        assert_eq!(stock.daily_use, 210.0);
+        assert_eq!(stock.runs_out_on, Some(day(14)));
+        assert_eq!(stock.untracked_feedings, 1);
+        assert!(stock.runs_low_by(day(14)));
+        assert!(!stock.runs_low_by(day(13)));
+    }
+
+    #[test]
+    fn test_unused_food_never_runs_out() {
+        let stock = FoodStock::estimate(kibble(), &[purchase(500.0, day(1))], &[], day(10));
+        assert_eq!(stock.remaining, 500.0);
+        assert_eq!(stock.runs_out_on, None);
+        assert!(!stock.runs_low_by(day(31)));
+    }
+
+    #[test]
+    fn test_stock_lasting_past_the_calendar_never_runs_out() {
+        let purchases = [purchase(f32::MAX, day(1))];
+        let feedings = [feeding(day(10), Some(f32::MIN_POSITIVE), FoodUnit::Gram)];
+
+        let stock = FoodStock::estimate(kibble(), &purchases, &feedings, day(10));
+        assert!(stock.daily_use > 0.0);
+        assert_eq!(stock.runs_out_on, None);
+
+        let stock = FoodStock::estimate(
+            kibble(),
+            &[purchase(1.0e6, NaiveDate::MAX)],
+            &[feeding(NaiveDate::MAX, Some(1.0), FoodUnit::Gram)],
+            NaiveDate::MAX,
+        );
+        assert_eq!(stock.runs_out_on, None);
+    }
+}
@@ -0,0 +1,275 @@
+use chrono::{Datelike, Months, NaiveDate};
+use entity::entities::{sea_orm_active_enums::DateDurationType, species};
+
+use crate::species::PetSpeciesType;
+
+/// Age of a pet as far as its birthday precision allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PetAge {
+    pub years: i32,
+    /// Months past `years`, unknown when only the birth year is known.
+    pub months: Option<i32>,
+    /// Days old, only known for full date birthdays.
+    pub days: Option<i64>,
+}
+
+impl PetAge {
+    /// Age as of `today`. A birthday in the future counts as newborn.
+    pub fn on(birthday: NaiveDate, precision: &DateDurationType, today: NaiveDate) -> Self {
+        match precision {
+            DateDurationType::FullDate => {
+                let mut months = months_between(birthday, today);
+                if today.day() < birthday.day() {
+                    months -= 1;
+                }
+                let months = months.max(0);
+                Self {
+                    years: months / 12,
+                    months: Some(months % 12),
+                    days: Some((today - birthday).num_days().max(0)),
+                }
+            }
+            DateDurationType::Month => {
+                let months = months_between(birthday, today).max(0);
+                Self {
+                    years: months / 12,
+                    months: Some(months % 12),
+                    days: None,
+                }
+            }
+            DateDurationType::Year => Self {
+                years: (today.year() - birthday.year()).max(0),
+                months: None,
+                days: None,
+            },
+        }
+    }
+
+    pub fn total_months(&self) -> i32 {
+        self.years * 12 + self.months.unwrap_or(0)
+    }
+
+    /// Whether only the birth year is known.
+    pub fn is_approximate(&self) -> bool {
+        self.months.is_none()
+    }
+
+    /// e.g. `3 years 2 months`, `12 days` or `about 3 years`.
+    pub fn describe(&self) -> String {
+        let Some(months) = self.months else {
+            return match self.years {
+                0 => "under a year".to_owned(),
+                years => format!("about {}", plural(years.into(), "year")),
+            };
+        };
+        match (self.years, months, self.days) {
+            (0, 0, Some(days)) => plural(days, "day"),
+            (0, 0, None) => "under a month".to_owned(),
+            (0, months, _) => plural(months.into(), "month"),
+            (years, 0, _) => plural(years.into(), "year"),
+            (years, months, _) => format!(
+                "{} {}",
+                plural(years.into(), "year"),
+                plural(months.into(), "month")
+            ),
+        }
+    }
+}
+
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    match n {
+        1 => format!("1 {}", unit),
+        n => format!("{} {}s", n, unit),
+    }
+}
+
+/// Days of `year` the pet celebrates its birthday on, as a `[start, end)` range.
+///
+/// A single day for full date birthdays, Feb 29 falling back to Feb 28 outside leap years,
+/// and the whole month when only the month is known. `None` when only the year is known.
+pub fn birthday_in(
+    birthday: NaiveDate,
+    precision: &DateDurationType,
+    year: i32,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let month_start = NaiveDate::from_ymd_opt(year, birthday.month(), 1)?;
+    match precision {
+        DateDurationType::FullDate => {
+            let day = birthday
+                .with_year(year)
+                .or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))?;
+            Some((day, day.succ_opt()?))
+        }
+        DateDurationType::Month => Some((month_start, month_start + Months::new(1))),
+        DateDurationType::Year => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeStage {
+    /// Puppy, kitten or juvenile.
+    Young,
+    Adult,
+    Senior,
+}
+
+/// `None` when the species has no known life stage thresholds.
+pub fn life_stage(species: &species::Model, age: &PetAge) -> Option<LifeStage> {
+    let (adult_from, senior_from) = (species.adult_from_months?, species.senior_from_months?);
+    Some(match age.total_months() {
+        months if months < adult_from => LifeStage::Young,
+        months if months < senior_from => LifeStage::Adult,
+        _ => LifeStage::Senior,
+    })
+}
+
+/// Equivalent human age in whole years, for dogs and cats only.
+///
+/// Both count 15 human years for the first year and 9 more for the second. After that a dog
+/// ages 5 years and a cat 4 years per year.
+pub fn human_equivalent_years(species: PetSpeciesType, age: &PetAge) -> Option<i32> {
+    let per_year_after_two = match species {
+        PetSpeciesType::Dog => 5,
+        PetSpeciesType::Cat => 4,
+        _ => return None,
+    };
+    let months = age.total_months();
+    let human_months = match months {
+        0..=12 => months * 15,
+        13..=24 => 12 * 15 + (months - 12) * 9,
+        _ => 12 * 24 + (months - 24) * per_year_after_two,
+    };
+    Some(human_months / 12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_age_respects_precision() {
+        let today = date(2026, 10, 19);
+
+        let full = PetAge::on(date(2023, 8, 20), &DateDurationType::FullDate, today);
+        assert_eq!((full.years, full.months), (3, Some(1)));
+        assert_eq!(full.describe(), "3 years 1 month");
+
+        let month = PetAge::on(date(2023, 8, 1), &DateDurationType::Month, today);
+        assert_eq!((month.years, month.months), (3, Some(2)));
+
+        let year = PetAge::on(date(2023, 1, 1), &DateDurationType::Year, today);
+        assert!(year.is_approximate());
+        assert_eq!(year.describe(), "about 3 years");
+    }
+
+    #[test]
+    fn test_young_pets_are_described_in_small_units() {
+        let today = date(2026, 10, 19);
+        let describe = |birthday, precision| PetAge::on(birthday, &precision, today).describe();
+
+        assert_eq!(
+            describe(date(2026, 10, 7), DateDurationType::FullDate),
+            "12 days"
+        );
+        assert_eq!(
+            describe(date(2026, 10, 1), DateDurationType::Month),
+            "under a month"
+        );
+        assert_eq!(
+            describe(date(2026, 5, 1), DateDurationType::Month),
+            "5 months"
+        );
+        assert_eq!(
+            describe(date(2026, 1, 1), DateDurationType::Year),
+            "under a year"
+        );
+        assert_eq!(
+            describe(date(2027, 1, 1), DateDurationType::FullDate),
+            "0 days"
+        );
+    }
+
+    #[test]
+    fn test_birthday_in_follows_precision() {
+        assert_eq!(
+            birthday_in(date(2020, 2, 29), &DateDurationType::FullDate, 2027),
+            Some((date(2027, 2, 28), date(2027, 3, 1)))
+        );
+        assert_eq!(
+            birthday_in(date(2020, 2, 29), &DateDurationType::FullDate, 2028),
+            Some((date(2028, 2, 29), date(2028, 3, 1)))
+        );
+        assert_eq!(
+            birthday_in(date(2020, 12, 1), &DateDurationType::Month, 2026),
+            Some((date(2026, 12, 1), date(2027, 1, 1)))
+        );
+        assert_eq!(
+            birthday_in(date(2020, 1, 1), &DateDurationType::Year, 2026),
+            None
+        );
+    }
+
+    #[test]
+    fn test_life_stage_thresholds_per_species() {
+        let species = |thresholds: Option<(i32, i32)>| species::Model {
+            id: 1,
+            code: None,
+            name: "Rabbit".to_owned(),
+            user_id: Some(1),
+            adult_from_months: thresholds.map(|(adult, _)| adult),
+            senior_from_months: thresholds.map(|(_, senior)| senior),
+            created_at: chrono::Local::now().fixed_offset(),
+        };
+        let age = |years| PetAge {
+            years,
+            months: Some(0),
+            days: None,
+        };
+        let dog = species(Some((12, 7 * 12)));
+        assert_eq!(life_stage(&dog, &age(0)), Some(LifeStage::Young));
+        assert_eq!(life_stage(&dog, &age(3)), Some(LifeStage::Adult));
+        assert_eq!(life_stage(&dog, &age(8)), Some(LifeStage::Senior));
+        assert_eq!(life_stage(&species(None), &age(8)), None);
+    }
+
+    #[test]
+    fn test_human_equivalent_age() {
+        let age = |years, months| PetAge {
+            years,
+            months: Some(months),
+            days: None,
+        };
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Dog, &age(1, 0)),
+            Some(15)
+        );
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Dog, &age(2, 0)),
+            Some(24)
+        );
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Dog, &age(10, 0)),
+            Some(64)
+        );
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Cat, &age(10, 0)),
+            Some(56)
+        );
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Cat, &age(0, 6)),
+            Some(7)
+        );
+        assert_eq!(
+            human_equivalent_years(PetSpeciesType::Snake, &age(10, 0)),
+            None
+        );
+    }
+}
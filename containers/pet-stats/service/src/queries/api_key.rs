@@ -0,0 +1,18 @@
+use entity::entities::api_keys::{self, Column as C, Entity as ApiKeys};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+pub struct ApiKeyQuery;
+
+impl ApiKeyQuery {
+    /// Keys of the user that haven't been revoked, newest first.
+    #[instrument(skip(db), fields())]
+    pub async fn active_by_user(db: &DbConn, user_id: i32) -> Result<Vec<api_keys::Model>, DbErr> {
+        ApiKeys::find()
+            .filter(C::UserId.eq(user_id))
+            .filter(C::RevokedAt.is_null())
+            .order_by_desc(C::CreatedAt)
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,48 @@
+use entity::entities::breeds::{self, Column as C, Entity as Breeds};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+/// Where a pet's weight sits against the typical range of its breed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightStatus {
+    Under,
+    Within,
+    Over,
+}
+
+/// `None` without a weight or when the breed has no known weight range.
+pub fn weight_status(weight_kg: Option<f32>, breed: &breeds::Model) -> Option<WeightStatus> {
+    let weight = weight_kg?;
+    match (breed.min_weight_kg, breed.max_weight_kg) {
+        (Some(min), _) if weight < min => Some(WeightStatus::Under),
+        (_, Some(max)) if weight > max => Some(WeightStatus::Over),
+        (None, None) => None,
+        _ => Some(WeightStatus::Within),
+    }
+}
+
+pub struct BreedQuery;
+
+impl BreedQuery {
+    /// Breed catalog, of one species when given, by name.
+    #[instrument(skip(db))]
+    pub async fn list(db: &DbConn, species_id: Option<i32>) -> Result<Vec<breeds::Model>, DbErr> {
+        let mut query = Breeds::find();
+        if let Some(species_id) = species_id {
+            query = query.filter(C::SpeciesId.eq(species_id));
+        }
+        query
+            .order_by_asc(C::SpeciesId)
+            .order_by_asc(C::Name)
+            .all(db)
+            .await
+    }
+
+    #[instrument(skip(db))]
+    pub async fn by_id(db: &DbConn, id: i32) -> Result<breeds::Model, DbErr> {
+        Breeds::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Breed Not Found".to_owned()))
+    }
+}
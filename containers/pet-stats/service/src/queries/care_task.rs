@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use entity::entities::{
+    care_task_completions::{self, Column as CompletionColumn, Entity as CareTaskCompletions},
+    care_tasks::{self, Column as C, Entity as CareTasks},
+    pets,
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    ColumnTrait, DbConn, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait,
+};
+use tracing::{instrument, warn};
+
+use crate::recurrence::Recurrence;
+use crate::utils::user_pet_ids;
+
+/// Completions listed per task.
+const COMPLETION_HISTORY_LIMIT: u64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CareTaskStatus {
+    Overdue,
+    DueToday,
+    Upcoming,
+}
+
+impl CareTaskStatus {
+    pub fn of(due_on: NaiveDate, today: NaiveDate) -> Self {
+        match due_on.cmp(&today) {
+            std::cmp::Ordering::Less => Self::Overdue,
+            std::cmp::Ordering::Equal => Self::DueToday,
+            std::cmp::Ordering::Greater => Self::Upcoming,
+        }
+    }
+}
+
+/// The next open occurrence of a care task.
+#[derive(Debug, Clone)]
+pub struct CareAgendaItem {
+    pub care_task: care_tasks::Model,
+    pub due_on: NaiveDate,
+    pub status: CareTaskStatus,
+}
+
+pub struct CareTaskQuery;
+
+impl CareTaskQuery {
+    #[instrument(skip(db))]
+    pub async fn by_pet(db: &DbConn, pet_id: i32) -> Result<Vec<care_tasks::Model>, DbErr> {
+        CareTasks::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_asc(C::Title)
+            .all(db)
+            .await
+    }
+
+    /// The care task, if it belongs to a pet the user has at least `min_role` on.
+    #[instrument(skip(db))]
+    pub async fn user_care_task(
+        db: &DbConn,
+        user_id: i32,
+        min_role: PetRole,
+        id: i32,
+    ) -> Result<care_tasks::Model, DbErr> {
+        CareTasks::find_by_id(id)
+            .join(JoinType::InnerJoin, care_tasks::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Care Task Not Found".to_owned()))
+    }
+
+    /// Latest completions of a task, most recent occurrence first.
+    #[instrument(skip(db))]
+    pub async fn completions(
+        db: &DbConn,
+        care_task_id: i32,
+    ) -> Result<Vec<care_task_completions::Model>, DbErr> {
+        CareTaskCompletions::find()
+            .filter(CompletionColumn::CareTaskId.eq(care_task_id))
+            .order_by_desc(CompletionColumn::DueOn)
+            .limit(COMPLETION_HISTORY_LIMIT)
+            .all(db)
+            .await
+    }
+
+    /// Occurrence date of the latest completion of each task that has one.
+    #[instrument(skip(db))]
+    pub async fn last_completed_on(
+        db: &DbConn,
+        care_task_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, NaiveDate>, DbErr> {
+        if care_task_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let rows: Vec<(i32, Option<NaiveDate>)> = CareTaskCompletions::find()
+            .select_only()
+            .column(CompletionColumn::CareTaskId)
+            .column_as(CompletionColumn::DueOn.max(), "due_on")
+            .filter(CompletionColumn::CareTaskId.is_in(care_task_ids))
+            .group_by(CompletionColumn::CareTaskId)
+            .into_tuple()
+            .all(db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, due_on)| Some((id, due_on?)))
+            .collect())
+    }
+
+    /// Next open occurrence of every care task of the user due on or before `until`,
+    /// soonest first.
+    ///
+    /// An occurrence is open until it or a later one is completed, so a task missed for a
+    /// while shows up once, overdue since the first missed occurrence.
+    #[instrument(skip(db))]
+    pub async fn agenda(
+        db: &DbConn,
+        user_id: i32,
+        today: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<CareAgendaItem>, DbErr> {
+        let care_tasks = CareTasks::find()
+            .join(JoinType::InnerJoin, care_tasks::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(C::StartsOn.lte(until))
+            .all(db)
+            .await?;
+
+        let last_completed =
+            Self::last_completed_on(db, care_tasks.iter().map(|task| task.id).collect()).await?;
+
+        let mut agenda: Vec<_> = care_tasks
+            .into_iter()
+            .filter_map(|care_task| {
+                let recurrence = match care_task.rrule.parse::<Recurrence>() {
+                    Ok(recurrence) => recurrence,
+                    Err(e) => {
+                        warn!("Skipping care task {}: {}", care_task.id, e);
+                        return None;
+                    }
+                };
+                let due_on = recurrence
+                    .next_after(
+                        care_task.starts_on,
+                        last_completed.get(&care_task.id).copied(),
+                    )
+                    .filter(|due_on| *due_on <= until)?;
+                Some(CareAgendaItem {
+                    status: CareTaskStatus::of(due_on, today),
+                    due_on,
+                    care_task,
+                })
+            })
+            .collect();
+        agenda.sort_by_key(|item| (item.due_on, item.care_task.pet_id, item.care_task.id));
+        Ok(agenda)
+    }
+}
@@ -0,0 +1,22 @@
+use entity::entities::devices::{self, Column as C, Entity as Devices};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+pub struct DeviceQuery;
+
+impl DeviceQuery {
+    #[instrument(skip(db), fields())]
+    pub async fn by_uid(db: &DbConn, uid: &str) -> Result<Option<devices::Model>, DbErr> {
+        Devices::find().filter(C::Uid.eq(uid)).one(db).await
+    }
+
+    /// Devices of the user, newest first.
+    #[instrument(skip(db), fields())]
+    pub async fn by_user(db: &DbConn, user_id: i32) -> Result<Vec<devices::Model>, DbErr> {
+        Devices::find()
+            .filter(C::UserId.eq(user_id))
+            .order_by_desc(C::CreatedAt)
+            .all(db)
+            .await
+    }
+}
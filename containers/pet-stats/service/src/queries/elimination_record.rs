@@ -0,0 +1,67 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use entity::entities::elimination_records::{self, Column as C, Entity as EliminationRecords};
+use sea_orm::{
+    ColumnTrait, DbBackend, DbConn, DbErr, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Statement,
+};
+use tracing::instrument;
+
+use crate::schedule::parse_timezone;
+
+/// Eliminations per calendar day in the timezone `$2`.
+const DAILY: &str = r#"SELECT ("created_at" AT TIME ZONE $2)::date AS "day",
+    COUNT(*) FILTER (WHERE "kind" = 'Urine') AS "urinations",
+    COUNT(*) FILTER (WHERE "kind" = 'Stool') AS "stools",
+    AVG("consistency")::float8 AS "average_consistency"
+FROM "elimination_records"
+WHERE "pet_id" = $1 AND "created_at" >= $3 AND "created_at" < $4
+GROUP BY "day"
+ORDER BY "day""#;
+
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct DailyElimination {
+    pub day: NaiveDate,
+    pub urinations: i64,
+    pub stools: i64,
+    /// Of the stools that were scored.
+    pub average_consistency: Option<f64>,
+}
+
+pub struct EliminationRecordQuery;
+
+impl EliminationRecordQuery {
+    /// Latest eliminations of the pet first.
+    #[instrument(skip(db))]
+    pub async fn recent_by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        limit: u64,
+    ) -> Result<Vec<elimination_records::Model>, DbErr> {
+        EliminationRecords::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await
+    }
+
+    /// Days from `from` up to `until` with at least one elimination, in order. Days are
+    /// those of `timezone`, unknown names count as UTC.
+    #[instrument(skip(db))]
+    pub async fn daily(
+        db: &DbConn,
+        pet_id: i32,
+        timezone: &str,
+        from: DateTime<FixedOffset>,
+        until: DateTime<FixedOffset>,
+    ) -> Result<Vec<DailyElimination>, DbErr> {
+        let timezone = parse_timezone(timezone).map_or("UTC", |_| timezone);
+        DailyElimination::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            DAILY,
+            [pet_id.into(), timezone.into(), from.into(), until.into()],
+        ))
+        .all(db)
+        .await
+    }
+}
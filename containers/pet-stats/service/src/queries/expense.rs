@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+use entity::entities::{
+    expenses::{self, Column as C, Entity as Expenses},
+    pets,
+    sea_orm_active_enums::{ExpenseCategory, PetRole},
+};
+use sea_orm::{
+    prelude::Decimal,
+    sea_query::{Expr, SimpleExpr},
+    ColumnTrait, DbConn, DbErr, EntityTrait, FromQueryResult, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+use tracing::instrument;
+
+use crate::utils::user_pet_ids;
+
+/// What an expense report is broken down by, on top of the currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpenseGrouping {
+    Month,
+    Category,
+    Pet,
+}
+
+/// One row of an expense report. Dimensions the report is not grouped by are `None`.
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct ExpenseTotal {
+    /// First day of the month.
+    pub month: Option<NaiveDate>,
+    pub category: Option<ExpenseCategory>,
+    pub pet_id: Option<i32>,
+    pub currency: String,
+    pub total: Decimal,
+    pub count: i64,
+}
+
+pub struct ExpenseQuery;
+
+impl ExpenseQuery {
+    /// Expenses of the pet spent between `from` and `until`, both inclusive and both
+    /// optional, latest first.
+    #[instrument(skip(db))]
+    pub async fn by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        from: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Result<Vec<expenses::Model>, DbErr> {
+        let mut query = Expenses::find().filter(C::PetId.eq(pet_id));
+        if let Some(from) = from {
+            query = query.filter(C::SpentOn.gte(from));
+        }
+        if let Some(until) = until {
+            query = query.filter(C::SpentOn.lte(until));
+        }
+        query
+            .order_by_desc(C::SpentOn)
+            .order_by_desc(C::Id)
+            .all(db)
+            .await
+    }
+
+    /// The expense, if it belongs to a pet the user has at least `min_role` on.
+    #[instrument(skip(db))]
+    pub async fn user_expense(
+        db: &DbConn,
+        user_id: i32,
+        min_role: PetRole,
+        id: i32,
+    ) -> Result<expenses::Model, DbErr> {
+        Expenses::find_by_id(id)
+            .join(JoinType::InnerJoin, expenses::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Expense Not Found".to_owned()))
+    }
+
+    /// Totals of the user's expenses spent between `from` and `until`, both inclusive,
+    /// summed up by the database per currency and each of `group_by`.
+    #[instrument(skip(db))]
+    pub async fn report(
+        db: &DbConn,
+        user_id: i32,
+        from: NaiveDate,
+        until: NaiveDate,
+        pet_id: Option<i32>,
+        group_by: &[ExpenseGrouping],
+    ) -> Result<Vec<ExpenseTotal>, DbErr> {
+        let mut query = Expenses::find()
+            .select_only()
+            .join(JoinType::InnerJoin, expenses::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(C::SpentOn.between(from, until));
+        if let Some(pet_id) = pet_id {
+            query = query.filter(C::PetId.eq(pet_id));
+        }
+
+        let dimensions: [(ExpenseGrouping, &str, SimpleExpr, &str); 3] = [
+            (
+                ExpenseGrouping::Month,
+                "month",
+                Expr::cust(r#"date_trunc('month', "expenses"."spent_on")::date"#),
+                "NULL::date",
+            ),
+            (
+                ExpenseGrouping::Category,
+                "category",
+                Expr::cust(r#"CAST("expenses"."category" AS text)"#),
+                "NULL::text",
+            ),
+            (
+                ExpenseGrouping::Pet,
+                "pet_id",
+                Expr::col((Expenses, C::PetId)).into(),
+                "NULL::int",
+            ),
+        ];
+        for (grouping, alias, expr, null) in dimensions {
+            if group_by.contains(&grouping) {
+                query = query
+                    .column_as(expr.clone(), alias)
+                    .group_by(expr.clone())
+                    .order_by_asc(expr);
+            } else {
+                query = query.column_as(Expr::cust(null), alias);
+            }
+        }
+
+        query
+            .column(C::Currency)
+            .column_as(C::Amount.sum(), "total")
+            .column_as(C::Id.count(), "count")
+            .group_by(C::Currency)
+            .order_by_asc(C::Currency)
+            .into_model::<ExpenseTotal>()
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::{
+    feed_records::{self, Column as C, Entity as FeedRecords},
+    foods::{self, Entity as Foods},
+};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use tracing::instrument;
+
+pub struct FeedRecordQuery;
+
+impl FeedRecordQuery {
+    /// Latest feedings of the pet first.
+    #[instrument(skip(db))]
+    pub async fn recent_by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        limit: u64,
+    ) -> Result<Vec<feed_records::Model>, DbErr> {
+        FeedRecords::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await
+    }
+
+    /// Feedings of the pet from `from` up to `until`, with the food they were of.
+    #[instrument(skip(db))]
+    pub async fn with_food_between(
+        db: &DbConn,
+        pet_id: i32,
+        from: DateTime<FixedOffset>,
+        until: DateTime<FixedOffset>,
+    ) -> Result<Vec<(feed_records::Model, Option<foods::Model>)>, DbErr> {
+        FeedRecords::find()
+            .find_also_related(Foods)
+            .filter(C::PetId.eq(pet_id))
+            .filter(C::CreatedAt.gte(from))
+            .filter(C::CreatedAt.lt(until))
+            .order_by_asc(C::CreatedAt)
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,49 @@
+use entity::entities::{
+    food_purchases::{self, Entity as FoodPurchases},
+    foods::{self, Column as C, Entity as Foods},
+};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+pub struct FoodQuery;
+
+impl FoodQuery {
+    /// Foods of the user by brand and name.
+    #[instrument(skip(db))]
+    pub async fn by_user(db: &DbConn, user_id: i32) -> Result<Vec<foods::Model>, DbErr> {
+        Foods::find()
+            .filter(C::UserId.eq(user_id))
+            .order_by_asc(C::Brand)
+            .order_by_asc(C::Name)
+            .all(db)
+            .await
+    }
+
+    /// The food, if it belongs to the user.
+    #[instrument(skip(db))]
+    pub async fn user_food(db: &DbConn, user_id: i32, id: i32) -> Result<foods::Model, DbErr> {
+        Foods::find_by_id(id)
+            .filter(C::UserId.eq(user_id))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Food Not Found".to_owned()))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn by_id(db: &DbConn, id: i32) -> Result<foods::Model, DbErr> {
+        Foods::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Food Not Found".to_owned()))
+    }
+
+    /// Purchases of the food, latest first.
+    #[instrument(skip(db))]
+    pub async fn purchases(db: &DbConn, food_id: i32) -> Result<Vec<food_purchases::Model>, DbErr> {
+        FoodPurchases::find()
+            .filter(food_purchases::Column::FoodId.eq(food_id))
+            .order_by_desc(food_purchases::Column::PurchasedOn)
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, FixedOffset};
+use entity::entities::{
+    journal_entries::{self, Column as C, Entity as JournalEntries},
+    pets,
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DbBackend, DbConn, DbErr, EntityTrait, FromQueryResult, JoinType,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Statement, Value,
+};
+use tracing::instrument;
+
+use crate::utils::user_pet_ids;
+
+/// How often each symptom was noted, most frequent first.
+const SYMPTOM_FREQUENCY: &str = r#"SELECT "symptom", COUNT(*) AS "entries", MAX("noted_at") AS "last_noted_at"
+FROM "journal_entries", unnest("symptoms") AS "symptom"
+WHERE "pet_id" = $1 AND "noted_at" >= $2 AND "noted_at" < $3
+GROUP BY "symptom"
+ORDER BY "entries" DESC, "symptom" ASC"#;
+
+/// A symptom with the number of journal entries noting it.
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct SymptomCount {
+    pub symptom: String,
+    pub entries: i64,
+    pub last_noted_at: DateTime<FixedOffset>,
+}
+
+pub struct JournalQuery;
+
+impl JournalQuery {
+    /// Journal of the pet noted from `from` up to `until`, latest first. With `symptoms`,
+    /// only entries noting every one of them.
+    #[instrument(skip(db))]
+    pub async fn by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        symptoms: Vec<String>,
+        from: Option<DateTime<FixedOffset>>,
+        until: Option<DateTime<FixedOffset>>,
+    ) -> Result<Vec<journal_entries::Model>, DbErr> {
+        let mut query = JournalEntries::find().filter(C::PetId.eq(pet_id));
+        if !symptoms.is_empty() {
+            query = query.filter(Expr::cust_with_values(
+                r#""journal_entries"."symptoms" @> $1"#,
+                [Value::from(symptoms)],
+            ));
+        }
+        if let Some(from) = from {
+            query = query.filter(C::NotedAt.gte(from));
+        }
+        if let Some(until) = until {
+            query = query.filter(C::NotedAt.lt(until));
+        }
+        query
+            .order_by_desc(C::NotedAt)
+            .order_by_desc(C::Id)
+            .all(db)
+            .await
+    }
+
+    /// The entry, if it is about a pet the user has at least `min_role` on.
+    #[instrument(skip(db))]
+    pub async fn user_entry(
+        db: &DbConn,
+        user_id: i32,
+        min_role: PetRole,
+        id: i32,
+    ) -> Result<journal_entries::Model, DbErr> {
+        JournalEntries::find_by_id(id)
+            .join(JoinType::InnerJoin, journal_entries::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Journal Entry Not Found".to_owned()))
+    }
+
+    /// Symptoms noted for the pet from `from` up to `until`, counted by the database.
+    #[instrument(skip(db))]
+    pub async fn symptom_frequency(
+        db: &DbConn,
+        pet_id: i32,
+        from: DateTime<FixedOffset>,
+        until: DateTime<FixedOffset>,
+    ) -> Result<Vec<SymptomCount>, DbErr> {
+        SymptomCount::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SYMPTOM_FREQUENCY,
+            [pet_id.into(), from.into(), until.into()],
+        ))
+        .all(db)
+        .await
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use entity::entities::{
+    medication_doses::{self, Column as DoseColumn, Entity as MedicationDoses},
+    medications::{self, Column as C, Entity as Medications},
+    pets,
+    sea_orm_active_enums::PetRole,
+};
+use sea_orm::{
+    ColumnTrait, Condition, DbConn, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+use tracing::instrument;
+
+use crate::schedule::medication_due_on;
+use crate::utils::user_pet_ids;
+
+/// One dose due on a day, with what was logged for it so far.
+#[derive(Debug, Clone)]
+pub struct DueDose {
+    pub medication: medications::Model,
+    pub dose_number: i32,
+    pub logged: Option<medication_doses::Model>,
+}
+
+pub struct MedicationQuery;
+
+impl MedicationQuery {
+    #[instrument(skip(db))]
+    pub async fn by_pet(db: &DbConn, pet_id: i32) -> Result<Vec<medications::Model>, DbErr> {
+        Medications::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::StartOn)
+            .all(db)
+            .await
+    }
+
+    /// The medication, if it belongs to a pet the user has at least `min_role` on.
+    #[instrument(skip(db))]
+    pub async fn user_medication(
+        db: &DbConn,
+        user_id: i32,
+        min_role: PetRole,
+        id: i32,
+    ) -> Result<medications::Model, DbErr> {
+        Medications::find_by_id(id)
+            .join(JoinType::InnerJoin, medications::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Medication Not Found".to_owned()))
+    }
+
+    /// Every dose due on `date` across the user's pets, ordered by pet then medication.
+    #[instrument(skip(db))]
+    pub async fn due_doses(
+        db: &DbConn,
+        user_id: i32,
+        date: NaiveDate,
+    ) -> Result<Vec<DueDose>, DbErr> {
+        let running = Medications::find()
+            .join(JoinType::InnerJoin, medications::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(C::StartOn.lte(date))
+            .filter(
+                Condition::any()
+                    .add(C::EndOn.is_null())
+                    .add(C::EndOn.gte(date)),
+            )
+            .order_by_asc(C::PetId)
+            .order_by_asc(C::Id)
+            .all(db)
+            .await?;
+
+        let due: Vec<_> = running
+            .into_iter()
+            .filter(|medication| medication_due_on(medication, date))
+            .collect();
+        if due.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let logged = MedicationDoses::find()
+            .filter(DoseColumn::MedicationId.is_in(due.iter().map(|m| m.id)))
+            .filter(DoseColumn::ScheduledOn.eq(date))
+            .all(db)
+            .await?;
+
+        Ok(due
+            .into_iter()
+            .flat_map(|medication| {
+                (1..=medication.doses_per_day)
+                    .map(move |dose_number| (medication.clone(), dose_number))
+            })
+            .map(|(medication, dose_number)| DueDose {
+                logged: logged
+                    .iter()
+                    .find(|l| l.medication_id == medication.id && l.dose_number == dose_number)
+                    .cloned(),
+                medication,
+                dose_number,
+            })
+            .collect())
+    }
+}
@@ -1,2 +1,23 @@
+pub mod api_key;
+pub mod breed;
+pub mod care_task;
+pub mod device;
+pub mod elimination_record;
+pub mod expense;
+pub mod feed_record;
+pub mod food;
+pub mod journal;
+pub mod medication;
+pub mod pantry;
 pub mod pet;
+pub mod pet_member;
+pub mod search;
+pub mod security_event;
+pub mod species;
+pub mod token_revocation;
+pub mod two_factor;
 pub mod user;
+pub mod vaccination;
+pub mod vet_visit;
+pub mod walk;
+pub mod water_record;
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use entity::entities::{
+    feed_records::{self, Entity as FeedRecords},
+    food_purchases::{self, Entity as FoodPurchases},
+    foods::{self, Entity as Foods},
+};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+use crate::pantry::FoodStock;
+use crate::schedule::{day_bounds_in, parse_timezone};
+
+pub struct PantryQuery;
+
+impl PantryQuery {
+    /// Estimated stock of every food the user bought, by brand and name. Days are the
+    /// user's, in `timezone`.
+    #[instrument(skip(db))]
+    pub async fn stock(
+        db: &DbConn,
+        user_id: i32,
+        timezone: &str,
+        today: NaiveDate,
+    ) -> Result<Vec<FoodStock>, DbErr> {
+        let purchases = FoodPurchases::find()
+            .find_also_related(Foods)
+            .filter(foods::Column::UserId.eq(user_id))
+            .order_by_asc(food_purchases::Column::PurchasedOn)
+            .all(db)
+            .await?;
+
+        let mut bought: BTreeMap<i32, (foods::Model, Vec<food_purchases::Model>)> = BTreeMap::new();
+        for (purchase, food) in purchases {
+            let Some(food) = food else { continue };
+            bought
+                .entry(food.id)
+                .or_insert_with(|| (food, Vec::new()))
+                .1
+                .push(purchase);
+        }
+        let Some(since) = bought
+            .values()
+            .filter_map(|(_, purchases)| purchases.first())
+            .map(|purchase| purchase.purchased_on)
+            .min()
+        else {
+            return Ok(Vec::new());
+        };
+
+        let tz = parse_timezone(timezone).unwrap_or(Tz::UTC);
+        let (since, _) = day_bounds_in(timezone, since)
+            .ok_or_else(|| DbErr::Custom("Purchase date out of range".to_owned()))?;
+        let feedings = FeedRecords::find()
+            .filter(feed_records::Column::FoodId.is_in(bought.keys().copied()))
+            .filter(feed_records::Column::CreatedAt.gte(since))
+            .all(db)
+            .await?;
+        let mut fed: BTreeMap<i32, Vec<(NaiveDate, feed_records::Model)>> = BTreeMap::new();
+        for feeding in feedings {
+            let Some(food_id) = feeding.food_id else {
+                continue;
+            };
+            let fed_on = feeding.created_at.with_timezone(&tz).date_naive();
+            fed.entry(food_id).or_default().push((fed_on, feeding));
+        }
+
+        let mut stock: Vec<_> = bought
+            .into_values()
+            .map(|(food, purchases)| {
+                let feedings = fed.remove(&food.id).unwrap_or_default();
+                FoodStock::estimate(food, &purchases, &feedings, today)
+            })
+            .collect();
+        stock.sort_by(|a, b| (&a.food.brand, &a.food.name).cmp(&(&b.food.brand, &b.food.name)));
+        Ok(stock)
+    }
+}
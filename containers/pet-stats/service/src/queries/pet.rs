@@ -1,14 +1,56 @@
-use entity::entities::{pets, pets::Model as Pet};
-use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, PaginatorTrait, QueryFilter};
+use chrono::{Datelike, NaiveDate};
+use entity::entities::{
+    pets,
+    pets::Model as Pet,
+    sea_orm_active_enums::{DateDurationType, PetRole},
+};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DbConn, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Value,
+};
 use tracing::{error, info, instrument};
 
+use crate::pet_age::birthday_in;
+use crate::utils::user_pet_ids;
+
+/// Range of dates the pet may have been born on, as precise as `birthday_precision`.
+const BIRTH_RANGE: &str = r#"make_birth_range(
+    EXTRACT(YEAR FROM "pets"."birthday")::int,
+    CASE WHEN "pets"."birthday_precision" <> 'Year' THEN EXTRACT(MONTH FROM "pets"."birthday")::int END,
+    CASE WHEN "pets"."birthday_precision" = 'FullDate' THEN EXTRACT(DAY FROM "pets"."birthday")::int END
+)"#;
+
+/// `make_birth_range` of the pet's birthday moved to the year bound to `$1`, a Feb 29
+/// birthday is celebrated on Feb 28 outside leap years. Only meaningful when at least the
+/// month is known.
+const BIRTHDAY_IN_YEAR_RANGE: &str = r#"make_birth_range(
+    $1,
+    EXTRACT(MONTH FROM "pets"."birthday")::int,
+    CASE WHEN "pets"."birthday_precision" = 'FullDate' THEN
+        LEAST(
+            EXTRACT(DAY FROM "pets"."birthday")::int,
+            EXTRACT(DAY FROM (make_date($1, EXTRACT(MONTH FROM "pets"."birthday")::int, 1) + INTERVAL '1 month - 1 day'))::int
+        )
+    END
+)"#;
+
+/// A birthday coming up, see [`PetQuery::upcoming_birthdays`].
+#[derive(Debug, Clone)]
+pub struct UpcomingBirthday {
+    pub pet: Pet,
+    /// The birthday, or the first of the month when only the birth month is known.
+    pub on: NaiveDate,
+    /// Age in years the pet turns.
+    pub turning: i32,
+}
+
 pub struct PetQuery;
 
 impl PetQuery {
     #[instrument(skip(db))]
     pub async fn get_pets_by_user_id(db: &DbConn, user_id: i32) -> Result<Vec<Pet>, DbErr> {
         pets::Entity::find()
-            .filter(pets::Column::UserId.eq(user_id))
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
             .all(db)
             .await
             .inspect(|ps| info!("Found user: {:?} pets count: {:?}", user_id, ps.len()))
@@ -18,7 +60,7 @@ impl PetQuery {
     #[instrument(skip(db))]
     pub async fn count_pets_by_user_id(db: &DbConn, user_id: i32) -> Result<u64, DbErr> {
         pets::Entity::find()
-            .filter(pets::Column::UserId.eq(user_id))
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
             .count(db)
             .await
             .inspect(|n| info!("Found user: {:?} pets count: {:?}", user_id, n))
@@ -40,4 +82,109 @@ impl PetQuery {
             .inspect_err(|e| error!("Error occur: {:?}", e))?
             .ok_or_else(|| DbErr::RecordNotFound("Pet Not Found".to_owned()))
     }
+
+    /// The pet, if `user_id` is a member of it with at least `min_role`. Pets the user isn't
+    /// a member of, or only with a lower role, are reported as not found.
+    #[instrument(skip(db))]
+    pub async fn get_user_pet(
+        db: &DbConn,
+        user_id: i32,
+        pet_id: i32,
+        min_role: PetRole,
+    ) -> Result<Pet, DbErr> {
+        pets::Entity::find_by_id(pet_id)
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .one(db)
+            .await
+            .inspect_err(|e| error!("Error occur: {:?}", e))?
+            .ok_or_else(|| DbErr::RecordNotFound("Pet Not Found".to_owned()))
+    }
+
+    /// Pets of the user that may have been born in `year`, or in `month` of it when given.
+    /// A pet only known to be born in 2020 matches any month of 2020.
+    #[instrument(skip(db))]
+    pub async fn possibly_born_in(
+        db: &DbConn,
+        user_id: i32,
+        year: i32,
+        month: Option<u32>,
+    ) -> Result<Vec<Pet>, DbErr> {
+        pets::Entity::find()
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(Expr::cust_with_values(
+                format!("{} && make_birth_range($1, $2)", BIRTH_RANGE),
+                [Value::from(year), Value::from(month.map(|m| m as i32))],
+            ))
+            .order_by_asc(pets::Column::Birthday)
+            .all(db)
+            .await
+    }
+
+    /// Pets of the user with a birthday in `month` of `year`. Pets with only a known birth
+    /// year have no birthday to celebrate and are left out.
+    #[instrument(skip(db))]
+    pub async fn birthdays_in_month(
+        db: &DbConn,
+        user_id: i32,
+        year: i32,
+        month: u32,
+    ) -> Result<Vec<Pet>, DbErr> {
+        pets::Entity::find()
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(pets::Column::BirthdayPrecision.ne(DateDurationType::Year))
+            .filter(Expr::cust_with_values(
+                format!("{} && make_birth_range($1, $2)", BIRTHDAY_IN_YEAR_RANGE),
+                [year, month as i32],
+            ))
+            .all(db)
+            .await
+    }
+
+    /// Birthdays of the user's pets falling between `from` and `until`, both inclusive,
+    /// soonest first. A birthday only known by month is upcoming for the whole month.
+    #[instrument(skip(db))]
+    pub async fn upcoming_birthdays(
+        db: &DbConn,
+        user_id: i32,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<UpcomingBirthday>, DbErr> {
+        let years = from.year()..=until.year();
+        let in_window = years.clone().fold(Condition::any(), |cond, year| {
+            cond.add(Expr::cust_with_values(
+                format!("{} && daterange($2, $3, '[]')", BIRTHDAY_IN_YEAR_RANGE),
+                [Value::from(year), Value::from(from), Value::from(until)],
+            ))
+        });
+
+        let pets = pets::Entity::find()
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(pets::Column::BirthdayPrecision.ne(DateDurationType::Year))
+            .filter(in_window)
+            .all(db)
+            .await?;
+
+        let mut upcoming: Vec<_> = pets
+            .into_iter()
+            .filter_map(|pet| {
+                // The birth date itself is no birthday.
+                let first_birthday = pet.birthday.year() + 1;
+                let (year, on) = years
+                    .clone()
+                    .filter(|year| *year >= first_birthday)
+                    .find_map(|year| {
+                        birthday_in(pet.birthday, &pet.birthday_precision, year)
+                            .filter(|(start, end)| *start <= until && *end > from)
+                            .map(|(start, _)| (year, start))
+                    })?;
+                Some(UpcomingBirthday {
+                    turning: year - pet.birthday.year(),
+                    on,
+                    pet,
+                })
+            })
+            .collect();
+        upcoming.sort_by_key(|birthday| (birthday.on, birthday.pet.id));
+        Ok(upcoming)
+    }
 }
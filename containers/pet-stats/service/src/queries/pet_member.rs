@@ -0,0 +1,33 @@
+use entity::entities::{
+    pet_members::{self, Column as C, Entity as PetMembers},
+    users::{self, Entity as Users},
+};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+pub struct PetMemberQuery;
+
+impl PetMemberQuery {
+    /// Members of the pet with their accounts, in the order they joined.
+    #[instrument(skip(db), fields())]
+    pub async fn by_pet(
+        db: &DbConn,
+        pet_id: i32,
+    ) -> Result<Vec<(pet_members::Model, Option<users::Model>)>, DbErr> {
+        PetMembers::find()
+            .find_also_related(Users)
+            .filter(C::PetId.eq(pet_id))
+            .order_by_asc(C::CreatedAt)
+            .all(db)
+            .await
+    }
+
+    #[instrument(skip(db), fields())]
+    pub async fn membership(
+        db: &DbConn,
+        pet_id: i32,
+        user_id: i32,
+    ) -> Result<Option<pet_members::Model>, DbErr> {
+        PetMembers::find_by_id((pet_id, user_id)).one(db).await
+    }
+}
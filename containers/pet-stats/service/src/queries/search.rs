@@ -0,0 +1,130 @@
+use entity::entities::{
+    journal_entries, pets, prelude::JournalEntries, prelude::Pets, prelude::VetVisits, vet_visits,
+};
+use sea_orm::{
+    ColumnTrait, DbBackend, DbConn, DbErr, EntityTrait, FromQueryResult, QueryFilter, Statement,
+};
+use tracing::instrument;
+
+/// Ranks the matching pets the user is a member of, and their journal entries and vet visits.
+/// `$2` is read with `websearch_to_tsquery`, so quotes, `or` and `-` work as in a search engine.
+const SEARCH: &str = r#"WITH "query" AS (
+    SELECT websearch_to_tsquery('english', $2) AS "english",
+        websearch_to_tsquery('simple', $2) AS "simple"
+), "member_pets" AS (
+    SELECT "pet_id" FROM "pet_members" WHERE "user_id" = $1
+)
+SELECT 'Pet' AS "kind", "pets"."id", ts_rank("pets"."search_vector", "query"."simple") AS "rank"
+FROM "pets", "query"
+WHERE "pets"."id" IN (SELECT "pet_id" FROM "member_pets") AND "pets"."search_vector" @@ "query"."simple"
+UNION ALL
+SELECT 'JournalEntry', "journal_entries"."id",
+    ts_rank("journal_entries"."search_vector", "query"."english")
+FROM "journal_entries" JOIN "pets" ON "pets"."id" = "journal_entries"."pet_id", "query"
+WHERE "pets"."id" IN (SELECT "pet_id" FROM "member_pets") AND "journal_entries"."search_vector" @@ "query"."english"
+UNION ALL
+SELECT 'VetVisit', "vet_visits"."id", ts_rank("vet_visits"."search_vector", "query"."english")
+FROM "vet_visits" JOIN "pets" ON "pets"."id" = "vet_visits"."pet_id", "query"
+WHERE "pets"."id" IN (SELECT "pet_id" FROM "member_pets") AND "vet_visits"."search_vector" @@ "query"."english"
+ORDER BY "rank" DESC, "kind", "id"
+LIMIT $3"#;
+
+#[derive(Debug, FromQueryResult)]
+struct Match {
+    kind: String,
+    id: i32,
+    rank: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchItem {
+    Pet(pets::Model),
+    JournalEntry(journal_entries::Model),
+    VetVisit(vet_visits::Model),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub rank: f32,
+    pub item: SearchItem,
+}
+
+pub struct SearchQuery;
+
+impl SearchQuery {
+    /// Up to `limit` of the user's records matching `query`, best match first.
+    #[instrument(skip(db))]
+    pub async fn search(
+        db: &DbConn,
+        user_id: i32,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<SearchResult>, DbErr> {
+        let matches = Match::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SEARCH,
+            [user_id.into(), query.into(), limit.into()],
+        ))
+        .all(db)
+        .await?;
+
+        let ids = |kind: &str| -> Vec<i32> {
+            matches
+                .iter()
+                .filter(|m| m.kind == kind)
+                .map(|m| m.id)
+                .collect()
+        };
+        let (pet_ids, entry_ids, visit_ids) = (ids("Pet"), ids("JournalEntry"), ids("VetVisit"));
+
+        let pets = if pet_ids.is_empty() {
+            vec![]
+        } else {
+            Pets::find()
+                .filter(pets::Column::Id.is_in(pet_ids))
+                .all(db)
+                .await?
+        };
+        let entries = if entry_ids.is_empty() {
+            vec![]
+        } else {
+            JournalEntries::find()
+                .filter(journal_entries::Column::Id.is_in(entry_ids))
+                .all(db)
+                .await?
+        };
+        let visits = if visit_ids.is_empty() {
+            vec![]
+        } else {
+            VetVisits::find()
+                .filter(vet_visits::Column::Id.is_in(visit_ids))
+                .all(db)
+                .await?
+        };
+
+        // Keep the ranking order. Rows deleted since they were ranked are left out.
+        Ok(matches
+            .into_iter()
+            .filter_map(|m| {
+                let item = match m.kind.as_str() {
+                    "Pet" => pets
+                        .iter()
+                        .find(|pet| pet.id == m.id)
+                        .cloned()
+                        .map(SearchItem::Pet),
+                    "JournalEntry" => entries
+                        .iter()
+                        .find(|entry| entry.id == m.id)
+                        .cloned()
+                        .map(SearchItem::JournalEntry),
+                    _ => visits
+                        .iter()
+                        .find(|visit| visit.id == m.id)
+                        .cloned()
+                        .map(SearchItem::VetVisit),
+                }?;
+                Some(SearchResult { rank: m.rank, item })
+            })
+            .collect())
+    }
+}
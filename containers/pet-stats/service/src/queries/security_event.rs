@@ -0,0 +1,23 @@
+use entity::entities::security_events::{self, Entity as SecurityEvents};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use tracing::instrument;
+
+pub struct SecurityEventQuery;
+
+impl SecurityEventQuery {
+    /// Most recent account activity first.
+    #[instrument(skip(db), fields())]
+    pub async fn recent_by_user(
+        db: &DbConn,
+        user_id: i32,
+        limit: u64,
+    ) -> Result<Vec<security_events::Model>, DbErr> {
+        SecurityEvents::find()
+            .filter(security_events::Column::UserId.eq(user_id))
+            .order_by_desc(security_events::Column::CreatedAt)
+            .order_by_desc(security_events::Column::Id)
+            .limit(limit)
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,63 @@
+use entity::entities::species::{self, Column as C, Entity as Species};
+use sea_orm::{
+    sea_query::NullOrdering, ColumnTrait, Condition, DbConn, DbErr, EntityTrait, Order,
+    QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+
+fn visible_to(user_id: i32) -> Condition {
+    Condition::any()
+        .add(C::UserId.is_null())
+        .add(C::UserId.eq(user_id))
+}
+
+pub struct SpeciesQuery;
+
+impl SpeciesQuery {
+    /// Shared species followed by the ones the user defined, by name.
+    #[instrument(skip(db))]
+    pub async fn available(db: &DbConn, user_id: i32) -> Result<Vec<species::Model>, DbErr> {
+        Species::find()
+            .filter(visible_to(user_id))
+            .order_by_with_nulls(C::UserId, Order::Asc, NullOrdering::First)
+            .order_by_asc(C::Name)
+            .all(db)
+            .await
+    }
+
+    #[instrument(skip(db))]
+    pub async fn by_id(db: &DbConn, id: i32) -> Result<species::Model, DbErr> {
+        Species::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Species Not Found".to_owned()))
+    }
+
+    /// Species with any of the ids, in no particular order. Missing ids are left out.
+    #[instrument(skip(db))]
+    pub async fn by_ids(db: &DbConn, ids: &[i32]) -> Result<Vec<species::Model>, DbErr> {
+        Species::find()
+            .filter(C::Id.is_in(ids.iter().copied()))
+            .all(db)
+            .await
+    }
+
+    /// The species, if it is shared or defined by the user.
+    #[instrument(skip(db))]
+    pub async fn usable_by(db: &DbConn, user_id: i32, id: i32) -> Result<species::Model, DbErr> {
+        Species::find_by_id(id)
+            .filter(visible_to(user_id))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Species Not Found".to_owned()))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn by_code(db: &DbConn, code: &str) -> Result<species::Model, DbErr> {
+        Species::find()
+            .filter(C::Code.eq(code))
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Species Not Found".to_owned()))
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::Local;
+use entity::entities::{
+    revoked_access_tokens::{self, Column as C, Entity as RevokedAccessTokens},
+    users,
+};
+use jwt::DEFAULT_EXP;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+};
+use tracing::instrument;
+
+pub struct TokenRevocationQuery;
+
+impl TokenRevocationQuery {
+    /// Revocations of tokens that are not expired yet, optionally only those made since `since`.
+    #[instrument(skip(db), fields())]
+    pub async fn active(
+        db: &DbConn,
+        since: Option<DateTimeWithTimeZone>,
+    ) -> Result<Vec<revoked_access_tokens::Model>, DbErr> {
+        let mut query =
+            RevokedAccessTokens::find().filter(C::ExpiresAt.gt(Local::now().fixed_offset()));
+        if let Some(since) = since {
+            query = query.filter(C::CreatedAt.gte(since));
+        }
+        query.all(db).await
+    }
+
+    /// Users whose access token cutoff still rejects unexpired tokens, optionally only those
+    /// cut off since `since`.
+    #[instrument(skip(db), fields())]
+    pub async fn active_cutoffs(
+        db: &DbConn,
+        since: Option<DateTimeWithTimeZone>,
+    ) -> Result<Vec<users::Model>, DbErr> {
+        let mut query = users::Entity::find().filter(
+            users::Column::TokensRevokedBefore.gt(Local::now().fixed_offset() - DEFAULT_EXP),
+        );
+        if let Some(since) = since {
+            query = query.filter(users::Column::TokensRevokedBefore.gte(since));
+        }
+        query.all(db).await
+    }
+}
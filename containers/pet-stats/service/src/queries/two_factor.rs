@@ -0,0 +1,24 @@
+use entity::entities::user_totp::{self, Entity as UserTotp};
+use sea_orm::{DbConn, DbErr, EntityTrait};
+use tracing::instrument;
+
+pub struct TwoFactorQuery;
+
+impl TwoFactorQuery {
+    /// TOTP credential of the user, confirmed or still pending.
+    #[instrument(skip(db), fields())]
+    pub async fn totp_by_user_id(
+        db: &DbConn,
+        user_id: i32,
+    ) -> Result<Option<user_totp::Model>, DbErr> {
+        UserTotp::find_by_id(user_id).one(db).await
+    }
+
+    /// Whether the user has to pass a second factor to sign in.
+    #[instrument(skip(db), fields())]
+    pub async fn totp_enabled(db: &DbConn, user_id: i32) -> Result<bool, DbErr> {
+        Ok(Self::totp_by_user_id(db, user_id)
+            .await?
+            .is_some_and(|totp| totp.confirmed_at.is_some()))
+    }
+}
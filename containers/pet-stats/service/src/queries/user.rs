@@ -1,12 +1,21 @@
 use ::entity::entities::{
     user_tokens, user_tokens::Entity as UserTokens, users, users::Entity as Users,
 };
-use entity::entities::oauth_accounts;
+use chrono::Local;
+use entity::entities::{
+    api_keys, care_task_completions, care_tasks, device_events, devices, elimination_records,
+    expenses, feed_records, food_purchases, foods, journal_entries, medication_doses, medications,
+    oauth_accounts, pet_invitations, pet_members, pets, prelude::ApiKeys, prelude::Foods,
+    prelude::OauthAccounts, prelude::Pets, prelude::SecurityEvents, prelude::Species,
+    sea_orm_active_enums::LoginType, vaccinations, vet_visits, walk_tracks, water_records,
+    work_goals, work_records,
+};
 use sea_orm::{
-    ColumnTrait, DbConn, DbErr, EntityTrait, Iterable, JoinType, QueryFilter,
-    QuerySelect, RelationTrait,
+    ColumnTrait, DbConn, DbErr, EntityTrait, Iterable, JoinType, JsonValue, ModelTrait,
+    QueryFilter, QuerySelect, RelationTrait,
 };
-use tracing::instrument;
+use serde_json::json;
+use tracing::{info, instrument};
 
 pub struct UserQuery;
 
@@ -49,6 +58,19 @@ impl UserQuery {
         Ok(user)
     }
 
+    /// Email and password account with `email`, if any.
+    #[instrument(skip(db, email), fields())]
+    pub async fn local_user_by_email(
+        db: &DbConn,
+        email: &str,
+    ) -> Result<Option<users::Model>, DbErr> {
+        Users::find()
+            .filter(users::Column::Email.eq(email))
+            .filter(users::Column::LoginType.eq(LoginType::Local))
+            .one(db)
+            .await
+    }
+
     #[instrument(skip(db), fields())]
     pub async fn user_token_by_token_hash(
         db: &DbConn,
@@ -62,4 +84,236 @@ impl UserQuery {
             .ok_or_else(|| DbErr::RecordNotFound("Token Not Found".to_owned()))?;
         Ok(user_token)
     }
+
+    /// Collect everything stored about the user as a single JSON document.
+    ///
+    /// Secrets are left out: the password hash, provider ID tokens, refresh token hashes and
+    /// two-factor secrets and recovery codes.
+    #[instrument(skip(db), fields(user_id = id))]
+    pub async fn export_user_data(db: &DbConn, id: i32) -> Result<JsonValue, DbErr> {
+        let user = Self::user_by_id(db, id).await?;
+
+        let profile = Users::find_by_id(id)
+            .select_only()
+            .columns(users::Column::iter().filter(|c| !matches!(c, users::Column::PasswordHash)))
+            .into_json()
+            .one(db)
+            .await?;
+
+        let oauth_accounts = user
+            .find_related(OauthAccounts)
+            .select_only()
+            .columns(
+                oauth_accounts::Column::iter()
+                    .filter(|c| !matches!(c, oauth_accounts::Column::IdToken)),
+            )
+            .into_json()
+            .all(db)
+            .await?;
+
+        let sessions = user
+            .find_related(UserTokens)
+            .select_only()
+            .columns(
+                user_tokens::Column::iter()
+                    .filter(|c| !matches!(c, user_tokens::Column::RefreshToken)),
+            )
+            .into_json()
+            .all(db)
+            .await?;
+
+        let pets = user.find_related(Pets).into_json().all(db).await?;
+
+        let feed_records = feed_records::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let foods = user.find_related(Foods).into_json().all(db).await?;
+
+        let food_purchases = food_purchases::Entity::find()
+            .inner_join(Foods)
+            .filter(foods::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let work_goals = work_goals::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let work_records = work_records::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let walk_tracks = walk_tracks::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                walk_tracks::Relation::WorkRecords.def(),
+            )
+            .join(JoinType::InnerJoin, work_records::Relation::Pets.def())
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let vaccinations = vaccinations::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let vet_visits = vet_visits::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let medications = medications::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let medication_doses = medication_doses::Entity::find()
+            .inner_join(medications::Entity)
+            .join(JoinType::InnerJoin, medications::Relation::Pets.def())
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let care_tasks = care_tasks::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let care_task_completions = care_task_completions::Entity::find()
+            .inner_join(care_tasks::Entity)
+            .join(JoinType::InnerJoin, care_tasks::Relation::Pets.def())
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let expenses = expenses::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let journal_entries = journal_entries::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let elimination_records = elimination_records::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let water_records = water_records::Entity::find()
+            .inner_join(Pets)
+            .filter(pets::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let devices = devices::Entity::find()
+            .filter(devices::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let device_events = device_events::Entity::find()
+            .inner_join(devices::Entity)
+            .filter(devices::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let pet_members = pet_members::Entity::find()
+            .filter(pet_members::Column::UserId.eq(id))
+            .into_json()
+            .all(db)
+            .await?;
+
+        let pet_invitations = pet_invitations::Entity::find()
+            .filter(pet_invitations::Column::InvitedBy.eq(id))
+            .select_only()
+            .columns(
+                pet_invitations::Column::iter()
+                    .filter(|c| !matches!(c, pet_invitations::Column::CodeHash)),
+            )
+            .into_json()
+            .all(db)
+            .await?;
+
+        let security_events = user
+            .find_related(SecurityEvents)
+            .into_json()
+            .all(db)
+            .await?;
+
+        let species = user.find_related(Species).into_json().all(db).await?;
+
+        let api_keys = user
+            .find_related(ApiKeys)
+            .select_only()
+            .columns(api_keys::Column::iter().filter(|c| !matches!(c, api_keys::Column::KeyHash)))
+            .into_json()
+            .all(db)
+            .await?;
+
+        info!("Exported user data with {} pets", pets.len());
+
+        Ok(json!({
+            "exported_at": Local::now().fixed_offset(),
+            "user": profile,
+            "oauth_accounts": oauth_accounts,
+            "sessions": sessions,
+            "pets": pets,
+            "feed_records": feed_records,
+            "foods": foods,
+            "food_purchases": food_purchases,
+            "work_goals": work_goals,
+            "work_records": work_records,
+            "walk_tracks": walk_tracks,
+            "vaccinations": vaccinations,
+            "vet_visits": vet_visits,
+            "medications": medications,
+            "medication_doses": medication_doses,
+            "care_tasks": care_tasks,
+            "care_task_completions": care_task_completions,
+            "expenses": expenses,
+            "journal_entries": journal_entries,
+            "elimination_records": elimination_records,
+            "water_records": water_records,
+            "devices": devices,
+            "device_events": device_events,
+            "pet_members": pet_members,
+            "pet_invitations": pet_invitations,
+            "species": species,
+            "security_events": security_events,
+            "api_keys": api_keys,
+        }))
+    }
 }
@@ -0,0 +1,60 @@
+use chrono::NaiveDate;
+use entity::entities::{
+    pets,
+    sea_orm_active_enums::PetRole,
+    vaccinations::{self, Column as C, Entity as Vaccinations},
+};
+use sea_orm::{
+    sea_query::{Alias, Expr, Query},
+    ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+use tracing::instrument;
+
+use crate::utils::user_pet_ids;
+
+pub struct VaccinationQuery;
+
+impl VaccinationQuery {
+    /// Vaccination history of a pet, latest first.
+    #[instrument(skip(db))]
+    pub async fn by_pet(db: &DbConn, pet_id: i32) -> Result<Vec<vaccinations::Model>, DbErr> {
+        Vaccinations::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::GivenOn)
+            .all(db)
+            .await
+    }
+
+    /// Vaccinations due on or before `until` across all pets of the user, overdue ones included,
+    /// soonest first.
+    ///
+    /// Only the latest dose of each vaccine counts, once a booster is recorded the due date of
+    /// the previous dose is settled.
+    #[instrument(skip(db))]
+    pub async fn upcoming_for_user(
+        db: &DbConn,
+        user_id: i32,
+        until: NaiveDate,
+    ) -> Result<Vec<vaccinations::Model>, DbErr> {
+        let later = Alias::new("later");
+        let later_dose = Query::select()
+            .expr(Expr::val(1))
+            .from_as(Vaccinations, later.clone())
+            .and_where(Expr::col((later.clone(), C::PetId)).equals((Vaccinations, C::PetId)))
+            .and_where(Expr::col((later.clone(), C::Vaccine)).equals((Vaccinations, C::Vaccine)))
+            .and_where(Expr::col((later, C::GivenOn)).gt(Expr::col((Vaccinations, C::GivenOn))))
+            .to_owned();
+
+        Vaccinations::find()
+            .join(
+                sea_orm::JoinType::InnerJoin,
+                vaccinations::Relation::Pets.def(),
+            )
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, PetRole::Viewer)))
+            .filter(C::NextDueOn.lte(until))
+            .filter(Expr::exists(later_dose).not())
+            .order_by_asc(C::NextDueOn)
+            .all(db)
+            .await
+    }
+}
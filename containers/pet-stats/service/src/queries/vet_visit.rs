@@ -0,0 +1,17 @@
+use entity::entities::vet_visits::{self, Column as C, Entity as VetVisits};
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+pub struct VetVisitQuery;
+
+impl VetVisitQuery {
+    /// Vet visits of a pet, latest first.
+    #[instrument(skip(db))]
+    pub async fn by_pet(db: &DbConn, pet_id: i32) -> Result<Vec<vet_visits::Model>, DbErr> {
+        VetVisits::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::VisitedOn)
+            .all(db)
+            .await
+    }
+}
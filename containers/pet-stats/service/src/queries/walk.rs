@@ -0,0 +1,86 @@
+use entity::entities::{
+    pets,
+    sea_orm_active_enums::PetRole,
+    walk_tracks::{self, Entity as WalkTracks},
+    work_records::{self, Column as C, Entity as WorkRecords},
+};
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, sea_query::Expr, ColumnTrait, ConnectionTrait, DbConn, DbErr,
+    DerivePartialModel, EntityTrait, FromQueryResult, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+use tracing::instrument;
+
+use crate::utils::user_pet_ids;
+
+/// A work record. `time` is an interval, which is read as whole minutes.
+#[derive(Debug, Clone, PartialEq, DerivePartialModel, FromQueryResult)]
+#[sea_orm(entity = "WorkRecords")]
+pub struct Walk {
+    pub id: i32,
+    pub pet_id: i32,
+    pub distance_m: Option<i32>,
+    #[sea_orm(
+        from_expr = r##"Expr::cust(r#"(EXTRACT(EPOCH FROM "work_records"."time") / 60)::int"#)"##
+    )]
+    pub duration_minutes: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+pub struct WalkQuery;
+
+impl WalkQuery {
+    /// Latest walks of the pet first, with their routes.
+    #[instrument(skip(db))]
+    pub async fn recent_by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        limit: u64,
+    ) -> Result<Vec<(Walk, Option<walk_tracks::Model>)>, DbErr> {
+        let walks: Vec<Walk> = WorkRecords::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::CreatedAt)
+            .limit(limit)
+            .into_partial_model()
+            .all(db)
+            .await?;
+        Self::with_tracks(db, walks).await
+    }
+
+    /// The walk, if it is of a pet the user has at least `min_role` on.
+    #[instrument(skip(db))]
+    pub async fn user_walk<Db: ConnectionTrait>(
+        db: &Db,
+        user_id: i32,
+        min_role: PetRole,
+        id: i32,
+    ) -> Result<Walk, DbErr> {
+        WorkRecords::find_by_id(id)
+            .join(JoinType::InnerJoin, work_records::Relation::Pets.def())
+            .filter(pets::Column::Id.in_subquery(user_pet_ids(user_id, min_role)))
+            .into_partial_model()
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("Walk Not Found".to_owned()))
+    }
+
+    async fn with_tracks(
+        db: &DbConn,
+        walks: Vec<Walk>,
+    ) -> Result<Vec<(Walk, Option<walk_tracks::Model>)>, DbErr> {
+        if walks.is_empty() {
+            return Ok(vec![]);
+        }
+        let tracks = WalkTracks::find()
+            .filter(walk_tracks::Column::WorkRecordId.is_in(walks.iter().map(|w| w.id)))
+            .all(db)
+            .await?;
+        Ok(walks
+            .into_iter()
+            .map(|walk| {
+                let track = tracks.iter().find(|t| t.work_record_id == walk.id).cloned();
+                (walk, track)
+            })
+            .collect())
+    }
+}
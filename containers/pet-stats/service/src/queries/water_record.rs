@@ -0,0 +1,64 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use entity::entities::water_records::{self, Column as C, Entity as WaterRecords};
+use sea_orm::{
+    ColumnTrait, DbBackend, DbConn, DbErr, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Statement,
+};
+use tracing::instrument;
+
+use crate::schedule::parse_timezone;
+
+/// Water drunk per calendar day in the timezone `$2`.
+const DAILY: &str = r#"SELECT ("created_at" AT TIME ZONE $2)::date AS "day",
+    SUM("amount_ml") AS "total_ml",
+    COUNT(*) AS "drinks"
+FROM "water_records"
+WHERE "pet_id" = $1 AND "created_at" >= $3 AND "created_at" < $4
+GROUP BY "day"
+ORDER BY "day""#;
+
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct DailyWaterIntake {
+    pub day: NaiveDate,
+    pub total_ml: i64,
+    pub drinks: i64,
+}
+
+pub struct WaterRecordQuery;
+
+impl WaterRecordQuery {
+    /// Latest drinks of the pet first.
+    #[instrument(skip(db))]
+    pub async fn recent_by_pet(
+        db: &DbConn,
+        pet_id: i32,
+        limit: u64,
+    ) -> Result<Vec<water_records::Model>, DbErr> {
+        WaterRecords::find()
+            .filter(C::PetId.eq(pet_id))
+            .order_by_desc(C::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await
+    }
+
+    /// Days from `from` up to `until` with water logged, in order. Days are those of
+    /// `timezone`, unknown names count as UTC.
+    #[instrument(skip(db))]
+    pub async fn daily(
+        db: &DbConn,
+        pet_id: i32,
+        timezone: &str,
+        from: DateTime<FixedOffset>,
+        until: DateTime<FixedOffset>,
+    ) -> Result<Vec<DailyWaterIntake>, DbErr> {
+        let timezone = parse_timezone(timezone).map_or("UTC", |_| timezone);
+        DailyWaterIntake::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            DAILY,
+            [pet_id.into(), timezone.into(), from.into(), until.into()],
+        ))
+        .all(db)
+        .await
+    }
+}
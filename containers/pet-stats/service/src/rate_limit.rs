@@ -0,0 +1,347 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use tracing::{instrument, warn};
+
+use crate::auth::error::CacheError;
+
+/// Size and refill speed of a token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BucketPolicy {
+    pub burst: u32,
+    pub per_minute: u32,
+}
+
+impl BucketPolicy {
+    fn refill_per_sec(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+}
+
+/// What a bucket is counted against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitKey {
+    ClientIp(String),
+    /// Account identifier claimed by the request, e.g. provider subject or email.
+    Subject(String),
+}
+
+impl RateLimitKey {
+    fn as_bucket_key(&self) -> String {
+        match self {
+            Self::ClientIp(ip) => format!("ip:{}", ip),
+            Self::Subject(subject) => format!("subject:{}", subject),
+        }
+    }
+}
+
+/// Storage for token buckets. The in-memory backend is per process; a shared store can be
+/// plugged in when running several instances.
+#[async_trait]
+pub trait RateLimitBackend: Send + Sync {
+    /// Take `cost` tokens from the bucket for `key`, creating a full bucket if needed.
+    async fn take(
+        &self,
+        key: &str,
+        policy: BucketPolicy,
+        cost: u32,
+    ) -> Result<RateDecision, CacheError>;
+
+    /// Drop buckets that have refilled completely, returning how many went. Stores that
+    /// expire keys on their own have nothing to do.
+    async fn sweep(&self) -> Result<usize, CacheError> {
+        Ok(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn full(policy: BucketPolicy, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(policy.burst),
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, policy: BucketPolicy, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * policy.refill_per_sec()).min(f64::from(policy.burst));
+        self.updated_at = now;
+    }
+
+    fn take(&mut self, policy: BucketPolicy, cost: u32, now: Instant) -> RateDecision {
+        self.refill(policy, now);
+
+        let cost = f64::from(cost);
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            return RateDecision::Allowed;
+        }
+
+        let rate = policy.refill_per_sec();
+        let retry_after = if rate > 0.0 && cost <= f64::from(policy.burst) {
+            Duration::from_secs_f64(((cost - self.tokens) / rate).ceil())
+        } else {
+            Duration::MAX
+        };
+        RateDecision::Limited { retry_after }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryRateLimitBackend {
+    buckets: Mutex<HashMap<String, (BucketPolicy, Bucket)>>,
+}
+
+impl InMemoryRateLimitBackend {
+    fn take_at(&self, key: &str, policy: BucketPolicy, cost: u32, now: Instant) -> RateDecision {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let (_, bucket) = buckets
+            .entry(key.to_owned())
+            .or_insert_with(|| (policy, Bucket::full(policy, now)));
+        bucket.take(policy, cost, now)
+    }
+
+    fn sweep_at(&self, now: Instant) -> usize {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let before = buckets.len();
+        // A bucket that has refilled completely is the same as no bucket.
+        buckets.retain(|_, (policy, bucket)| {
+            bucket.refill(*policy, now);
+            bucket.tokens < f64::from(policy.burst)
+        });
+        before - buckets.len()
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for InMemoryRateLimitBackend {
+    async fn take(
+        &self,
+        key: &str,
+        policy: BucketPolicy,
+        cost: u32,
+    ) -> Result<RateDecision, CacheError> {
+        Ok(self.take_at(key, policy, cost, Instant::now()))
+    }
+
+    async fn sweep(&self) -> Result<usize, CacheError> {
+        Ok(self.sweep_at(Instant::now()))
+    }
+}
+
+/// Token bucket rate limiter with separate policies for client IPs and account subjects.
+#[derive(Clone)]
+pub struct RateLimiter {
+    backend: Arc<dyn RateLimitBackend>,
+    ip_policy: BucketPolicy,
+    subject_policy: BucketPolicy,
+}
+
+impl RateLimiter {
+    pub fn new(
+        backend: Arc<dyn RateLimitBackend>,
+        ip_policy: BucketPolicy,
+        subject_policy: BucketPolicy,
+    ) -> Self {
+        Self {
+            backend,
+            ip_policy,
+            subject_policy,
+        }
+    }
+
+    pub fn in_memory(ip_policy: BucketPolicy, subject_policy: BucketPolicy) -> Self {
+        Self::new(
+            Arc::new(InMemoryRateLimitBackend::default()),
+            ip_policy,
+            subject_policy,
+        )
+    }
+
+    /// Charge `cost` attempts to every key. Limited when any of the buckets runs dry,
+    /// with the longest wait among them.
+    ///
+    /// Client IP buckets are charged first; a request whose IP is already limited is
+    /// rejected without charging, or creating, the buckets of the accounts it names.
+    #[instrument(skip(self))]
+    pub async fn check(
+        &self,
+        keys: &[RateLimitKey],
+        cost: u32,
+    ) -> Result<RateDecision, CacheError> {
+        let (ips, subjects): (Vec<_>, Vec<_>) = keys
+            .iter()
+            .partition(|key| matches!(key, RateLimitKey::ClientIp(_)));
+
+        let decision = self.charge(&ips, cost, RateDecision::Allowed).await?;
+        if decision != RateDecision::Allowed {
+            return Ok(decision);
+        }
+        self.charge(&subjects, cost, decision).await
+    }
+
+    /// Drop idle buckets from the backend.
+    #[instrument(skip(self))]
+    pub async fn sweep(&self) -> Result<usize, CacheError> {
+        self.backend.sweep().await
+    }
+
+    async fn charge(
+        &self,
+        keys: &[&RateLimitKey],
+        cost: u32,
+        mut decision: RateDecision,
+    ) -> Result<RateDecision, CacheError> {
+        for key in keys {
+            let policy = match key {
+                RateLimitKey::ClientIp(_) => self.ip_policy,
+                RateLimitKey::Subject(_) => self.subject_policy,
+            };
+            let taken = self
+                .backend
+                .take(&key.as_bucket_key(), policy, cost)
+                .await?;
+
+            if let RateDecision::Limited { retry_after } = taken {
+                warn!("Rate limited: {:?}", key);
+                decision = match decision {
+                    RateDecision::Limited { retry_after: other } => RateDecision::Limited {
+                        retry_after: retry_after.max(other),
+                    },
+                    RateDecision::Allowed => taken,
+                };
+            }
+        }
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: BucketPolicy = BucketPolicy {
+        burst: 3,
+        per_minute: 6,
+    };
+
+    #[test]
+    fn test_bucket_allows_burst_then_limits() {
+        let backend = InMemoryRateLimitBackend::default();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(backend.take_at("k", POLICY, 1, now), RateDecision::Allowed);
+        }
+        assert_eq!(
+            backend.take_at("k", POLICY, 1, now),
+            RateDecision::Limited {
+                retry_after: Duration::from_secs(10)
+            }
+        );
+        assert_eq!(
+            backend.take_at("other", POLICY, 1, now),
+            RateDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let backend = InMemoryRateLimitBackend::default();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            backend.take_at("k", POLICY, 1, now);
+        }
+        let later = now + Duration::from_secs(10);
+        assert_eq!(
+            backend.take_at("k", POLICY, 1, later),
+            RateDecision::Allowed
+        );
+        assert!(matches!(
+            backend.take_at("k", POLICY, 1, later),
+            RateDecision::Limited { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_limiter_reports_longest_wait() {
+        let limiter = RateLimiter::in_memory(
+            POLICY,
+            BucketPolicy {
+                burst: 1,
+                per_minute: 1,
+            },
+        );
+        let keys = [
+            RateLimitKey::ClientIp("10.0.0.1".to_owned()),
+            RateLimitKey::Subject("google:123".to_owned()),
+        ];
+
+        assert_eq!(
+            limiter.check(&keys, 1).await.unwrap(),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check(&keys, 1).await.unwrap(),
+            RateDecision::Limited {
+                retry_after: Duration::from_secs(60)
+            }
+        );
+    }
+
+    #[test]
+    fn test_sweep_drops_only_refilled_buckets() {
+        let backend = InMemoryRateLimitBackend::default();
+        let now = Instant::now();
+
+        backend.take_at("idle", POLICY, 1, now);
+        backend.take_at("busy", POLICY, 3, now + Duration::from_secs(10));
+
+        // "idle" is full again after 10s, "busy" still owes a token.
+        assert_eq!(backend.sweep_at(now + Duration::from_secs(15)), 1);
+        assert!(matches!(
+            backend.take_at("busy", POLICY, 2, now + Duration::from_secs(15)),
+            RateDecision::Limited { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_limited_ip_does_not_create_subject_buckets() {
+        let backend = Arc::new(InMemoryRateLimitBackend::default());
+        let limiter = RateLimiter::new(
+            backend.clone(),
+            BucketPolicy {
+                burst: 1,
+                per_minute: 1,
+            },
+            POLICY,
+        );
+        let ip = RateLimitKey::ClientIp("10.0.0.1".to_owned());
+
+        for i in 0..100 {
+            let keys = [RateLimitKey::Subject(format!("google:{}", i)), ip.clone()];
+            limiter.check(&keys, 1).await.unwrap();
+        }
+
+        // One for the IP, one for the only subject checked while the IP had tokens.
+        assert_eq!(backend.buckets.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,415 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use thiserror::Error;
+
+/// Occurrences are not generated past this year.
+const MAX_YEAR: i32 = 9999;
+
+/// Rules with a COUNT are walked from the start to count what came before, so it is capped.
+const MAX_COUNT: u32 = 10_000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RecurrenceError {
+    #[error("FREQ is required")]
+    MissingFrequency,
+    #[error("Malformed rule part `{0}`")]
+    Malformed(String),
+    #[error("Unsupported rule part `{0}`")]
+    Unsupported(String),
+    #[error("Invalid value for {0}")]
+    InvalidValue(&'static str),
+    #[error("COUNT and UNTIL can not be combined")]
+    CountAndUntil,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of an iCalendar (RFC 5545) RRULE care tasks can use:
+/// `FREQ`, `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY` (weekly rules) and `BYMONTHDAY`
+/// (monthly rules), e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+///
+/// `DTSTART` is not part of the rule, occurrences are counted from the task's start date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Vec<i32>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+impl FromStr for Recurrence {
+    type Err = RecurrenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = s.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| RecurrenceError::Malformed(part.to_owned()))?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(RecurrenceError::Unsupported(part.to_owned())),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| (1..=1000).contains(interval))
+                        .ok_or(RecurrenceError::InvalidValue("INTERVAL"))?
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|count| (1..=MAX_COUNT).contains(count))
+                            .ok_or(RecurrenceError::InvalidValue("COUNT"))?,
+                    )
+                }
+                "UNTIL" => {
+                    // Date or date-time form, only the date matters for day based tasks.
+                    let date = value.get(..8).unwrap_or(value);
+                    until = Some(
+                        NaiveDate::parse_from_str(date, "%Y%m%d")
+                            .map_err(|_| RecurrenceError::InvalidValue("UNTIL"))?,
+                    )
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Option<_>>()
+                        .ok_or(RecurrenceError::InvalidValue("BYDAY"))?
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = value
+                        .split(',')
+                        .map(|day| {
+                            day.parse::<i32>()
+                                .ok()
+                                .filter(|day| *day != 0 && (-31..=31).contains(day))
+                        })
+                        .collect::<Option<_>>()
+                        .ok_or(RecurrenceError::InvalidValue("BYMONTHDAY"))?
+                }
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(RecurrenceError::Unsupported(part.to_owned())),
+            }
+        }
+
+        let frequency = frequency.ok_or(RecurrenceError::MissingFrequency)?;
+        if count.is_some() && until.is_some() {
+            return Err(RecurrenceError::CountAndUntil);
+        }
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err(RecurrenceError::Unsupported("BYDAY".to_owned()));
+        }
+        if !by_month_day.is_empty() && frequency != Frequency::Monthly {
+            return Err(RecurrenceError::Unsupported("BYMONTHDAY".to_owned()));
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            count,
+            until,
+        })
+    }
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    match day.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl Recurrence {
+    /// Every occurrence from `start` on, in order.
+    pub fn occurrences(&self, start: NaiveDate) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.occurrences_around(start, start)
+    }
+
+    /// First occurrence strictly after `after`, or the first one at all.
+    pub fn next_after(&self, start: NaiveDate, after: Option<NaiveDate>) -> Option<NaiveDate> {
+        self.occurrences_around(start, after.unwrap_or(start))
+            .find(|date| after.is_none_or(|after| *date > after))
+    }
+
+    /// Whether `date` is one of the occurrences.
+    pub fn occurs_on(&self, start: NaiveDate, date: NaiveDate) -> bool {
+        self.occurrences_around(start, date)
+            .take_while(|occurrence| *occurrence <= date)
+            .any(|occurrence| occurrence == date)
+    }
+
+    /// Occurrences from the period containing `from` on. Rules with a COUNT start at the
+    /// first period, the occurrences before `from` count against it.
+    fn occurrences_around(
+        &self,
+        start: NaiveDate,
+        from: NaiveDate,
+    ) -> impl Iterator<Item = NaiveDate> + '_ {
+        let first = match self.count {
+            Some(_) => 0,
+            None => self.period_of(start, from),
+        };
+        (first..)
+            .map_while(move |period| self.period_dates(start, period))
+            .flatten()
+            .filter(move |date| *date >= start)
+            .take_while(move |date| self.until.is_none_or(|until| *date <= until))
+            .take(self.count.map_or(usize::MAX, |count| count as usize))
+    }
+
+    /// Index of the interval `date` falls in, 0 up to the start.
+    fn period_of(&self, start: NaiveDate, date: NaiveDate) -> u32 {
+        if date <= start {
+            return 0;
+        }
+        let elapsed = match self.frequency {
+            Frequency::Daily => (date - start).num_days(),
+            Frequency::Weekly => {
+                let monday = |day: NaiveDate| day.week(Weekday::Mon).first_day();
+                (monday(date) - monday(start)).num_weeks()
+            }
+            Frequency::Monthly => {
+                i64::from(date.year() - start.year()) * 12 + i64::from(date.month())
+                    - i64::from(start.month())
+            }
+            Frequency::Yearly => i64::from(date.year() - start.year()),
+        };
+        u32::try_from(elapsed / i64::from(self.interval)).unwrap_or(u32::MAX)
+    }
+
+    /// Candidate dates of the `period`th interval, `None` once past `MAX_YEAR`.
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+        let dates = match self.frequency {
+            Frequency::Daily => vec![start.checked_add_days(Days::new(step.into()))?],
+            Frequency::Weekly => {
+                let monday = start.week(Weekday::Mon).first_day();
+                let week = monday.checked_add_days(Days::new(u64::from(step) * 7))?;
+                if self.by_day.is_empty() {
+                    vec![week.checked_add_days(Days::new(
+                        start.weekday().num_days_from_monday().into(),
+                    ))?]
+                } else {
+                    let mut dates: Vec<_> = self
+                        .by_day
+                        .iter()
+                        .filter_map(|day| {
+                            week.checked_add_days(Days::new(day.num_days_from_monday().into()))
+                        })
+                        .collect();
+                    dates.sort();
+                    dates.dedup();
+                    dates
+                }
+            }
+            Frequency::Monthly => {
+                let month = start.with_day(1)?.checked_add_months(Months::new(step))?;
+                let days_in_month =
+                    month.checked_add_months(Months::new(1))?.pred_opt()?.day() as i32;
+                let days = if self.by_month_day.is_empty() {
+                    vec![start.day() as i32]
+                } else {
+                    self.by_month_day.clone()
+                };
+                // Days the month doesn't have are skipped, as RFC 5545 does.
+                let mut dates: Vec<_> = days
+                    .into_iter()
+                    .map(|day| {
+                        if day < 0 {
+                            days_in_month + 1 + day
+                        } else {
+                            day
+                        }
+                    })
+                    .filter(|day| (1..=days_in_month).contains(day))
+                    .filter_map(|day| month.with_day(day as u32))
+                    .collect();
+                dates.sort();
+                dates.dedup();
+                dates
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(step).ok()?)?;
+                if year > MAX_YEAR {
+                    return None;
+                }
+                // Feb 29 only occurs on leap years.
+                NaiveDate::from_ymd_opt(year, start.month(), start.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+        match dates.first() {
+            Some(first) if first.year() > MAX_YEAR => None,
+            _ => Some(dates),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn first(rule: &str, start: NaiveDate, n: usize) -> Vec<NaiveDate> {
+        rule.parse::<Recurrence>()
+            .unwrap()
+            .occurrences(start)
+            .take(n)
+            .collect()
+    }
+
+    #[test]
+    fn test_daily_with_interval_and_count() {
+        assert_eq!(
+            first(
+                "RRULE:FREQ=DAILY;INTERVAL=3;COUNT=3",
+                date(2026, 10, 30),
+                10
+            ),
+            vec![date(2026, 10, 30), date(2026, 11, 2), date(2026, 11, 5)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_by_day_skips_days_before_start() {
+        // 2026-10-21 is a Wednesday, Monday of that week is before the start.
+        assert_eq!(
+            first("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH", date(2026, 10, 21), 3),
+            vec![date(2026, 10, 22), date(2026, 11, 2), date(2026, 11, 5)]
+        );
+        assert_eq!(
+            first("FREQ=WEEKLY", date(2026, 10, 21), 2),
+            vec![date(2026, 10, 21), date(2026, 10, 28)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_skips_missing_days() {
+        assert_eq!(
+            first("FREQ=MONTHLY", date(2026, 1, 31), 3),
+            vec![date(2026, 1, 31), date(2026, 3, 31), date(2026, 5, 31)]
+        );
+        assert_eq!(
+            first("FREQ=MONTHLY;BYMONTHDAY=1,-1", date(2026, 2, 10), 3),
+            vec![date(2026, 2, 28), date(2026, 3, 1), date(2026, 3, 31)]
+        );
+    }
+
+    #[test]
+    fn test_until_is_inclusive() {
+        let rule: Recurrence = "FREQ=YEARLY;UNTIL=20280301T000000Z".parse().unwrap();
+        let dates: Vec<_> = rule.occurrences(date(2024, 2, 29)).collect();
+        assert_eq!(dates, vec![date(2024, 2, 29), date(2028, 2, 29)]);
+    }
+
+    #[test]
+    fn test_next_after_and_occurs_on() {
+        let rule: Recurrence = "FREQ=DAILY;INTERVAL=2".parse().unwrap();
+        let start = date(2026, 10, 1);
+        assert_eq!(rule.next_after(start, None), Some(start));
+        assert_eq!(
+            rule.next_after(start, Some(date(2026, 10, 3))),
+            Some(date(2026, 10, 5))
+        );
+        assert!(rule.occurs_on(start, date(2026, 10, 19)));
+        assert!(!rule.occurs_on(start, date(2026, 10, 20)));
+
+        let finished: Recurrence = "FREQ=DAILY;COUNT=1".parse().unwrap();
+        assert_eq!(finished.next_after(start, Some(start)), None);
+    }
+
+    #[test]
+    fn test_far_dates_match_walking_every_period() {
+        let start = date(1900, 1, 31);
+        let far = date(2199, 12, 31);
+        for rule in [
+            "FREQ=DAILY;INTERVAL=7",
+            "FREQ=WEEKLY;INTERVAL=3;BYDAY=TU,SU",
+            "FREQ=MONTHLY;INTERVAL=5",
+            "FREQ=MONTHLY;BYMONTHDAY=-1,15",
+            "FREQ=YEARLY;INTERVAL=4",
+        ] {
+            let rule: Recurrence = rule.parse().unwrap();
+            let walked = rule
+                .occurrences(start)
+                .find(|occurrence| *occurrence > date(2199, 6, 1))
+                .unwrap();
+            assert_eq!(rule.next_after(start, Some(date(2199, 6, 1))), Some(walked));
+            assert!(rule.occurs_on(start, walked));
+            assert_eq!(
+                rule.occurs_on(start, far),
+                rule.occurrences(start).any(|occurrence| occurrence == far)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsupported_rules() {
+        assert_eq!(
+            "INTERVAL=2".parse::<Recurrence>(),
+            Err(RecurrenceError::MissingFrequency)
+        );
+        assert_eq!(
+            "FREQ=HOURLY".parse::<Recurrence>(),
+            Err(RecurrenceError::Unsupported("FREQ=HOURLY".to_owned()))
+        );
+        assert_eq!(
+            "FREQ=DAILY;BYDAY=MO".parse::<Recurrence>(),
+            Err(RecurrenceError::Unsupported("BYDAY".to_owned()))
+        );
+        assert_eq!(
+            "FREQ=DAILY;COUNT=2;UNTIL=20270101".parse::<Recurrence>(),
+            Err(RecurrenceError::CountAndUntil)
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;BYMONTHDAY=32".parse::<Recurrence>(),
+            Err(RecurrenceError::InvalidValue("BYMONTHDAY"))
+        );
+        assert_eq!(
+            "FREQ=DAILY;COUNT=10001".parse::<Recurrence>(),
+            Err(RecurrenceError::InvalidValue("COUNT"))
+        );
+        assert!("FREQ".parse::<Recurrence>().is_err());
+    }
+}
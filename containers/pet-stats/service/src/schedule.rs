@@ -0,0 +1,144 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use entity::entities::{medications, sea_orm_active_enums::MedicationFrequency};
+
+/// Parse an IANA timezone name such as `Asia/Seoul`.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse::<Tz>().ok()
+}
+
+/// Current date on the user's wall clock. Unknown timezone names fall back to UTC.
+pub fn today_in(timezone: &str) -> NaiveDate {
+    let tz = parse_timezone(timezone).unwrap_or(Tz::UTC);
+    Utc::now().with_timezone(&tz).date_naive()
+}
+
+/// Start and end of `date` on the user's wall clock, the end being the next day's start.
+///
+/// `None` at the ends of the calendar, where the next day or the UTC instant can't be
+/// represented.
+pub fn day_bounds_in(
+    timezone: &str,
+    date: NaiveDate,
+) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+    let tz = parse_timezone(timezone).unwrap_or(Tz::UTC);
+    let start_of = |date: NaiveDate| {
+        let midnight = date.and_hms_opt(0, 0, 0)?;
+        // Where DST skips midnight, the day starts at the moment the clock jumps.
+        let start = match tz.from_local_datetime(&midnight).earliest() {
+            Some(start) => start,
+            None => {
+                let offset = tz.offset_from_utc_datetime(&midnight).fix();
+                tz.from_utc_datetime(&midnight.checked_sub_offset(offset)?)
+            }
+        };
+        Some(start.fixed_offset())
+    };
+    Some((start_of(date)?, start_of(date.succ_opt()?)?))
+}
+
+/// Whether the medication has doses due on `date`.
+pub fn medication_due_on(medication: &medications::Model, date: NaiveDate) -> bool {
+    if date < medication.start_on || medication.end_on.is_some_and(|end| date > end) {
+        return false;
+    }
+    let days = (date - medication.start_on).num_days();
+    match medication.frequency {
+        MedicationFrequency::Daily => true,
+        MedicationFrequency::EveryOtherDay => days % 2 == 0,
+        MedicationFrequency::Weekly => days % 7 == 0,
+    }
+}
+
+/// Whether `dose_number` is one of the doses due on `date`.
+pub fn is_scheduled_dose(
+    medication: &medications::Model,
+    date: NaiveDate,
+    dose_number: i32,
+) -> bool {
+    medication_due_on(medication, date) && (1..=medication.doses_per_day).contains(&dose_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+
+    fn medication(frequency: MedicationFrequency, end_on: Option<NaiveDate>) -> medications::Model {
+        let now = Local::now().fixed_offset();
+        medications::Model {
+            id: 1,
+            pet_id: 1,
+            name: "Apoquel".to_owned(),
+            dose: 5.4,
+            unit: "mg".to_owned(),
+            frequency,
+            doses_per_day: 2,
+            start_on: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            end_on,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn test_due_days_follow_frequency_within_course() {
+        let daily = medication(MedicationFrequency::Daily, Some(day(10)));
+        assert!(!medication_due_on(
+            &daily,
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap()
+        ));
+        assert!(medication_due_on(&daily, day(1)));
+        assert!(medication_due_on(&daily, day(10)));
+        assert!(!medication_due_on(&daily, day(11)));
+
+        let every_other = medication(MedicationFrequency::EveryOtherDay, None);
+        assert!(medication_due_on(&every_other, day(3)));
+        assert!(!medication_due_on(&every_other, day(4)));
+
+        let weekly = medication(MedicationFrequency::Weekly, None);
+        assert!(medication_due_on(&weekly, day(8)));
+        assert!(!medication_due_on(&weekly, day(9)));
+    }
+
+    #[test]
+    fn test_dose_number_is_bounded_by_doses_per_day() {
+        let daily = medication(MedicationFrequency::Daily, None);
+        assert!(is_scheduled_dose(&daily, day(2), 1));
+        assert!(is_scheduled_dose(&daily, day(2), 2));
+        assert!(!is_scheduled_dose(&daily, day(2), 3));
+        assert!(!is_scheduled_dose(&daily, day(2), 0));
+    }
+
+    #[test]
+    fn test_timezone_parsing() {
+        assert!(parse_timezone("Asia/Seoul").is_some());
+        assert!(parse_timezone("Mars/Olympus").is_none());
+    }
+
+    #[test]
+    fn test_day_bounds_follow_the_users_clock() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let (start, end) = day_bounds_in("Asia/Seoul", date).unwrap();
+        assert_eq!(start.to_rfc3339(), "2026-10-19T00:00:00+09:00");
+        assert_eq!(end.to_rfc3339(), "2026-10-20T00:00:00+09:00");
+
+        // Brazil used to skip midnight when DST began.
+        let date = NaiveDate::from_ymd_opt(2018, 11, 4).unwrap();
+        let (start, end) = day_bounds_in("America/Sao_Paulo", date).unwrap();
+        assert_eq!(start.to_rfc3339(), "2018-11-04T01:00:00-02:00");
+        assert_eq!((end - start).num_hours(), 23);
+    }
+
+    #[test]
+    fn test_day_bounds_at_the_ends_of_the_calendar() {
+        assert_eq!(day_bounds_in("Asia/Seoul", NaiveDate::MAX), None);
+        assert!(day_bounds_in("America/Los_Angeles", NaiveDate::MIN).is_some());
+        assert!(day_bounds_in("Asia/Seoul", NaiveDate::MAX.pred_opt().unwrap()).is_some());
+    }
+}
@@ -0,0 +1,74 @@
+use entity::entities::species;
+
+/// Species as the GraphQL API has always exposed them. Species added to the `species`
+/// table later on, and the ones users define themselves, are `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PetSpeciesType {
+    Dog,
+    Cat,
+    Fish,
+    Lizard,
+    Turtle,
+    Snake,
+    Other,
+}
+
+impl PetSpeciesType {
+    /// `species.code` of the built-in species.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Self::Dog => Some("Dog"),
+            Self::Cat => Some("Cat"),
+            Self::Fish => Some("Fish"),
+            Self::Lizard => Some("Lizard"),
+            Self::Turtle => Some("Turtle"),
+            Self::Snake => Some("Snake"),
+            Self::Other => None,
+        }
+    }
+
+    pub fn of(species: &species::Model) -> Self {
+        match species.code.as_deref() {
+            Some("Dog") => Self::Dog,
+            Some("Cat") => Self::Cat,
+            Some("Fish") => Self::Fish,
+            Some("Lizard") => Self::Lizard,
+            Some("Turtle") => Self::Turtle,
+            Some("Snake") => Self::Snake,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+
+    #[test]
+    fn test_unknown_and_user_defined_species_are_other() {
+        let species = |code: Option<&str>, user_id| species::Model {
+            id: 1,
+            code: code.map(str::to_owned),
+            name: "Rabbit".to_owned(),
+            user_id,
+            adult_from_months: None,
+            senior_from_months: None,
+            created_at: Local::now().fixed_offset(),
+        };
+        assert_eq!(
+            PetSpeciesType::of(&species(Some("Cat"), None)),
+            PetSpeciesType::Cat
+        );
+        assert_eq!(
+            PetSpeciesType::of(&species(Some("Rabbit"), None)),
+            PetSpeciesType::Other
+        );
+        assert_eq!(
+            PetSpeciesType::of(&species(None, Some(3))),
+            PetSpeciesType::Other
+        );
+        assert_eq!(PetSpeciesType::Snake.code(), Some("Snake"));
+    }
+}
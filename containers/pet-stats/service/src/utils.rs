@@ -1,7 +1,13 @@
 use chrono::{DateTime, FixedOffset, Local};
-use sea_orm::{DbConn, DbErr, TransactionTrait};
+use entity::entities::{foods, pet_members, sea_orm_active_enums::PetRole};
+use sea_orm::{
+    sea_query::{Query, SelectStatement},
+    ColumnTrait, DbConn, DbErr, TransactionTrait,
+};
 use tracing::{error, trace};
 
+use crate::membership::roles_at_least;
+
 pub async fn start_transaction(db: &DbConn) -> Result<sea_orm::DatabaseTransaction, DbErr> {
     match db.begin().await {
         Ok(txn) => {
@@ -31,3 +37,26 @@ pub async fn commit_transaction(txn: sea_orm::DatabaseTransaction) -> Result<(),
 pub(crate) fn get_current_time() -> DateTime<FixedOffset> {
     Local::now().with_timezone(Local::now().offset())
 }
+
+/// `SELECT pet_id FROM pet_members WHERE user_id = ?`, to scope access to the pets the user
+/// is a member of with at least `min_role`.
+pub(crate) fn user_pet_ids(user_id: i32, min_role: PetRole) -> SelectStatement {
+    let mut select = Query::select()
+        .column(pet_members::Column::PetId)
+        .from(pet_members::Entity)
+        .and_where(pet_members::Column::UserId.eq(user_id))
+        .to_owned();
+    if min_role != PetRole::Viewer {
+        select.and_where(pet_members::Column::Role.is_in(roles_at_least(&min_role)));
+    }
+    select
+}
+
+/// `SELECT id FROM foods WHERE user_id = ?`, to scope writes to the user's own foods.
+pub(crate) fn user_food_ids(user_id: i32) -> SelectStatement {
+    Query::select()
+        .column(foods::Column::Id)
+        .from(foods::Entity)
+        .and_where(foods::Column::UserId.eq(user_id))
+        .to_owned()
+}
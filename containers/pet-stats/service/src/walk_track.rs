@@ -0,0 +1,235 @@
+use chrono::{DateTime, FixedOffset};
+use thiserror::Error;
+
+/// Mean radius of the earth in meters.
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Upper bound for points in a track, about 14 hours at one point per second.
+pub const MAX_TRACK_POINTS: usize = 50_000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TrackError {
+    #[error("Invalid GPX: {0}")]
+    InvalidGpx(String),
+    #[error("Coordinate {0} is out of range")]
+    InvalidCoordinate(usize),
+    #[error("A track needs at least two points")]
+    TooShort,
+    #[error("A track can have at most {MAX_TRACK_POINTS} points")]
+    TooLong,
+    #[error("The track is too far to store in meters")]
+    TooFar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackPoint {
+    pub lat: f64,
+    pub lon: f64,
+    pub at: Option<DateTime<FixedOffset>>,
+}
+
+/// Points of the tracks in a GPX document in order, or of its routes if it has no tracks.
+///
+/// Stops at the first point past [`MAX_TRACK_POINTS`] instead of reading all of them.
+pub fn parse_gpx(gpx: &str) -> Result<Vec<TrackPoint>, TrackError> {
+    let document =
+        roxmltree::Document::parse(gpx).map_err(|e| TrackError::InvalidGpx(e.to_string()))?;
+    let points_named = |name: &str| -> Result<Vec<TrackPoint>, TrackError> {
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name(name))
+            .take(MAX_TRACK_POINTS + 1)
+            .map(|node| {
+                let coordinate = |attribute: &str| {
+                    node.attribute(attribute)
+                        .and_then(|value| value.trim().parse::<f64>().ok())
+                        .ok_or_else(|| {
+                            TrackError::InvalidGpx(format!("{name} without a valid {attribute}"))
+                        })
+                };
+                let at = node
+                    .children()
+                    .find(|child| child.has_tag_name("time"))
+                    .and_then(|time| time.text())
+                    .and_then(|time| DateTime::parse_from_rfc3339(time.trim()).ok());
+                Ok(TrackPoint {
+                    lat: coordinate("lat")?,
+                    lon: coordinate("lon")?,
+                    at,
+                })
+            })
+            .collect()
+    };
+
+    let mut points = points_named("trkpt")?;
+    if points.is_empty() {
+        points = points_named("rtept")?;
+    }
+    if points.len() > MAX_TRACK_POINTS {
+        return Err(TrackError::TooLong);
+    }
+    Ok(points)
+}
+
+/// Great-circle distance between two points in meters.
+pub fn haversine_m(a: &TrackPoint, b: &TrackPoint) -> f64 {
+    let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.lon - a.lon).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * h.sqrt().min(1.0).asin()
+}
+
+/// Encode the points in Google's polyline format with five decimal places.
+pub fn encode_polyline(points: &[TrackPoint]) -> String {
+    fn encode_value(value: i64, out: &mut String) {
+        let mut value = if value < 0 { !(value << 1) } else { value << 1 };
+        while value >= 0x20 {
+            out.push(char::from((0x20 | (value & 0x1f)) as u8 + 63));
+            value >>= 5;
+        }
+        out.push(char::from(value as u8 + 63));
+    }
+
+    let mut out = String::new();
+    let (mut last_lat, mut last_lon) = (0, 0);
+    for point in points {
+        let lat = (point.lat * 1e5).round() as i64;
+        let lon = (point.lon * 1e5).round() as i64;
+        encode_value(lat - last_lat, &mut out);
+        encode_value(lon - last_lon, &mut out);
+        (last_lat, last_lon) = (lat, lon);
+    }
+    out
+}
+
+/// A walk's route as stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    pub polyline: String,
+    pub point_count: i32,
+    pub distance_m: i32,
+    /// Time of the first and last point, when the points are timed.
+    pub started_at: Option<DateTime<FixedOffset>>,
+    pub ended_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Track {
+    pub fn from_points(points: &[TrackPoint]) -> Result<Self, TrackError> {
+        if points.len() < 2 {
+            return Err(TrackError::TooShort);
+        }
+        if points.len() > MAX_TRACK_POINTS {
+            return Err(TrackError::TooLong);
+        }
+        if let Some(i) = points
+            .iter()
+            .position(|p| !(-90.0..=90.0).contains(&p.lat) || !(-180.0..=180.0).contains(&p.lon))
+        {
+            return Err(TrackError::InvalidCoordinate(i));
+        }
+
+        let distance_m: f64 = points.windows(2).map(|w| haversine_m(&w[0], &w[1])).sum();
+        // Casting a float to an int saturates, the conversion to i32 is what can fail.
+        let distance_m =
+            i32::try_from(distance_m.round() as i64).map_err(|_| TrackError::TooFar)?;
+        let (started_at, ended_at) = match (points[0].at, points[points.len() - 1].at) {
+            (Some(start), Some(end)) if start <= end => (Some(start), Some(end)),
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            polyline: encode_polyline(points),
+            point_count: points.len() as i32,
+            distance_m,
+            started_at,
+            ended_at,
+        })
+    }
+
+    pub fn duration_seconds(&self) -> Option<i64> {
+        Some((self.ended_at? - self.started_at?).num_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lon: f64) -> TrackPoint {
+        TrackPoint { lat, lon, at: None }
+    }
+
+    #[test]
+    fn test_polyline_matches_reference_encoding() {
+        let points = [
+            point(38.5, -120.2),
+            point(40.7, -120.95),
+            point(43.252, -126.453),
+        ];
+        assert_eq!(encode_polyline(&points), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        // One degree of latitude.
+        let d = haversine_m(&point(0.0, 0.0), &point(1.0, 0.0));
+        assert!((d - 111_195.0).abs() < 1.0);
+        assert_eq!(haversine_m(&point(51.5, -0.1), &point(51.5, -0.1)), 0.0);
+    }
+
+    #[test]
+    fn test_track_from_gpx() {
+        let gpx = r#"<?xml version="1.0"?>
+            <gpx version="1.1" xmlns="http://www.topografix.com/GPX/1/1">
+              <trk><trkseg>
+                <trkpt lat="37.5665" lon="126.9780"><time>2026-10-18T07:00:00Z</time></trkpt>
+                <trkpt lat="37.5675" lon="126.9780"><time>2026-10-18T07:01:30Z</time></trkpt>
+                <trkpt lat="37.5675" lon="126.9792"><time>2026-10-18T07:03:00Z</time></trkpt>
+              </trkseg></trk>
+            </gpx>"#;
+        let track = Track::from_points(&parse_gpx(gpx).unwrap()).unwrap();
+        assert_eq!(track.point_count, 3);
+        assert_eq!(track.distance_m, 217);
+        assert_eq!(track.duration_seconds(), Some(180));
+    }
+
+    #[test]
+    fn test_invalid_tracks() {
+        assert!(matches!(parse_gpx("<gpx>"), Err(TrackError::InvalidGpx(_))));
+        assert_eq!(
+            parse_gpx(r#"<gpx><trk><trkseg><trkpt lat="1"/></trkseg></trk></gpx>"#),
+            Err(TrackError::InvalidGpx(
+                "trkpt without a valid lon".to_owned()
+            ))
+        );
+        assert_eq!(
+            Track::from_points(&[point(1.0, 1.0)]),
+            Err(TrackError::TooShort)
+        );
+        assert_eq!(
+            Track::from_points(&[point(1.0, 1.0), point(91.0, 1.0)]),
+            Err(TrackError::InvalidCoordinate(1))
+        );
+    }
+
+    #[test]
+    fn test_oversized_tracks_are_rejected() {
+        let trkpt = r#"<trkpt lat="1" lon="1"/>"#.repeat(MAX_TRACK_POINTS + 2);
+        let gpx = format!("<gpx><trk><trkseg>{trkpt}</trkseg></trk></gpx>");
+        assert_eq!(parse_gpx(&gpx), Err(TrackError::TooLong));
+
+        // Back and forth across the globe, about 20 000 km each way.
+        let zigzag: Vec<_> = (0..200)
+            .map(|i| point(0.0, if i % 2 == 0 { 0.0 } else { 180.0 }))
+            .collect();
+        assert_eq!(Track::from_points(&zigzag), Err(TrackError::TooFar));
+    }
+
+    #[test]
+    fn test_untimed_points_have_no_duration() {
+        let track = Track::from_points(&[point(0.0, 0.0), point(0.0, 0.001)]).unwrap();
+        assert_eq!(track.duration_seconds(), None);
+        assert_eq!(track.distance_m, 111);
+    }
+}
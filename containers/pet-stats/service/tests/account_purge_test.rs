@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Local, NaiveDate};
+use config::db_config::PetStatCentralDbConfig;
+use entity::entities::{
+    pet_members, pets,
+    sea_orm_active_enums::{DateDurationType, LoginType, PetRole, PetSexType},
+    species, users,
+};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Database, DatabaseBackend, DatabaseConnection,
+    DbErr, EntityTrait, MockDatabase, MockExecResult, QueryFilter, Transaction, Value,
+};
+use service::mutations::user::UserMutation;
+
+fn user(id: i32) -> users::Model {
+    let now = Local::now().fixed_offset();
+    users::Model {
+        id,
+        email: None,
+        password_hash: None,
+        login_type: LoginType::Oauth,
+        created_at: now,
+        updated_at: now,
+        deletion_scheduled_at: Some(now - Duration::days(1)),
+        timezone: "UTC".to_owned(),
+        tokens_revoked_before: Some(now - Duration::days(31)),
+        disabled_at: None,
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+#[tokio::test]
+async fn test_failing_account_does_not_block_the_others() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            BTreeMap::from([("id", Value::Int(Some(1)))]),
+            BTreeMap::from([("id", Value::Int(Some(2)))]),
+        ]])
+        .append_query_results([[user(1)]])
+        .append_exec_results([rows(0), rows(0)])
+        .append_exec_errors([DbErr::Custom("violates foreign key constraint".to_owned())])
+        .append_query_results([[user(2)]])
+        .append_exec_results([rows(0), rows(1), rows(1)])
+        .into_connection();
+
+    assert_eq!(
+        UserMutation::purge_scheduled_accounts(&db).await.unwrap(),
+        1
+    );
+
+    let log = db.into_transaction_log();
+    // The due accounts, then one transaction per account.
+    assert_eq!(log.len(), 3);
+    let committed = |txn: &Transaction| {
+        txn.statements()
+            .last()
+            .is_some_and(|stmt| stmt.sql == "COMMIT")
+    };
+    assert!(!committed(&log[1]));
+    assert!(committed(&log[2]));
+    assert!(log[2]
+        .statements()
+        .iter()
+        .any(|stmt| stmt.sql.starts_with(r#"DELETE FROM "users""#)));
+}
+
+async fn migrated_db() -> DatabaseConnection {
+    let db = Database::connect(PetStatCentralDbConfig::from_env().unwrap().url)
+        .await
+        .unwrap();
+    Migrator::up(&db, None).await.unwrap();
+    db
+}
+
+async fn create_user(db: &DatabaseConnection, deletion_scheduled: bool) -> users::Model {
+    let now = Local::now().fixed_offset();
+    users::ActiveModel {
+        login_type: Set(LoginType::Oauth),
+        deletion_scheduled_at: Set(deletion_scheduled.then(|| now - Duration::days(1))),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+async fn add_member(db: &DatabaseConnection, pet_id: i32, user_id: i32, role: PetRole) {
+    pet_members::ActiveModel {
+        pet_id: Set(pet_id),
+        user_id: Set(user_id),
+        role: Set(role),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+}
+
+async fn create_pet(db: &DatabaseConnection, user_id: i32, species_id: i32) -> pets::Model {
+    pets::ActiveModel {
+        user_id: Set(user_id),
+        name: Set("Bun".to_owned()),
+        sex: Set(PetSexType::Female),
+        species_id: Set(species_id),
+        birthday: Set(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()),
+        birthday_precision: Set(DateDurationType::FullDate),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres, run with `cargo test -- --ignored`
+async fn test_purge_hands_over_shared_pet_with_custom_species() {
+    let db = migrated_db().await;
+    let leaving = create_user(&db, true).await;
+    let staying = create_user(&db, false).await;
+
+    let rabbit = species::ActiveModel {
+        name: Set("Rabbit".to_owned()),
+        user_id: Set(Some(leaving.id)),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+    let pet = create_pet(&db, leaving.id, rabbit.id).await;
+    add_member(&db, pet.id, leaving.id, PetRole::Owner).await;
+    add_member(&db, pet.id, staying.id, PetRole::Owner).await;
+
+    assert!(UserMutation::purge_scheduled_accounts(&db).await.unwrap() >= 1);
+
+    assert!(users::Entity::find_by_id(leaving.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_none());
+    let pet = pets::Entity::find_by_id(pet.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pet.user_id, staying.id);
+    // The new owner gets a copy of the species, the original goes with its owner.
+    let copy = species::Entity::find_by_id(pet.species_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(copy.name, rabbit.name);
+    assert_eq!(copy.user_id, Some(staying.id));
+
+    users::Entity::delete_by_id(staying.id)
+        .exec(&db)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres, run with `cargo test -- --ignored`
+async fn test_purge_promotes_the_longest_standing_member_of_an_ownerless_pet() {
+    let db = migrated_db().await;
+    let leaving = create_user(&db, true).await;
+    let caretaker = create_user(&db, false).await;
+    let viewer = create_user(&db, false).await;
+
+    let dog = species::Entity::find()
+        .filter(species::Column::Code.eq("Dog"))
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    let pet = create_pet(&db, leaving.id, dog.id).await;
+    add_member(&db, pet.id, leaving.id, PetRole::Owner).await;
+    add_member(&db, pet.id, caretaker.id, PetRole::Caretaker).await;
+    add_member(&db, pet.id, viewer.id, PetRole::Viewer).await;
+
+    assert!(UserMutation::purge_scheduled_accounts(&db).await.unwrap() >= 1);
+
+    let pet = pets::Entity::find_by_id(pet.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("Pet shared with a caretaker must survive the purge");
+    assert_eq!(pet.user_id, caretaker.id);
+    let role = |user_id| {
+        let db = &db;
+        async move {
+            pet_members::Entity::find_by_id((pet.id, user_id))
+                .one(db)
+                .await
+                .unwrap()
+                .unwrap()
+                .role
+        }
+    };
+    assert_eq!(role(caretaker.id).await, PetRole::Owner);
+    assert_eq!(role(viewer.id).await, PetRole::Viewer);
+
+    for user in [caretaker, viewer] {
+        users::Entity::delete_by_id(user.id)
+            .exec(&db)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::Local;
+use config::auth_config::AuthConfig;
+use entity::entities::api_keys;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::auth::{
+    api_key::{ApiKey, ApiKeyService, ApiScope},
+    error::AuthError,
+};
+
+fn auth_config() -> AuthConfig {
+    AuthConfig {
+        google_oauth_public_key_url: "http://127.0.0.1/unused".to_owned(),
+        google_oauth_client_id: "unused".to_owned(),
+        jwt_sign_secret: "test-jwt-sign-secret".to_owned(),
+        refresh_key_hashing_secret: "test-refresh-key-hashing-secret".to_owned(),
+    }
+}
+
+fn stored_key(key: &ApiKey, scopes: &[&str]) -> api_keys::Model {
+    let now = Local::now().fixed_offset();
+    api_keys::Model {
+        id: 4,
+        user_id: 9,
+        name: "feeder".to_owned(),
+        key_prefix: key.display_prefix(),
+        key_hash: key
+            .hash(auth_config().refresh_key_hashing_secret.as_bytes())
+            .to_vec(),
+        scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        last_used_at: Some(now),
+        revoked_at: None,
+        created_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_authenticate_resolves_owner_and_scopes() {
+    let key = ApiKey::generate().unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[stored_key(&key, &["feed:write", "unknown:scope"])]])
+        .into_connection();
+
+    let principal = ApiKeyService::authenticate(&db, &auth_config(), &key)
+        .await
+        .unwrap();
+    assert_eq!(principal.user_id, 9);
+    assert_eq!(principal.scopes, vec![ApiScope::FeedWrite]);
+    assert!(principal.allows(ApiScope::FeedWrite));
+    assert!(!principal.allows(ApiScope::PetsRead));
+
+    let log = db.into_transaction_log();
+    let update = log[0].statements()[0].sql.to_owned();
+    assert!(update.starts_with(r#"UPDATE "api_keys""#));
+    assert!(update.contains(r#""revoked_at" IS NULL"#));
+}
+
+#[tokio::test]
+async fn test_authenticate_skips_accounts_pending_deletion_or_disabled() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<api_keys::Model>::new()])
+        .into_connection();
+
+    let key = ApiKey::generate().unwrap();
+    let result = ApiKeyService::authenticate(&db, &auth_config(), &key).await;
+    assert!(matches!(result, Err(AuthError::InvalidToken)));
+
+    let log = db.into_transaction_log();
+    let update = log[0].statements()[0].sql.to_owned();
+    assert!(update.contains(
+        r#""user_id" IN (SELECT "id" FROM "users" WHERE "users"."deletion_scheduled_at" IS NULL AND "users"."disabled_at" IS NULL)"#
+    ));
+}
+
+#[tokio::test]
+async fn test_authenticate_rejects_unknown_or_revoked_key() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<api_keys::Model>::new()])
+        .into_connection();
+
+    let key = ApiKey::generate().unwrap();
+    let result = ApiKeyService::authenticate(&db, &auth_config(), &key).await;
+    assert!(matches!(result, Err(AuthError::InvalidToken)));
+}
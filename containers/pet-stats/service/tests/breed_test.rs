@@ -0,0 +1,51 @@
+use chrono::Local;
+use entity::entities::breeds;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::queries::breed::{weight_status, BreedQuery, WeightStatus};
+
+fn breed(name: &str, weight_kg: Option<(f32, f32)>) -> breeds::Model {
+    breeds::Model {
+        id: 1,
+        species_id: 1,
+        name: name.to_owned(),
+        min_weight_kg: weight_kg.map(|(min, _)| min),
+        max_weight_kg: weight_kg.map(|(_, max)| max),
+        min_life_expectancy_years: 10,
+        max_life_expectancy_years: 15,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+#[test]
+fn test_weight_status_against_breed_range() {
+    let beagle = breed("Beagle", Some((9.0, 11.0)));
+    assert_eq!(weight_status(Some(8.2), &beagle), Some(WeightStatus::Under));
+    assert_eq!(
+        weight_status(Some(9.0), &beagle),
+        Some(WeightStatus::Within)
+    );
+    assert_eq!(
+        weight_status(Some(11.0), &beagle),
+        Some(WeightStatus::Within)
+    );
+    assert_eq!(weight_status(Some(13.5), &beagle), Some(WeightStatus::Over));
+    assert_eq!(weight_status(None, &beagle), None);
+
+    let goldfish = breed("Goldfish", None);
+    assert_eq!(weight_status(Some(0.1), &goldfish), None);
+}
+
+#[tokio::test]
+async fn test_breeds_are_listed_per_species() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[breed("Beagle", Some((9.0, 11.0)))]])
+        .into_connection();
+
+    let breeds = BreedQuery::list(&db, Some(1)).await.unwrap();
+    assert_eq!(breeds.len(), 1);
+
+    let log = db.into_transaction_log();
+    let sql = log[0].statements()[0].sql.to_owned();
+    assert!(sql.contains(r#"WHERE "breeds"."species_id" = $1"#));
+    assert!(sql.contains(r#"ORDER BY "breeds"."species_id" ASC, "breeds"."name" ASC"#));
+}
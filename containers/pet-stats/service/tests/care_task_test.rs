@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, NaiveDate};
+use entity::entities::care_tasks;
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::mutations::care_task::CareTaskMutation;
+use service::queries::care_task::{CareTaskQuery, CareTaskStatus};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+}
+
+fn care_task(id: i32, rrule: &str, starts_on: NaiveDate) -> care_tasks::Model {
+    let now = Local::now().fixed_offset();
+    care_tasks::Model {
+        id,
+        pet_id: 4,
+        title: format!("Task {}", id),
+        notes: None,
+        rrule: rrule.to_owned(),
+        starts_on,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn last_completed(care_task_id: i32, due_on: NaiveDate) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("care_task_id", Value::from(care_task_id)),
+        ("due_on", Value::from(due_on)),
+    ])
+}
+
+#[tokio::test]
+async fn test_agenda_sorts_open_occurrences_by_status() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            // Weekly on Mondays, done on the 12th: the 19th is today.
+            care_task(1, "FREQ=WEEKLY;BYDAY=MO", day(5)),
+            // Every 3 days, never done: open since the 1st.
+            care_task(2, "FREQ=DAILY;INTERVAL=3", day(1)),
+            // Monthly on the 22nd.
+            care_task(3, "FREQ=MONTHLY", day(22)),
+            // Finished after two occurrences, both done.
+            care_task(4, "FREQ=DAILY;COUNT=2", day(1)),
+            // Next occurrence falls outside the window.
+            care_task(5, "FREQ=YEARLY", day(30)),
+            care_task(6, "not a rule", day(1)),
+        ]])
+        .append_query_results([[last_completed(1, day(12)), last_completed(4, day(2))]])
+        .into_connection();
+
+    let agenda = CareTaskQuery::agenda(&db, 3, day(19), day(26))
+        .await
+        .unwrap();
+    let summary: Vec<_> = agenda
+        .iter()
+        .map(|item| (item.care_task.id, item.due_on, item.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (2, day(1), CareTaskStatus::Overdue),
+            (1, day(19), CareTaskStatus::DueToday),
+            (3, day(22), CareTaskStatus::Upcoming),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_complete_care_task_upserts_the_occurrence() {
+    let completion = entity::entities::care_task_completions::Model {
+        id: 7,
+        care_task_id: 1,
+        due_on: day(19),
+        note: Some("Trimmed front paws".to_owned()),
+        completed_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[completion]])
+        .into_connection();
+
+    let completion =
+        CareTaskMutation::complete_care_task(&db, 1, day(19), Some("Trimmed front paws".into()))
+            .await
+            .unwrap();
+    assert_eq!(completion.due_on, day(19));
+
+    let log = db.into_transaction_log();
+    let insert = log[0].statements()[0].sql.to_owned();
+    assert!(insert.contains(r#"ON CONFLICT ("care_task_id", "due_on") DO UPDATE SET"#));
+}
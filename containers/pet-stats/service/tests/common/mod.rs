@@ -0,0 +1,133 @@
+//! Test support: an in-process stand-in for Google's OIDC key endpoint.
+//!
+//! `OidcStandIn` generates an RSA key pair, serves its public half as a JWKS over plain
+//! HTTP on a random local port and mints RS256 ID tokens signed with the private half,
+//! so the sign-in flow can be exercised without touching the network.
+#![allow(dead_code)]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use config::auth_config::AuthConfig;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use openssl::rsa::Rsa;
+use service::auth::google::{GoogleClaims, GoogleOAuth};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+pub const CLIENT_ID: &str = "pet-stats-test-client";
+pub const ISSUER: &str = "https://accounts.google.com";
+pub const KID: &str = "pet-stats-test-kid";
+
+pub struct OidcStandIn {
+    url: String,
+    encoding_key: EncodingKey,
+    hits: Arc<AtomicUsize>,
+}
+
+impl OidcStandIn {
+    /// Start serving the JWKS with `Cache-Control: public, max-age=3600`.
+    pub async fn start() -> Self {
+        Self::start_with_cache_control("public, max-age=3600").await
+    }
+
+    /// Start serving the JWKS with the given `Cache-Control` header value.
+    pub async fn start_with_cache_control(cache_control: &'static str) -> Self {
+        let rsa = Rsa::generate(2048).unwrap();
+        let encoding_key = EncodingKey::from_rsa_pem(&rsa.private_key_to_pem().unwrap()).unwrap();
+        let jwks = format!(
+            r#"{{"keys":[{{"kty":"RSA","use":"sig","alg":"RS256","kid":"{}","n":"{}","e":"{}"}}]}}"#,
+            KID,
+            URL_SAFE_NO_PAD.encode(rsa.n().to_vec()),
+            URL_SAFE_NO_PAD.encode(rsa.e().to_vec()),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/oauth2/v3/certs", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let served = hits.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                served.fetch_add(1, Ordering::SeqCst);
+                let body = jwks.clone();
+                tokio::spawn(async move {
+                    // Requests are small GETs, one read is enough to drain the headers.
+                    let mut buf = [0u8; 4096];
+                    let _ = stream.read(&mut buf).await;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\n\
+                         Content-Type: application/json\r\n\
+                         Cache-Control: {}\r\n\
+                         Content-Length: {}\r\n\
+                         Connection: close\r\n\r\n{}",
+                        cache_control,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self {
+            url,
+            encoding_key,
+            hits,
+        }
+    }
+
+    pub fn jwks_url(&self) -> String {
+        self.url.to_owned()
+    }
+
+    /// Number of JWKS downloads served so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    pub fn google_oauth(&self) -> GoogleOAuth {
+        GoogleOAuth::new(CLIENT_ID.to_owned(), self.jwks_url()).unwrap()
+    }
+
+    pub fn auth_config(&self) -> AuthConfig {
+        AuthConfig {
+            google_oauth_public_key_url: self.jwks_url(),
+            google_oauth_client_id: CLIENT_ID.to_owned(),
+            jwt_sign_secret: "test-jwt-sign-secret".to_owned(),
+            refresh_key_hashing_secret: "test-refresh-key-hashing-secret".to_owned(),
+        }
+    }
+
+    /// Sign `claims` as an RS256 ID token with the served key.
+    pub fn mint(&self, claims: &GoogleClaims) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(KID.to_owned());
+        encode(&header, claims, &self.encoding_key).unwrap()
+    }
+}
+
+/// Claims Google would issue for `sub` to this client, valid for an hour.
+pub fn valid_claims(sub: &str) -> GoogleClaims {
+    let now = Utc::now();
+    GoogleClaims {
+        iss: ISSUER.to_owned(),
+        sub: sub.to_owned(),
+        aud: CLIENT_ID.to_owned(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        email: Some(format!("{}@example.com", sub)),
+        email_verified: Some(true),
+        name: Some("Test".to_owned()),
+    }
+}
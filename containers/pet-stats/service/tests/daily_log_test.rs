@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, NaiveDate};
+use entity::entities::{elimination_records, sea_orm_active_enums::EliminationType};
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::mutations::elimination_record::EliminationRecordMutation;
+use service::queries::{
+    elimination_record::{DailyElimination, EliminationRecordQuery},
+    water_record::{DailyWaterIntake, WaterRecordQuery},
+};
+
+#[tokio::test]
+async fn test_urine_has_no_consistency() {
+    let now = Local::now().fixed_offset();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[elimination_records::Model {
+            id: 1,
+            pet_id: 2,
+            kind: EliminationType::Urine,
+            consistency: None,
+            created_at: now,
+            updated_at: now,
+        }]])
+        .into_connection();
+
+    EliminationRecordMutation::add_elimination_record(
+        &db,
+        2,
+        EliminationType::Urine,
+        Some(4),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let log = db.into_transaction_log();
+    let values = &log[0].statements()[0].values.as_ref().unwrap().0;
+    assert_eq!(values[2], Value::Int(None));
+}
+
+#[tokio::test]
+async fn test_daily_eliminations_in_user_timezone() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[BTreeMap::from([
+            (
+                "day",
+                Value::from(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()),
+            ),
+            ("urinations", Value::from(4i64)),
+            ("stools", Value::from(2i64)),
+            ("average_consistency", Value::from(3.5f64)),
+        ])]])
+        .into_connection();
+
+    let from = DateTime::parse_from_rfc3339("2026-10-18T00:00:00+09:00").unwrap();
+    let until = DateTime::parse_from_rfc3339("2026-10-19T00:00:00+09:00").unwrap();
+    let days = EliminationRecordQuery::daily(&db, 2, "Asia/Seoul", from, until)
+        .await
+        .unwrap();
+    assert_eq!(
+        days,
+        [DailyElimination {
+            day: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            urinations: 4,
+            stools: 2,
+            average_consistency: Some(3.5),
+        }]
+    );
+
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(statement
+        .sql
+        .contains(r#"("created_at" AT TIME ZONE $2)::date AS "day""#));
+    assert_eq!(
+        statement.values.as_ref().unwrap().0[1],
+        Value::from("Asia/Seoul")
+    );
+}
+
+#[tokio::test]
+async fn test_daily_water_with_unknown_timezone_is_utc() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[BTreeMap::from([
+            (
+                "day",
+                Value::from(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()),
+            ),
+            ("total_ml", Value::from(420i64)),
+            ("drinks", Value::from(6i64)),
+        ])]])
+        .into_connection();
+
+    let from = DateTime::parse_from_rfc3339("2026-10-18T00:00:00+00:00").unwrap();
+    let until = DateTime::parse_from_rfc3339("2026-10-19T00:00:00+00:00").unwrap();
+    let days = WaterRecordQuery::daily(&db, 2, "Mars/Olympus_Mons", from, until)
+        .await
+        .unwrap();
+    assert_eq!(
+        days,
+        [DailyWaterIntake {
+            day: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            total_ml: 420,
+            drinks: 6,
+        }]
+    );
+
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(statement.sql.contains(r#"SUM("amount_ml") AS "total_ml""#));
+    assert_eq!(statement.values.as_ref().unwrap().0[1], Value::from("UTC"));
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use entity::entities::devices;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use service::ingest::DeviceEvent;
+use service::mutations::device::{DeviceMutation, IngestSummary};
+
+fn exec(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+#[tokio::test]
+async fn test_resent_events_are_skipped() {
+    let device = devices::Model {
+        id: 3,
+        user_id: 1,
+        pet_id: 2,
+        uid: "dev_abc".to_owned(),
+        name: "Collar".to_owned(),
+        last_seen_at: None,
+        created_at: Local::now().fixed_offset(),
+    };
+    let at = DateTime::parse_from_rfc3339("2026-10-18T07:00:00Z").unwrap();
+    let events = [
+        DeviceEvent::Feed {
+            id: "f-1".to_owned(),
+            at,
+            amount_g: Some(40.0),
+        },
+        DeviceEvent::Activity {
+            id: "a-1".to_owned(),
+            at,
+            distance_m: Some(1800),
+            duration_minutes: Some(32),
+        },
+    ];
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([exec(0), exec(1)])
+        .append_query_results([[BTreeMap::from([("id", Value::from(7))])]])
+        .append_exec_results([exec(1), exec(1), exec(1)])
+        .into_connection();
+
+    let summary = DeviceMutation::ingest(&db, &device, &events).await.unwrap();
+    assert_eq!(
+        summary,
+        IngestSummary {
+            accepted: 1,
+            duplicates: 1,
+        }
+    );
+
+    let log = db.into_transaction_log();
+    // Between BEGIN and COMMIT.
+    let statements = log[0].statements();
+    assert!(statements[1]
+        .sql
+        .contains(r#"ON CONFLICT ("device_id", "event_id") DO NOTHING"#));
+    assert!(statements[3]
+        .sql
+        .starts_with(r#"INSERT INTO "work_records""#));
+    assert!(statements[4]
+        .sql
+        .contains(r#""time" = make_interval(secs => $1)"#));
+    assert!(statements[5]
+        .sql
+        .starts_with(r#"UPDATE "device_events" SET "work_record_id" = $1"#));
+    assert_eq!(statements[5].values.as_ref().unwrap().0[0], Value::from(7));
+    assert!(statements[6].sql.contains(r#""last_seen_at" = $1"#));
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use entity::entities::sea_orm_active_enums::ExpenseCategory;
+use sea_orm::{prelude::Decimal, DatabaseBackend, MockDatabase, Value};
+use service::queries::expense::{ExpenseGrouping, ExpenseQuery, ExpenseTotal};
+
+fn row(
+    month: Option<NaiveDate>,
+    currency: &str,
+    total: Decimal,
+    count: i64,
+) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("month", Value::from(month)),
+        ("category", Value::String(None)),
+        ("pet_id", Value::Int(None)),
+        ("currency", Value::from(currency)),
+        ("total", Value::from(total)),
+        ("count", Value::from(count)),
+    ])
+}
+
+#[tokio::test]
+async fn test_report_is_summed_in_sql_per_currency() {
+    let october = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            row(Some(october), "EUR", Decimal::new(4250, 2), 2),
+            row(Some(october), "KRW", Decimal::new(55000, 0), 1),
+        ]])
+        .into_connection();
+
+    let report = ExpenseQuery::report(
+        &db,
+        1,
+        october,
+        NaiveDate::from_ymd_opt(2026, 10, 31).unwrap(),
+        None,
+        &[ExpenseGrouping::Month],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        report[0],
+        ExpenseTotal {
+            month: Some(october),
+            category: None,
+            pet_id: None,
+            currency: "EUR".to_owned(),
+            total: Decimal::new(4250, 2),
+            count: 2,
+        }
+    );
+
+    assert_eq!(report[1].total, Decimal::new(55000, 0));
+
+    let log = db.into_transaction_log();
+    let sql = log[0].statements()[0].sql.to_owned();
+    assert!(sql.contains(r#"SUM("expenses"."amount") AS "total""#));
+    assert!(sql.contains(r#"NULL::text AS "category""#));
+    assert!(sql.contains(
+        r#"GROUP BY date_trunc('month', "expenses"."spent_on")::date, "expenses"."currency""#
+    ));
+}
+
+#[tokio::test]
+async fn test_report_by_category_and_pet() {
+    let mut total = row(None, "EUR", Decimal::new(1999, 2), 1);
+    total.insert("category", Value::from("Toys"));
+    total.insert("pet_id", Value::from(7));
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[total]])
+        .into_connection();
+
+    let report = ExpenseQuery::report(
+        &db,
+        1,
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+        Some(7),
+        &[ExpenseGrouping::Category, ExpenseGrouping::Pet],
+    )
+    .await
+    .unwrap();
+    assert_eq!(report[0].category, Some(ExpenseCategory::Toys));
+    assert_eq!(report[0].pet_id, Some(7));
+    assert_eq!(report[0].month, None);
+
+    let log = db.into_transaction_log();
+    let sql = log[0].statements()[0].sql.to_owned();
+    assert!(sql.contains(
+        r#"GROUP BY CAST("expenses"."category" AS text), "expenses"."pet_id", "expenses"."currency""#
+    ));
+    assert!(sql.contains(r#""expenses"."pet_id" = $4"#));
+}
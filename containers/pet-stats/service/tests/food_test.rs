@@ -0,0 +1,59 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::{feed_records, foods, sea_orm_active_enums::FoodUnit};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::nutrition::CalorieIntake;
+use service::queries::feed_record::FeedRecordQuery;
+use service::schedule::day_bounds_in;
+
+fn kibble() -> foods::Model {
+    foods::Model {
+        id: 4,
+        user_id: 1,
+        brand: "Acme".to_owned(),
+        name: "Adult Kibble".to_owned(),
+        kcal_per_100g: Some(350.0),
+        kcal_per_cup: None,
+        unit: FoodUnit::Gram,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+fn feeding(amount: f32, food_id: Option<i32>) -> feed_records::Model {
+    let now = Local::now().fixed_offset();
+    feed_records::Model {
+        id: 1,
+        pet_id: 7,
+        amount: Some(amount),
+        created_at: now,
+        updated_at: now,
+        food_id,
+        unit: Some(FoodUnit::Gram),
+    }
+}
+
+#[tokio::test]
+async fn test_daily_intake_sums_feedings_of_the_day() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            (feeding(80.0, Some(4)), Some(kibble())),
+            (feeding(120.0, Some(4)), Some(kibble())),
+            (feeding(30.0, None), None),
+        ]])
+        .into_connection();
+
+    let date = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+    let (from, until) = day_bounds_in("Asia/Seoul", date).unwrap();
+    let feedings = FeedRecordQuery::with_food_between(&db, 7, from, until)
+        .await
+        .unwrap();
+
+    let intake = CalorieIntake::of(&feedings);
+    assert_eq!(intake.kcal, 700.0);
+    assert_eq!(intake.unknown_feedings, 1);
+
+    let log = db.into_transaction_log();
+    let sql = log[0].statements()[0].sql.to_owned();
+    assert!(sql.contains(r#"LEFT JOIN "foods" ON "feed_records"."food_id" = "foods"."id""#));
+    assert!(sql.contains(r#""feed_records"."created_at" >= $2"#));
+    assert!(sql.contains(r#""feed_records"."created_at" < $3"#));
+}
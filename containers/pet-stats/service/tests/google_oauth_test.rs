@@ -1,16 +1,16 @@
-use chrono::{Duration, Utc};
+mod common;
+
+use common::{valid_claims, OidcStandIn, CLIENT_ID};
 use config::auth_config::AuthConfig;
 use config::base_config::Config;
-use jsonwebtoken::{jwk::JwkSet, Header};
-use openssl::rsa::Rsa;
+use jsonwebtoken::jwk::JwkSet;
 use rest::client::HttpClientBuilder;
 use service::auth::{
-    google::{GoogleClaims, GoogleOAuth},
+    error::AuthError,
+    google::{unverified_subject, GoogleOAuth},
     oauth_provider::OAuthProvider,
 };
 
-static GOOGLE_ISSUERS: &[&str] = &["https://accounts.google.com", "accounts.google.com"];
-
 async fn fetch_first_kid() -> String {
     let auth_config = AuthConfig::new().unwrap();
 
@@ -35,36 +35,91 @@ async fn fetch_first_kid() -> String {
 
 #[tokio::test]
 async fn test_google_oauth_creation() {
-    let result = GoogleOAuth::new("test_client_id".to_string());
+    let result = GoogleOAuth::new(
+        "test_client_id".to_string(),
+        "https://www.googleapis.com/oauth2/v3/certs".to_string(),
+    );
     assert!(result.is_ok());
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_valid_verify_token_has_to_success() {
-    let kid = fetch_first_kid().await;
-    let rsa = Rsa::generate(2048).unwrap();
-    // PKCS#1 PEM
-    let priv_pem_pkcs1 = rsa.private_key_to_pem().unwrap();
-    let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
-    header.kid = Some(kid);
-
-    let claims = GoogleClaims {
-        iss: GOOGLE_ISSUERS.join(" "),
-        aud: "client_id".to_owned(),
-        sub: "sub".to_owned(),
-        exp: (Utc::now() + Duration::hours(1)).timestamp(),
-        iat: Utc::now().timestamp(),
-        email: Some("user@example.com".to_owned()),
-        email_verified: Some(true),
-        name: Some("Test".to_owned()),
-    };
+    let oidc = OidcStandIn::start().await;
+    let google_oauth = oidc.google_oauth();
+
+    let token = oidc.mint(&valid_claims("sub"));
+    let claims = google_oauth.verify_token(&token).await.unwrap();
+
+    assert_eq!(claims.sub, "sub");
+    assert_eq!(claims.aud, CLIENT_ID);
+    assert_eq!(claims.email.as_deref(), Some("sub@example.com"));
+}
+
+#[tokio::test]
+async fn test_verify_token_with_unknown_kid_fails() {
+    let oidc = OidcStandIn::start().await;
+    let google_oauth = oidc.google_oauth();
+
+    let token = oidc.mint(&valid_claims("sub"));
+    let result = google_oauth.fetch_public_key("rotated-away").await;
+    assert!(matches!(result, Err(AuthError::InvalidToken)));
+
+    // The set fetched for the unknown kid is reused for the known one.
+    assert!(google_oauth.verify_token(&token).await.is_ok());
+    assert_eq!(oidc.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_verifications_download_jwks_once() {
+    let oidc = OidcStandIn::start().await;
+    let google_oauth = oidc.google_oauth();
+
+    let tasks: Vec<_> = (0..16)
+        .map(|i| {
+            let google_oauth = google_oauth.clone();
+            let token = oidc.mint(&valid_claims(&format!("sub-{}", i)));
+            tokio::spawn(async move { google_oauth.verify_token(&token).await })
+        })
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+
+    assert_eq!(oidc.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_verifications_share_an_uncacheable_download() {
+    let oidc = OidcStandIn::start_with_cache_control("no-cache").await;
+    let google_oauth = oidc.google_oauth();
+
+    let tasks: Vec<_> = (0..16)
+        .map(|i| {
+            let google_oauth = google_oauth.clone();
+            let token = oidc.mint(&valid_claims(&format!("sub-{}", i)));
+            tokio::spawn(async move { google_oauth.verify_token(&token).await })
+        })
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+    assert_eq!(oidc.hits(), 1);
+
+    // Later callers don't reuse it.
+    let token = oidc.mint(&valid_claims("later"));
+    assert!(google_oauth.verify_token(&token).await.is_ok());
+    assert_eq!(oidc.hits(), 2);
 }
 
 #[tokio::test]
 #[ignore] // 기본 테스트에서는 제외, `cargo test -- --ignored`로 실행
 async fn test_fetch_real_google_jwk() {
-    let google_oauth = GoogleOAuth::new("test_client_id".to_string()).unwrap();
+    let auth_config = AuthConfig::new().unwrap();
+    let google_oauth = GoogleOAuth::new(
+        "test_client_id".to_string(),
+        auth_config.google_oauth_public_key_url,
+    )
+    .unwrap();
 
     // 실제 Google에서 사용되는 키 ID로 테스트 (구글에서 실제로 사용하는 kid)
     // 이 키들은 주기적으로 로테이션되므로, 테스트가 실패할 수 있습니다.
@@ -82,3 +137,13 @@ async fn test_fetch_real_google_jwk() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_unverified_subject_reads_sub_without_keys() {
+    let oidc = OidcStandIn::start().await;
+    let token = oidc.mint(&valid_claims("peek"));
+
+    assert_eq!(unverified_subject(&token).as_deref(), Some("peek"));
+    assert_eq!(unverified_subject("not-a-token"), None);
+    assert_eq!(oidc.hits(), 0);
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::queries::journal::{JournalQuery, SymptomCount};
+
+fn at(timestamp: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(timestamp).unwrap()
+}
+
+#[tokio::test]
+async fn test_entries_filtered_by_all_symptoms() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<entity::entities::journal_entries::Model>::new()])
+        .into_connection();
+
+    JournalQuery::by_pet(
+        &db,
+        3,
+        vec!["vomiting".to_owned(), "limping".to_owned()],
+        Some(at("2026-10-01T00:00:00+02:00")),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(statement
+        .sql
+        .contains(r#""journal_entries"."symptoms" @> $2"#));
+    assert!(statement
+        .sql
+        .contains(r#""journal_entries"."noted_at" >= $3"#));
+    assert!(statement
+        .sql
+        .ends_with(r#"ORDER BY "journal_entries"."noted_at" DESC, "journal_entries"."id" DESC"#));
+    assert_eq!(
+        statement.values.as_ref().unwrap().0[1],
+        Value::from(vec!["vomiting".to_owned(), "limping".to_owned()])
+    );
+}
+
+#[tokio::test]
+async fn test_symptom_frequency_is_counted_in_sql() {
+    let last = at("2026-10-12T08:30:00+02:00");
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[BTreeMap::from([
+            ("symptom", Value::from("vomiting")),
+            ("entries", Value::from(3i64)),
+            ("last_noted_at", Value::from(last)),
+        ])]])
+        .into_connection();
+
+    let summary = JournalQuery::symptom_frequency(
+        &db,
+        3,
+        at("2026-10-01T00:00:00+02:00"),
+        at("2026-11-01T00:00:00+01:00"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        summary,
+        [SymptomCount {
+            symptom: "vomiting".to_owned(),
+            entries: 3,
+            last_noted_at: last,
+        }]
+    );
+
+    let log = db.into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains(r#"unnest("symptoms") AS "symptom""#));
+    assert!(sql.contains(r#"GROUP BY "symptom""#));
+}
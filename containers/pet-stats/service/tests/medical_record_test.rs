@@ -0,0 +1,65 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::vaccinations;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use service::mutations::vet_visit::VetVisitMutation;
+use service::queries::vaccination::VaccinationQuery;
+
+#[tokio::test]
+async fn test_upcoming_vaccinations_skip_doses_followed_by_a_booster() {
+    let now = Local::now().fixed_offset();
+    let due = vaccinations::Model {
+        id: 2,
+        pet_id: 5,
+        vaccine: "Rabies".to_owned(),
+        given_on: NaiveDate::from_ymd_opt(2025, 11, 1).unwrap(),
+        next_due_on: NaiveDate::from_ymd_opt(2026, 11, 1),
+        clinic: None,
+        created_at: now,
+        updated_at: now,
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[due]])
+        .into_connection();
+
+    let until = NaiveDate::from_ymd_opt(2026, 11, 18).unwrap();
+    let upcoming = VaccinationQuery::upcoming_for_user(&db, 3, until)
+        .await
+        .unwrap();
+    assert_eq!(upcoming.len(), 1);
+
+    let log = db.into_transaction_log();
+    let select = log[0].statements()[0].sql.to_owned();
+    assert!(select.contains(r#"INNER JOIN "pets""#));
+    assert!(select.contains(
+        r#""pets"."id" IN (SELECT "pet_id" FROM "pet_members" WHERE "pet_members"."user_id" = $1)"#
+    ));
+    assert!(select.contains(r#"NOT EXISTS(SELECT"#));
+    assert!(select.contains(r#""later"."given_on" > "vaccinations"."given_on""#));
+    assert!(select.ends_with(r#"ORDER BY "vaccinations"."next_due_on" ASC"#));
+}
+
+#[tokio::test]
+async fn test_remove_vet_visit_is_scoped_to_the_owner() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .into_connection();
+
+    let removed = VetVisitMutation::remove_vet_visit(&db, 3, 11)
+        .await
+        .unwrap();
+    assert_eq!(removed, 0);
+
+    let log = db.into_transaction_log();
+    let delete = log[0].statements()[0].sql.to_owned();
+    // Viewers can't remove records.
+    assert!(delete.contains(
+        r#""vet_visits"."pet_id" IN (SELECT "pet_id" FROM "pet_members" WHERE "pet_members"."user_id" = $2 AND "pet_members"."role" IN (CAST($3 AS "pet_role"), CAST($4 AS "pet_role")))"#
+    ));
+    assert_eq!(
+        log[0].statements()[0].values.as_ref().unwrap().0[2..],
+        [Value::from("Owner"), Value::from("Caretaker")]
+    );
+}
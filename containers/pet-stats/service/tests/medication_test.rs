@@ -0,0 +1,96 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    medication_doses, medications,
+    sea_orm_active_enums::{DoseStatus, MedicationFrequency},
+};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::mutations::medication::MedicationMutation;
+use service::queries::medication::MedicationQuery;
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+}
+
+fn medication(id: i32, frequency: MedicationFrequency, doses_per_day: i32) -> medications::Model {
+    let now = Local::now().fixed_offset();
+    medications::Model {
+        id,
+        pet_id: 4,
+        name: "Meloxicam".to_owned(),
+        dose: 0.5,
+        unit: "ml".to_owned(),
+        frequency,
+        doses_per_day,
+        start_on: day(1),
+        end_on: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_due_doses_expand_per_day_and_carry_the_log() {
+    let given = medication_doses::Model {
+        id: 30,
+        medication_id: 1,
+        scheduled_on: day(19),
+        dose_number: 1,
+        status: DoseStatus::Given,
+        recorded_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            medication(1, MedicationFrequency::Daily, 2),
+            // Started on the 1st, so not due on the 19th.
+            medication(2, MedicationFrequency::Weekly, 1),
+        ]])
+        .append_query_results([[given]])
+        .into_connection();
+
+    let due = MedicationQuery::due_doses(&db, 3, day(19)).await.unwrap();
+    assert_eq!(due.len(), 2);
+    assert!(due.iter().all(|d| d.medication.id == 1));
+    assert_eq!(due[0].dose_number, 1);
+    assert_eq!(
+        due[0].logged.as_ref().map(|l| &l.status),
+        Some(&DoseStatus::Given)
+    );
+    assert_eq!(due[1].dose_number, 2);
+    assert!(due[1].logged.is_none());
+}
+
+#[tokio::test]
+async fn test_nothing_due_skips_the_dose_lookup() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[medication(2, MedicationFrequency::EveryOtherDay, 1)]])
+        .into_connection();
+
+    let due = MedicationQuery::due_doses(&db, 3, day(2)).await.unwrap();
+    assert!(due.is_empty());
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
+
+#[tokio::test]
+async fn test_record_dose_overwrites_an_earlier_mark() {
+    let skipped = medication_doses::Model {
+        id: 31,
+        medication_id: 1,
+        scheduled_on: day(19),
+        dose_number: 2,
+        status: DoseStatus::Skipped,
+        recorded_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[skipped]])
+        .into_connection();
+
+    let dose = MedicationMutation::record_dose(&db, 1, day(19), 2, DoseStatus::Skipped)
+        .await
+        .unwrap();
+    assert_eq!(dose.status, DoseStatus::Skipped);
+
+    let log = db.into_transaction_log();
+    let insert = log[0].statements()[0].sql.to_owned();
+    assert!(insert
+        .contains(r#"ON CONFLICT ("medication_id", "scheduled_on", "dose_number") DO UPDATE SET"#));
+}
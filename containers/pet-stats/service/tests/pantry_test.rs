@@ -0,0 +1,72 @@
+use chrono::{DateTime, Local, NaiveDate};
+use entity::entities::{feed_records, food_purchases, foods, sea_orm_active_enums::FoodUnit};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::queries::pantry::PantryQuery;
+
+fn kibble() -> foods::Model {
+    foods::Model {
+        id: 4,
+        user_id: 1,
+        brand: "Acme".to_owned(),
+        name: "Adult Kibble".to_owned(),
+        kcal_per_100g: Some(350.0),
+        kcal_per_cup: None,
+        unit: FoodUnit::Gram,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+fn purchase(size: f32, purchased_on: NaiveDate) -> food_purchases::Model {
+    food_purchases::Model {
+        id: 1,
+        food_id: 4,
+        size,
+        unit: FoodUnit::Gram,
+        purchased_on,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+fn feeding(amount: f32, fed_at: &str) -> feed_records::Model {
+    let fed_at = DateTime::parse_from_rfc3339(fed_at).unwrap();
+    feed_records::Model {
+        id: 1,
+        pet_id: 7,
+        amount: Some(amount),
+        created_at: fed_at,
+        updated_at: fed_at,
+        food_id: Some(4),
+        unit: Some(FoodUnit::Gram),
+    }
+}
+
+#[tokio::test]
+async fn test_stock_subtracts_feedings_on_the_users_days() {
+    let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(purchase(1000.0, day(18)), Some(kibble()))]])
+        .append_query_results([vec![
+            // Oct 18 09:00 in Seoul.
+            feeding(150.0, "2026-10-18T00:00:00+00:00"),
+            feeding(150.0, "2026-10-19T08:00:00+09:00"),
+        ]])
+        .into_connection();
+
+    let stock = PantryQuery::stock(&db, 1, "Asia/Seoul", day(19))
+        .await
+        .unwrap();
+    assert_eq!(stock.len(), 1);
+    assert_eq!(stock[0].remaining, 700.0);
+    assert_eq!(stock[0].daily_use, 150.0);
+    assert_eq!(stock[0].runs_out_on, Some(day(23)));
+
+    let log = db.into_transaction_log();
+    let sql = log[1].statements()[0].sql.to_owned();
+    assert!(sql.contains(r#""feed_records"."food_id" IN ($1)"#));
+    assert_eq!(
+        log[1].statements()[0].values.as_ref().unwrap().0[1],
+        DateTime::parse_from_rfc3339("2026-10-18T00:00:00+09:00")
+            .unwrap()
+            .into()
+    );
+}
@@ -0,0 +1,82 @@
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    pets,
+    sea_orm_active_enums::{DateDurationType, PetSexType},
+};
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::queries::pet::PetQuery;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn pet(id: i32, birthday: NaiveDate, birthday_precision: DateDurationType) -> pets::Model {
+    let now = Local::now().fixed_offset();
+    pets::Model {
+        id,
+        user_id: 3,
+        name: format!("Pet {}", id),
+        sex: PetSexType::Female,
+        species_id: 2,
+        birthday,
+        birthday_precision,
+        feed_count: Some(2),
+        feed_count_per: None,
+        weight: None,
+        is_disabled: false,
+        breed_id: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_upcoming_birthdays_span_the_new_year() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            pet(1, date(2020, 1, 3), DateDurationType::FullDate),
+            pet(2, date(2019, 12, 1), DateDurationType::Month),
+            // Born within the window, the birth date itself is no birthday.
+            pet(3, date(2026, 12, 30), DateDurationType::FullDate),
+        ]])
+        .into_connection();
+
+    let upcoming = PetQuery::upcoming_birthdays(&db, 3, date(2026, 12, 28), date(2027, 1, 27))
+        .await
+        .unwrap();
+    let summary: Vec<_> = upcoming
+        .iter()
+        .map(|b| (b.pet.id, b.on, b.turning))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(2, date(2026, 12, 1), 7), (1, date(2027, 1, 3), 7)]
+    );
+
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    // One anniversary range per calendar year the window touches.
+    assert_eq!(statement.sql.matches("make_birth_range").count(), 2);
+    let values = &statement.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::from(2026)));
+    assert!(values.contains(&Value::from(2027)));
+}
+
+#[tokio::test]
+async fn test_possibly_born_in_compares_birth_ranges() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[pet(1, date(2020, 1, 1), DateDurationType::Year)]])
+        .into_connection();
+
+    let pets = PetQuery::possibly_born_in(&db, 3, 2020, Some(6))
+        .await
+        .unwrap();
+    assert_eq!(pets.len(), 1);
+
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(statement.sql.contains("&& make_birth_range("));
+    let values = &statement.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::from(2020)));
+    assert!(values.contains(&Value::from(Some(6))));
+}
@@ -0,0 +1,102 @@
+use chrono::{Duration, Local};
+use entity::entities::{pet_invitations, pet_members, sea_orm_active_enums::PetRole};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use service::membership::MembershipError;
+use service::mutations::pet_member::PetMemberMutation;
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+fn member(user_id: i32, role: PetRole) -> pet_members::Model {
+    pet_members::Model {
+        pet_id: 2,
+        user_id,
+        role,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+#[tokio::test]
+async fn test_accepting_an_invitation_upgrades_a_lower_role() {
+    let invitation = pet_invitations::Model {
+        id: 1,
+        pet_id: 2,
+        invited_by: 3,
+        role: PetRole::Caretaker,
+        code_hash: vec![0; 32],
+        expires_at: Local::now().fixed_offset() + Duration::days(1),
+        created_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[invitation]])
+        .append_query_results([
+            [member(4, PetRole::Viewer)],
+            [member(4, PetRole::Caretaker)],
+        ])
+        .into_connection();
+
+    let joined = PetMemberMutation::accept_invitation(&db, 4, &[0; 32])
+        .await
+        .unwrap();
+    assert_eq!(joined.role, PetRole::Caretaker);
+
+    let log = db.into_transaction_log();
+    // Between BEGIN and COMMIT.
+    let statements = log[0].statements();
+    assert!(statements[1]
+        .sql
+        .starts_with(r#"DELETE FROM "pet_invitations""#));
+    assert!(statements[1].sql.contains(r#""expires_at" > $2"#));
+    assert!(statements[2].sql.ends_with("FOR UPDATE"));
+    assert!(statements[3].sql.starts_with(r#"UPDATE "pet_members""#));
+}
+
+#[tokio::test]
+async fn test_unknown_invitation_is_rejected() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<pet_invitations::Model>::new()])
+        .into_connection();
+
+    let res = PetMemberMutation::accept_invitation(&db, 4, &[0; 32]).await;
+    assert!(matches!(res, Err(MembershipError::InvalidInvitation)));
+}
+
+#[tokio::test]
+async fn test_last_owner_cannot_leave() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[member(3, PetRole::Owner)]])
+        .append_query_results([[member(3, PetRole::Owner), member(4, PetRole::Owner)]])
+        .append_exec_results([rows(1), rows(0)])
+        .into_connection();
+
+    let res = PetMemberMutation::remove_member(&db, 2, 3).await;
+    assert!(matches!(res, Err(MembershipError::LastOwner)));
+
+    // With a second owner around it works.
+    assert!(PetMemberMutation::remove_member(&db, 2, 3).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_removed_member_loses_their_devices_of_the_pet() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[member(3, PetRole::Owner)]])
+        .append_exec_results([rows(1), rows(2)])
+        .into_connection();
+
+    assert!(PetMemberMutation::remove_member(&db, 2, 4).await.unwrap());
+
+    let log = db.into_transaction_log();
+    let statements = log[0].statements();
+    assert!(statements[2]
+        .sql
+        .starts_with(r#"DELETE FROM "pet_members""#));
+    assert!(statements[3].sql.starts_with(r#"DELETE FROM "devices""#));
+    assert!(statements[3]
+        .sql
+        .contains(r#""pet_id" = $1 AND "devices"."user_id" = $2"#));
+    assert_eq!(statements[4].sql, "COMMIT");
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, NaiveDate};
+use entity::entities::{
+    pets,
+    sea_orm_active_enums::{DateDurationType, PetSexType},
+    vet_visits,
+};
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::queries::search::{SearchItem, SearchQuery};
+
+fn ranked(kind: &str, id: i32, rank: f32) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("kind", Value::from(kind)),
+        ("id", Value::from(id)),
+        ("rank", Value::from(rank)),
+    ])
+}
+
+fn pet(id: i32) -> pets::Model {
+    let now = Local::now().fixed_offset();
+    pets::Model {
+        id,
+        user_id: 3,
+        name: "Earl".to_owned(),
+        sex: PetSexType::Male,
+        species_id: 1,
+        birthday: NaiveDate::from_ymd_opt(2020, 5, 1).unwrap(),
+        birthday_precision: DateDurationType::FullDate,
+        feed_count: None,
+        feed_count_per: None,
+        weight: None,
+        is_disabled: false,
+        breed_id: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn vet_visit(id: i32) -> vet_visits::Model {
+    let now = Local::now().fixed_offset();
+    vet_visits::Model {
+        id,
+        pet_id: 1,
+        visited_on: NaiveDate::from_ymd_opt(2026, 9, 14).unwrap(),
+        reason: "Ear infection".to_owned(),
+        diagnosis: Some("Otitis externa".to_owned()),
+        notes: None,
+        cost_cents: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_results_keep_rank_order_across_kinds() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            ranked("VetVisit", 4, 0.6),
+            ranked("Pet", 1, 0.2),
+            ranked("VetVisit", 9, 0.1),
+        ]])
+        .append_query_results([[pet(1)]])
+        .append_query_results([[vet_visit(4)]])
+        .into_connection();
+
+    let results = SearchQuery::search(&db, 3, "ear infection", 20)
+        .await
+        .unwrap();
+
+    // Visit 9 was deleted after it was ranked.
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].rank, 0.6);
+    assert!(matches!(&results[0].item, SearchItem::VetVisit(visit) if visit.id == 4));
+    assert!(matches!(&results[1].item, SearchItem::Pet(pet) if pet.id == 1));
+
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 3, "no journal entries are loaded");
+    let statement = &log[0].statements()[0];
+    assert!(statement
+        .sql
+        .contains("websearch_to_tsquery('english', $2)"));
+    assert!(statement.sql.contains(r#"ORDER BY "rank" DESC"#));
+    assert_eq!(
+        statement.values.as_ref().unwrap().0,
+        [
+            Value::from(3),
+            Value::from("ear infection"),
+            Value::from(20u64)
+        ]
+    );
+}
@@ -0,0 +1,71 @@
+use chrono::Local;
+use entity::entities::{sea_orm_active_enums::SecurityEventType, security_events};
+use sea_orm::{DatabaseBackend, MockDatabase, Value};
+use service::auth::rejected_tokens::RejectedAccessTokens;
+use service::mutations::security_event::{EventOrigin, SecurityEventMutation};
+
+#[tokio::test]
+async fn test_record_clips_client_supplied_values() {
+    let stored = security_events::Model {
+        id: 1,
+        user_id: Some(3),
+        event_type: SecurityEventType::SignIn,
+        ip_address: Some("203.0.113.7".to_owned()),
+        user_agent: Some("a".repeat(512)),
+        device_id: None,
+        created_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[stored]])
+        .into_connection();
+
+    let origin = EventOrigin {
+        ip_address: Some("203.0.113.7".to_owned()),
+        user_agent: Some("a".repeat(4096)),
+        device_id: None,
+    };
+    let event = SecurityEventMutation::record(&db, Some(3), SecurityEventType::SignIn, &origin)
+        .await
+        .unwrap();
+    assert_eq!(event.user_id, Some(3));
+
+    let log = db.into_transaction_log();
+    let insert = log[0].statements()[0].to_owned();
+    assert!(insert.sql.starts_with(r#"INSERT INTO "security_events""#));
+    let clipped = insert
+        .values
+        .unwrap()
+        .0
+        .into_iter()
+        .any(|v| v == Value::String(Some(Box::new("a".repeat(512)))));
+    assert!(clipped, "User agent must be cut to the column size");
+}
+
+#[tokio::test]
+async fn test_rejected_access_tokens_are_flushed_once_per_client() {
+    let stored = security_events::Model {
+        id: 1,
+        user_id: None,
+        event_type: SecurityEventType::AccessTokenRejected,
+        ip_address: Some("203.0.113.7".to_owned()),
+        user_agent: None,
+        device_id: None,
+        created_at: Local::now().fixed_offset(),
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[stored]])
+        .into_connection();
+
+    let rejections = RejectedAccessTokens::new();
+    let origin = EventOrigin {
+        ip_address: Some("203.0.113.7".to_owned()),
+        ..Default::default()
+    };
+    for _ in 0..50 {
+        rejections.report(None, origin.clone());
+    }
+
+    assert_eq!(rejections.flush(&db).await.unwrap(), 1);
+    assert_eq!(rejections.flush(&db).await.unwrap(), 0);
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use chrono::{Duration, Local, Utc};
+use common::{valid_claims, OidcStandIn};
+use entity::entities::{
+    oauth_accounts,
+    sea_orm_active_enums::{LoginType, ProviderType},
+    user_tokens, users,
+};
+use jwt::verify_jwt;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use service::auth::{error::AuthError, sign_in::SignInService};
+
+fn user(id: i32, email: &str) -> users::Model {
+    let now = Local::now().fixed_offset();
+    users::Model {
+        id,
+        email: Some(email.to_owned()),
+        password_hash: None,
+        login_type: LoginType::Oauth,
+        created_at: now,
+        updated_at: now,
+        deletion_scheduled_at: None,
+        tokens_revoked_before: None,
+        disabled_at: None,
+        timezone: "UTC".to_owned(),
+    }
+}
+
+fn oauth_account(user_id: i32, sub: &str) -> oauth_accounts::Model {
+    let now = Local::now().fixed_offset();
+    oauth_accounts::Model {
+        user_id,
+        id: 1,
+        provider_user_id: sub.to_owned(),
+        id_token: None,
+        extra_data: None,
+        created_at: now,
+        updated_at: now,
+        provider_type: ProviderType::Google,
+    }
+}
+
+fn user_token(user_id: i32) -> user_tokens::Model {
+    let now = Local::now().fixed_offset();
+    user_tokens::Model {
+        id: 1,
+        user_id,
+        device_id: None,
+        refresh_token: vec![0; 32],
+        created_at: now,
+        updated_at: now,
+        expires_at: now + Duration::days(2),
+        revoked: Some(false),
+    }
+}
+
+fn statements(db: DatabaseConnection) -> Vec<String> {
+    db.into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().iter().map(|stmt| stmt.sql.to_owned()))
+        .collect()
+}
+
+fn executed(log: &[String], sql: &str) -> bool {
+    log.iter().any(|stmt| stmt.starts_with(sql))
+}
+
+#[tokio::test]
+async fn test_sign_in_creates_new_user() {
+    let oidc = OidcStandIn::start().await;
+    let auth_config = oidc.auth_config();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<users::Model>::new()])
+        .append_query_results([[user(7, "new@example.com")]])
+        .append_query_results([[oauth_account(7, "new")]])
+        .append_query_results([[user_token(7)]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("new"));
+    let tokens =
+        SignInService::sign_in_with_google(&db, &oidc.google_oauth(), &auth_config, &token)
+            .await
+            .unwrap();
+
+    assert_eq!(tokens.user.id, 7);
+    let claims = verify_jwt(&tokens.access_token, auth_config.jwt_sign_secret).unwrap();
+    assert_eq!(claims.sub, 7);
+    assert!(!tokens.refresh_token.0.is_empty());
+
+    let log = statements(db);
+    assert!(executed(&log, r#"INSERT INTO "users""#));
+    assert!(executed(&log, r#"INSERT INTO "oauth_accounts""#));
+    assert!(executed(&log, r#"INSERT INTO "user_tokens""#));
+}
+
+#[tokio::test]
+async fn test_sign_in_returning_user() {
+    let oidc = OidcStandIn::start().await;
+    let auth_config = oidc.auth_config();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[user(3, "back@example.com")]])
+        .append_query_results([[user_token(3)]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("back"));
+    let tokens =
+        SignInService::sign_in_with_google(&db, &oidc.google_oauth(), &auth_config, &token)
+            .await
+            .unwrap();
+
+    assert_eq!(tokens.user.id, 3);
+    let claims = verify_jwt(&tokens.access_token, auth_config.jwt_sign_secret).unwrap();
+    assert_eq!(claims.email.as_deref(), Some("back@example.com"));
+
+    let log = statements(db);
+    assert!(!executed(&log, r#"INSERT INTO "users""#));
+    assert!(executed(&log, r#"INSERT INTO "user_tokens""#));
+}
+
+#[tokio::test]
+async fn test_sign_in_during_deletion_grace_period_keeps_account() {
+    let oidc = OidcStandIn::start().await;
+    let mut leaving = user(5, "leaving@example.com");
+    leaving.deletion_scheduled_at = Some(Local::now().fixed_offset() + Duration::days(3));
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[leaving]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([[user_token(5)]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("leaving"));
+    SignInService::sign_in_with_google(&db, &oidc.google_oauth(), &oidc.auth_config(), &token)
+        .await
+        .unwrap();
+
+    let log = statements(db);
+    assert!(executed(
+        &log,
+        r#"UPDATE "users" SET "deletion_scheduled_at""#
+    ));
+}
+
+#[tokio::test]
+async fn test_sign_in_to_disabled_account_fails() {
+    let oidc = OidcStandIn::start().await;
+    let mut disabled = user(6, "disabled@example.com");
+    disabled.disabled_at = Some(Local::now().fixed_offset());
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[disabled]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("disabled"));
+    let err =
+        SignInService::sign_in_with_google(&db, &oidc.google_oauth(), &oidc.auth_config(), &token)
+            .await
+            .unwrap_err();
+    assert!(matches!(err, AuthError::AccountDisabled));
+    assert!(!executed(&statements(db), r#"INSERT INTO "user_tokens""#));
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_fresh_token_of_same_account() {
+    let oidc = OidcStandIn::start().await;
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[user(3, "me@example.com")]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("me"));
+    let result =
+        SignInService::reauthenticate_with_google(&db, &oidc.google_oauth(), 3, &token).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_another_account_fails() {
+    let oidc = OidcStandIn::start().await;
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[user(4, "other@example.com")]])
+        .into_connection();
+
+    let token = oidc.mint(&valid_claims("other"));
+    let err = SignInService::reauthenticate_with_google(&db, &oidc.google_oauth(), 3, &token)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidToken));
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_stale_token_fails() {
+    let oidc = OidcStandIn::start().await;
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+    let mut claims = valid_claims("me");
+    claims.iat = (Utc::now() - Duration::minutes(30)).timestamp();
+    let token = oidc.mint(&claims);
+    let err = SignInService::reauthenticate_with_google(&db, &oidc.google_oauth(), 3, &token)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::StaleAuthentication));
+}
+
+async fn sign_in_rejected(claims: service::auth::google::GoogleClaims) -> AuthError {
+    let oidc = OidcStandIn::start().await;
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+    let token = oidc.mint(&claims);
+    let err =
+        SignInService::sign_in_with_google(&db, &oidc.google_oauth(), &oidc.auth_config(), &token)
+            .await
+            .unwrap_err();
+
+    assert!(
+        statements(db).is_empty(),
+        "Rejected tokens must not reach DB"
+    );
+    err
+}
+
+#[tokio::test]
+async fn test_sign_in_with_bad_audience_fails() {
+    let mut claims = valid_claims("sub");
+    claims.aud = "someone-elses-client".to_owned();
+
+    let err = sign_in_rejected(claims).await;
+    assert!(matches!(err, AuthError::InvalidToken));
+}
+
+#[tokio::test]
+async fn test_sign_in_with_expired_token_fails() {
+    let mut claims = valid_claims("sub");
+    claims.iat = (Utc::now() - Duration::hours(2)).timestamp();
+    claims.exp = (Utc::now() - Duration::hours(1)).timestamp();
+
+    let err = sign_in_rejected(claims).await;
+    assert!(matches!(err, AuthError::TokenExpired));
+}
+
+#[tokio::test]
+async fn test_sign_in_with_wrong_issuer_fails() {
+    let mut claims = valid_claims("sub");
+    claims.iss = "https://accounts.example.com".to_owned();
+
+    let err = sign_in_rejected(claims).await;
+    assert!(matches!(err, AuthError::InvalidToken));
+}
@@ -0,0 +1,63 @@
+use chrono::Local;
+use entity::entities::species;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use service::queries::species::SpeciesQuery;
+
+fn species(id: i32, code: Option<&str>, name: &str, user_id: Option<i32>) -> species::Model {
+    species::Model {
+        id,
+        code: code.map(str::to_owned),
+        name: name.to_owned(),
+        user_id,
+        adult_from_months: None,
+        senior_from_months: None,
+        created_at: Local::now().fixed_offset(),
+    }
+}
+
+#[tokio::test]
+async fn test_available_species_are_shared_and_own() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            species(1, Some("Dog"), "Dog", None),
+            species(7, None, "Rabbit", Some(3)),
+        ]])
+        .into_connection();
+
+    let available = SpeciesQuery::available(&db, 3).await.unwrap();
+    assert_eq!(available.len(), 2);
+
+    let log = db.into_transaction_log();
+    let sql = log[0].statements()[0].sql.to_owned();
+    assert!(sql.contains(r#""species"."user_id" IS NULL OR "species"."user_id" = $1"#));
+    assert!(sql.contains(r#"ORDER BY "species"."user_id" ASC NULLS FIRST, "species"."name" ASC"#));
+}
+
+#[tokio::test]
+async fn test_someone_elses_species_is_not_usable() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<species::Model>::new()])
+        .into_connection();
+
+    let result = SpeciesQuery::usable_by(&db, 3, 8).await;
+    assert!(matches!(result, Err(sea_orm::DbErr::RecordNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_species_are_loaded_in_one_query() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[
+            species(1, Some("Dog"), "Dog", None),
+            species(7, None, "Rabbit", Some(3)),
+        ]])
+        .into_connection();
+
+    let loaded = SpeciesQuery::by_ids(&db, &[1, 7]).await.unwrap();
+    assert_eq!(loaded.len(), 2);
+
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 1);
+    assert!(log[0].statements()[0]
+        .sql
+        .contains(r#""species"."id" IN ($1, $2)"#));
+}
@@ -0,0 +1,25 @@
+use chrono::{Duration, Local};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use service::mutations::user::UserMutation;
+
+#[tokio::test]
+async fn test_purge_stale_tokens_deletes_one_bounded_batch() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 250,
+        }])
+        .into_connection();
+
+    let cutoff = (Local::now() - Duration::days(7)).fixed_offset();
+    let removed = UserMutation::purge_stale_tokens(&db, cutoff, 250)
+        .await
+        .unwrap();
+    assert_eq!(removed, 250);
+
+    let log = db.into_transaction_log();
+    let delete = log[0].statements()[0].sql.to_owned();
+    assert!(delete.starts_with(r#"DELETE FROM "user_tokens" WHERE "user_tokens"."id" IN (SELECT"#));
+    assert!(delete.contains(r#""user_tokens"."expires_at" < $1 OR ("user_tokens"."revoked" = $2"#));
+    assert!(delete.contains("LIMIT $4"));
+}
@@ -0,0 +1,123 @@
+use chrono::{Duration, Local, Utc};
+use entity::entities::{revoked_access_tokens, sea_orm_active_enums::LoginType, users};
+use jwt::{Claims, UnixTimestamp};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use service::auth::token_revocation::TokenRevocationList;
+
+fn claims(jti: &str) -> Claims {
+    let now = Utc::now();
+    Claims {
+        sub: 7,
+        email: None,
+        iat: UnixTimestamp::from(now),
+        exp: UnixTimestamp::from(now + Duration::minutes(30)),
+        jti: jti.to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn test_revoked_token_is_rejected_until_it_expires() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    let revocations = TokenRevocationList::new();
+
+    revocations.revoke(&db, &claims("jti-1")).await.unwrap();
+    assert!(revocations.is_revoked(&claims("jti-1")));
+    assert!(!revocations.is_revoked(&claims("jti-2")));
+
+    let log = db.into_transaction_log();
+    let insert = log[0].statements()[0].sql.to_owned();
+    assert!(insert.starts_with(r#"INSERT INTO "revoked_access_tokens""#));
+    assert!(insert.contains("ON CONFLICT"));
+}
+
+#[tokio::test]
+async fn test_token_without_jti_is_not_persisted() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let revocations = TokenRevocationList::new();
+
+    revocations.revoke(&db, &claims("")).await.unwrap();
+    assert!(!revocations.is_revoked(&claims("")));
+    assert!(db.into_transaction_log().is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_picks_up_revocations_from_other_instances() {
+    let now = Local::now().fixed_offset();
+    let elsewhere = revoked_access_tokens::Model {
+        jti: "revoked-elsewhere".to_owned(),
+        user_id: 7,
+        expires_at: now + Duration::minutes(10),
+        created_at: now,
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![elsewhere]])
+        .append_query_results([Vec::<users::Model>::new()])
+        .append_query_results([Vec::<revoked_access_tokens::Model>::new()])
+        .append_query_results([Vec::<users::Model>::new()])
+        .into_connection();
+    let revocations = TokenRevocationList::new();
+
+    assert_eq!(revocations.sync(&db).await.unwrap(), 1);
+    assert!(revocations.is_revoked(&claims("revoked-elsewhere")));
+    assert_eq!(revocations.sync(&db).await.unwrap(), 1);
+
+    let log = db.into_transaction_log();
+    assert!(!log[0].statements()[0].sql.contains(r#""created_at" >="#));
+    assert!(log[2].statements()[0].sql.contains(r#""created_at" >="#));
+}
+
+fn cut_off_user(id: i32, at: chrono::DateTime<chrono::FixedOffset>) -> users::Model {
+    users::Model {
+        id,
+        email: None,
+        password_hash: None,
+        login_type: LoginType::Oauth,
+        created_at: at,
+        updated_at: at,
+        deletion_scheduled_at: None,
+        tokens_revoked_before: Some(at),
+        disabled_at: Some(at),
+        timezone: "UTC".to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn test_tokens_issued_before_cutoff_are_rejected() {
+    let revocations = TokenRevocationList::new();
+    let before = claims("jti-1");
+    revocations.revoke_issued_before(7, Local::now().fixed_offset() + Duration::seconds(1));
+
+    assert!(revocations.is_revoked(&before));
+    assert!(revocations.is_revoked(&claims("")));
+
+    let mut other_user = claims("jti-2");
+    other_user.sub = 8;
+    assert!(!revocations.is_revoked(&other_user));
+
+    let mut later = claims("jti-3");
+    later.iat = UnixTimestamp::from(Utc::now() + Duration::seconds(2));
+    assert!(!revocations.is_revoked(&later));
+}
+
+#[tokio::test]
+async fn test_sync_picks_up_cutoffs_from_other_instances() {
+    let at = Local::now().fixed_offset() + Duration::seconds(1);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<revoked_access_tokens::Model>::new()])
+        .append_query_results([vec![cut_off_user(7, at)]])
+        .into_connection();
+    let revocations = TokenRevocationList::new();
+
+    assert_eq!(revocations.sync(&db).await.unwrap(), 1);
+    assert!(revocations.is_revoked(&claims("jti-1")));
+
+    let log = db.into_transaction_log();
+    assert!(log[1].statements()[0]
+        .sql
+        .contains(r#""users"."tokens_revoked_before" >"#));
+}
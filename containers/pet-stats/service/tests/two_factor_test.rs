@@ -0,0 +1,267 @@
+use chrono::{Duration, Local, Utc};
+use config::auth_config::AuthConfig;
+use entity::entities::{
+    sea_orm_active_enums::LoginType, two_factor_challenges, user_tokens, user_totp, users,
+};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use service::auth::{
+    error::AuthError,
+    password::hash_password,
+    sign_in::{LocalSignIn, SignInService, CHALLENGE_MAX_ATTEMPTS},
+    totp::{TotpSecret, TOTP_STEP_SECS},
+    two_factor::TwoFactorService,
+};
+use service::mutations::two_factor::TwoFactorMutation;
+
+const SECRET: &[u8] = b"12345678901234567890";
+
+fn auth_config() -> AuthConfig {
+    AuthConfig {
+        google_oauth_public_key_url: "http://127.0.0.1/unused".to_owned(),
+        google_oauth_client_id: "unused".to_owned(),
+        jwt_sign_secret: "test-jwt-sign-secret".to_owned(),
+        refresh_key_hashing_secret: "test-refresh-key-hashing-secret".to_owned(),
+    }
+}
+
+fn local_user(id: i32, password: &str) -> users::Model {
+    let now = Local::now().fixed_offset();
+    users::Model {
+        id,
+        email: Some("local@example.com".to_owned()),
+        password_hash: Some(hash_password(password).unwrap()),
+        login_type: LoginType::Local,
+        created_at: now,
+        updated_at: now,
+        deletion_scheduled_at: None,
+        tokens_revoked_before: None,
+        disabled_at: None,
+        timezone: "UTC".to_owned(),
+    }
+}
+
+fn confirmed_totp(user_id: i32) -> user_totp::Model {
+    let now = Local::now().fixed_offset();
+    user_totp::Model {
+        user_id,
+        secret: SECRET.to_vec(),
+        confirmed_at: Some(now),
+        last_used_step: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn challenge(user_id: i32) -> two_factor_challenges::Model {
+    let now = Local::now().fixed_offset();
+    two_factor_challenges::Model {
+        id: 1,
+        user_id,
+        challenge_hash: vec![0; 32],
+        attempts: 1,
+        expires_at: now + Duration::minutes(5),
+        created_at: now,
+    }
+}
+
+fn user_token(user_id: i32) -> user_tokens::Model {
+    let now = Local::now().fixed_offset();
+    user_tokens::Model {
+        id: 1,
+        user_id,
+        device_id: None,
+        refresh_token: vec![0; 32],
+        created_at: now,
+        updated_at: now,
+        expires_at: now + Duration::days(2),
+        revoked: Some(false),
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+fn statements(db: DatabaseConnection) -> Vec<String> {
+    db.into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().iter().map(|stmt| stmt.sql.to_owned()))
+        .collect()
+}
+
+fn current_code() -> String {
+    TotpSecret(SECRET.to_vec()).code_at_step(Utc::now().timestamp() / TOTP_STEP_SECS)
+}
+
+#[tokio::test]
+async fn test_local_sign_in_without_two_factor_issues_tokens() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_query_results([Vec::<user_totp::Model>::new()])
+        .append_query_results([[user_token(2)]])
+        .into_connection();
+
+    let result = SignInService::sign_in_local(&db, &auth_config(), "local@example.com", "hunter22")
+        .await
+        .unwrap();
+
+    assert!(matches!(result, LocalSignIn::Completed(tokens) if tokens.user.id == 2));
+}
+
+#[tokio::test]
+async fn test_local_sign_in_with_two_factor_returns_challenge() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_query_results([[confirmed_totp(2)]])
+        .append_exec_results([rows(1)])
+        .into_connection();
+
+    let result = SignInService::sign_in_local(&db, &auth_config(), "local@example.com", "hunter22")
+        .await
+        .unwrap();
+
+    let LocalSignIn::TwoFactorRequired(challenge) = result else {
+        panic!("Expected a two-factor challenge");
+    };
+    assert!(!challenge.token.0.is_empty());
+    assert!(challenge.expires_at > Local::now().fixed_offset());
+
+    let log = statements(db);
+    assert!(log
+        .iter()
+        .any(|sql| sql.starts_with(r#"INSERT INTO "two_factor_challenges""#)));
+    assert!(!log
+        .iter()
+        .any(|sql| sql.starts_with(r#"INSERT INTO "user_tokens""#)));
+}
+
+#[tokio::test]
+async fn test_local_sign_in_with_wrong_password_fails() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .into_connection();
+
+    let err = SignInService::sign_in_local(&db, &auth_config(), "local@example.com", "hunter2")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidCredentials));
+}
+
+#[tokio::test]
+async fn test_complete_two_factor_with_totp_code() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[challenge(2)]])
+        .append_query_results([[confirmed_totp(2)]])
+        .append_exec_results([rows(1), rows(1)])
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_query_results([[user_token(2)]])
+        .into_connection();
+
+    let tokens =
+        SignInService::complete_two_factor(&db, &auth_config(), "challenge", &current_code())
+            .await
+            .unwrap();
+    assert_eq!(tokens.user.id, 2);
+}
+
+#[tokio::test]
+async fn test_complete_two_factor_rejects_replayed_code() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[challenge(2)]])
+        .append_query_results([[confirmed_totp(2)]])
+        // The step was already used, so the conditional update matches nothing.
+        .append_exec_results([rows(0)])
+        .into_connection();
+
+    let err = SignInService::complete_two_factor(&db, &auth_config(), "challenge", &current_code())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidTwoFactorCode));
+}
+
+#[tokio::test]
+async fn test_complete_two_factor_with_exhausted_challenge_fails() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<two_factor_challenges::Model>::new()])
+        .into_connection();
+
+    let err = SignInService::complete_two_factor(&db, &auth_config(), "challenge", "123456")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidToken));
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_password_and_second_factor() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_query_results([[confirmed_totp(2)]])
+        .append_query_results([[confirmed_totp(2)]])
+        .append_exec_results([rows(1)])
+        .into_connection();
+
+    SignInService::reauthenticate_with_password(
+        &db,
+        &auth_config(),
+        2,
+        "hunter22",
+        Some(&current_code()),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_password_requires_second_factor() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_query_results([[confirmed_totp(2)]])
+        .into_connection();
+
+    let err = SignInService::reauthenticate_with_password(&db, &auth_config(), 2, "hunter22", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidTwoFactorCode));
+}
+
+#[tokio::test]
+async fn test_reauthenticate_with_wrong_password_fails() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .into_connection();
+
+    let err = SignInService::reauthenticate_with_password(&db, &auth_config(), 2, "hunter2", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AuthError::InvalidCredentials));
+}
+
+#[tokio::test]
+async fn test_enroll_when_already_enabled_keeps_the_confirmed_secret() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[local_user(2, "hunter22")]])
+        .append_exec_results([rows(0)])
+        .into_connection();
+
+    let err = TwoFactorService::enroll_totp(&db, 2).await.unwrap_err();
+    assert!(matches!(err, AuthError::TwoFactorAlreadyEnabled));
+}
+
+#[tokio::test]
+async fn test_purge_stale_challenges_deletes_expired_and_exhausted() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([rows(3)])
+        .into_connection();
+
+    let removed = TwoFactorMutation::purge_stale_challenges(&db, CHALLENGE_MAX_ATTEMPTS)
+        .await
+        .unwrap();
+    assert_eq!(removed, 3);
+
+    let log = statements(db);
+    assert!(log[0].starts_with(r#"DELETE FROM "two_factor_challenges""#));
+    assert!(log[0].contains(r#""expires_at" <= $1 OR "two_factor_challenges"."attempts" >= $2"#));
+}
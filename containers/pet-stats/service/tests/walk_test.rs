@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use entity::entities::walk_tracks;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+use service::mutations::walk::WalkMutation;
+use service::queries::walk::WalkQuery;
+use service::walk_track::{Track, TrackPoint};
+
+fn track() -> Track {
+    let at = |time: &str| Some(DateTime::parse_from_rfc3339(time).unwrap());
+    Track::from_points(&[
+        TrackPoint {
+            lat: 37.5665,
+            lon: 126.978,
+            at: at("2026-10-18T07:00:00Z"),
+        },
+        TrackPoint {
+            lat: 37.5675,
+            lon: 126.978,
+            at: at("2026-10-18T07:25:00Z"),
+        },
+    ])
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_attached_track_sets_distance_and_time() {
+    let track = track();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[walk_tracks::Model {
+            work_record_id: 5,
+            polyline: track.polyline.to_owned(),
+            point_count: track.point_count,
+            distance_m: track.distance_m,
+            started_at: track.started_at,
+            ended_at: track.ended_at,
+            created_at: Local::now().fixed_offset(),
+        }]])
+        .append_exec_results([
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .into_connection();
+
+    let stored = WalkMutation::attach_track(&db, 5, track).await.unwrap();
+    assert_eq!(stored.distance_m, 111);
+
+    let log = db.into_transaction_log();
+    // Between BEGIN and COMMIT.
+    let statements = log[0].statements();
+    assert!(statements[1]
+        .sql
+        .contains(r#"ON CONFLICT ("work_record_id") DO UPDATE"#));
+    assert!(statements[2].sql.contains(r#""distance_m" = $1"#));
+    assert!(statements[3]
+        .sql
+        .contains(r#""time" = make_interval(secs => $1)"#));
+    assert_eq!(
+        statements[3].values.as_ref().unwrap().0[0],
+        Value::from(1500i64)
+    );
+}
+
+#[tokio::test]
+async fn test_walks_read_time_as_minutes() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([[BTreeMap::from([
+            ("id", Value::from(5)),
+            ("pet_id", Value::from(2)),
+            ("distance_m", Value::from(111)),
+            ("duration_minutes", Value::from(25)),
+            ("created_at", Value::from(Local::now().fixed_offset())),
+        ])]])
+        .append_query_results([Vec::<walk_tracks::Model>::new()])
+        .into_connection();
+
+    let walks = WalkQuery::recent_by_pet(&db, 2, 10).await.unwrap();
+    assert_eq!(walks[0].0.duration_minutes, Some(25));
+    assert_eq!(walks[0].1, None);
+
+    let log = db.into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains(
+        r#"(EXTRACT(EPOCH FROM "work_records"."time") / 60)::int AS "duration_minutes""#
+    ));
+    assert!(!sql.contains(r#""work_records"."time" AS"#));
+}